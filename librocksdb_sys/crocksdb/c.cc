@@ -11,6 +11,7 @@
 
 #include <stdlib.h>
 
+#include <algorithm>
 #include <limits>
 
 #include "db/column_family.h"
@@ -54,6 +55,8 @@
 #include "rocksdb/utilities/debug.h"
 #include "rocksdb/utilities/options_util.h"
 #include "rocksdb/utilities/table_properties_collectors.h"
+#include "rocksdb/utilities/write_batch_with_index.h"
+#include "rocksdb/wal_filter.h"
 #include "rocksdb/write_batch.h"
 #include "rocksdb/write_buffer_manager.h"
 #include "src/blob_format.h"
@@ -161,8 +164,11 @@ using rocksdb::TablePropertiesCollector;
 using rocksdb::TablePropertiesCollectorFactory;
 using rocksdb::UserCollectedProperties;
 using rocksdb::WALRecoveryMode;
+using rocksdb::WalFilter;
+using rocksdb::WalProcessingOption;
 using rocksdb::WritableFile;
 using rocksdb::WriteBatch;
+using rocksdb::WriteBatchWithIndex;
 using rocksdb::WriteBufferManager;
 using rocksdb::WriteOptions;
 using rocksdb::WriteStallCondition;
@@ -245,15 +251,24 @@ struct crocksdb_restore_options_t {
 struct crocksdb_iterator_t {
   Iterator* rep;
 };
+struct crocksdb_wal_iterator_t {
+  TransactionLogIterator* rep;
+};
 struct crocksdb_writebatch_t {
   WriteBatch rep;
 };
+struct crocksdb_writebatch_wi_t {
+  WriteBatchWithIndex* rep;
+};
 struct crocksdb_snapshot_t {
   const Snapshot* rep;
 };
 struct crocksdb_flushoptions_t {
   FlushOptions rep;
 };
+struct crocksdb_size_approximation_options_t {
+  SizeApproximationOptions rep;
+};
 struct crocksdb_fifo_compaction_options_t {
   CompactionOptionsFIFO rep;
 };
@@ -261,9 +276,12 @@ struct crocksdb_readoptions_t {
   ReadOptions rep;
   Slice upper_bound;  // stack variable to set pointer to in ReadOptions
   Slice lower_bound;
+  Slice timestamp;
+  Slice iter_start_ts;
 };
 struct crocksdb_writeoptions_t {
   WriteOptions rep;
+  Slice timestamp;  // stack variable to set pointer to in WriteOptions
 };
 struct crocksdb_options_t {
   Options rep;
@@ -327,6 +345,9 @@ struct crocksdb_logger_impl_t : public Logger {
 
   void Logv(const InfoLogLevel log_level, const char* format,
             va_list ap) override {
+    if (log_level < GetInfoLogLevel()) {
+      return;
+    }
     log_help_(rep, static_cast<uint32_t>(log_level), format, ap);
   }
 
@@ -361,6 +382,7 @@ struct crocksdb_sstfilereader_t {
 };
 struct crocksdb_sstfilewriter_t {
   SstFileWriter* rep;
+  size_t ts_sz;
 };
 struct crocksdb_externalsstfileinfo_t {
   ExternalSstFileInfo rep;
@@ -472,6 +494,25 @@ struct crocksdb_compactionfilter_t : public CompactionFilter {
   virtual const char* Name() const override { return (*name_)(state_); }
 };
 
+struct crocksdb_walfilter_t : public WalFilter {
+  void* state_;
+  void (*destructor_)(void*);
+  uint32_t (*log_record_)(void*, const char* rep, size_t rep_length);
+  const char* (*name_)(void*);
+
+  virtual ~crocksdb_walfilter_t() { (*destructor_)(state_); }
+
+  virtual WalProcessingOption LogRecord(const WriteBatch& batch,
+                                        WriteBatch* /*new_batch*/,
+                                        bool* /*batch_changed*/) const override {
+    uint32_t r = (*log_record_)(state_, batch.Data().data(),
+                                batch.GetDataSize());
+    return static_cast<WalProcessingOption>(r);
+  }
+
+  virtual const char* Name() const override { return (*name_)(state_); }
+};
+
 struct crocksdb_compactionfilterfactory_t : public CompactionFilterFactory {
   void* state_;
   void (*destructor_)(void*);
@@ -520,6 +561,63 @@ struct crocksdb_comparator_t : public Comparator {
   virtual void FindShortSuccessor(std::string*) const override {}
 };
 
+// Bytewise comparator for keys suffixed with a fixed64 encoded user timestamp.
+// Keys with the same user key are ordered by descending timestamp. Uses the
+// same name as RocksDB's `BytewiseComparatorWithU64Ts` so the data is
+// compatible with it.
+class BytewiseComparatorWithU64TsImpl : public Comparator {
+ public:
+  BytewiseComparatorWithU64TsImpl()
+      : Comparator(/*ts_sz=*/sizeof(uint64_t)),
+        cmp_without_ts_(rocksdb::BytewiseComparator()) {}
+
+  static const BytewiseComparatorWithU64TsImpl* Instance() {
+    static BytewiseComparatorWithU64TsImpl instance;
+    return &instance;
+  }
+
+  const char* Name() const override {
+    return "leveldb.BytewiseComparator.u64ts";
+  }
+
+  void FindShortSuccessor(std::string*) const override {}
+  void FindShortestSeparator(std::string*, const Slice&) const override {}
+
+  int Compare(const Slice& a, const Slice& b) const override {
+    int ret = CompareWithoutTimestamp(a, b);
+    if (ret != 0) {
+      return ret;
+    }
+    size_t ts_sz = timestamp_size();
+    // Descending order of timestamps.
+    return -CompareTimestamp(Slice(a.data() + a.size() - ts_sz, ts_sz),
+                             Slice(b.data() + b.size() - ts_sz, ts_sz));
+  }
+
+  using Comparator::CompareWithoutTimestamp;
+  int CompareWithoutTimestamp(const Slice& a, bool a_has_ts, const Slice& b,
+                              bool b_has_ts) const override {
+    size_t ts_sz = timestamp_size();
+    Slice lhs = a_has_ts ? Slice(a.data(), a.size() - ts_sz) : a;
+    Slice rhs = b_has_ts ? Slice(b.data(), b.size() - ts_sz) : b;
+    return cmp_without_ts_->Compare(lhs, rhs);
+  }
+
+  int CompareTimestamp(const Slice& ts1, const Slice& ts2) const override {
+    uint64_t lhs = DecodeFixed64(ts1.data());
+    uint64_t rhs = DecodeFixed64(ts2.data());
+    if (lhs < rhs) {
+      return -1;
+    } else if (lhs > rhs) {
+      return 1;
+    }
+    return 0;
+  }
+
+ private:
+  const Comparator* cmp_without_ts_;
+};
+
 struct crocksdb_filterpolicy_t : public FilterPolicy {
   void* state_;
   void (*destructor_)(void*);
@@ -866,6 +964,12 @@ void crocksdb_backup_engine_create_new_backup(crocksdb_backup_engine_t* be,
   SaveError(errptr, be->rep->CreateNewBackup(db->rep));
 }
 
+void crocksdb_backup_engine_create_new_backup_flush(
+    crocksdb_backup_engine_t* be, crocksdb_t* db,
+    unsigned char flush_before_backup, char** errptr) {
+  SaveError(errptr, be->rep->CreateNewBackup(db->rep, flush_before_backup));
+}
+
 void crocksdb_backup_engine_purge_old_backups(crocksdb_backup_engine_t* be,
                                               uint32_t num_backups_to_keep,
                                               char** errptr) {
@@ -893,6 +997,21 @@ void crocksdb_backup_engine_restore_db_from_latest_backup(
                                                        restore_options->rep));
 }
 
+void crocksdb_backup_engine_restore_db_from_backup(
+    crocksdb_backup_engine_t* be, uint32_t backup_id, const char* db_dir,
+    const char* wal_dir, const crocksdb_restore_options_t* restore_options,
+    char** errptr) {
+  SaveError(errptr, be->rep->RestoreDBFromBackup(
+                        restore_options->rep, backup_id, std::string(db_dir),
+                        std::string(wal_dir)));
+}
+
+void crocksdb_backup_engine_verify_backup(crocksdb_backup_engine_t* be,
+                                          uint32_t backup_id, char** errptr) {
+  SaveError(errptr,
+            be->rep->VerifyBackup(backup_id, true /* verify_with_checksum */));
+}
+
 const crocksdb_backup_engine_info_t* crocksdb_backup_engine_get_backup_info(
     crocksdb_backup_engine_t* be) {
   crocksdb_backup_engine_info_t* result = new crocksdb_backup_engine_info_t;
@@ -1051,6 +1170,58 @@ crocksdb_t* crocksdb_open_for_read_only_column_families(
   return result;
 }
 
+crocksdb_t* crocksdb_open_as_secondary(const crocksdb_options_t* options,
+                                       const char* name,
+                                       const char* secondary_path,
+                                       char** errptr) {
+  DB* db;
+  if (SaveError(errptr,
+                DB::OpenAsSecondary(options->rep, std::string(name),
+                                    std::string(secondary_path), &db))) {
+    return nullptr;
+  }
+  crocksdb_t* result = new crocksdb_t;
+  result->rep = db;
+  return result;
+}
+
+crocksdb_t* crocksdb_open_as_secondary_column_families(
+    const crocksdb_options_t* db_options, const char* name,
+    const char* secondary_path, int num_column_families,
+    const char** column_family_names,
+    const crocksdb_options_t** column_family_options,
+    crocksdb_column_family_handle_t** column_family_handles, char** errptr) {
+  std::vector<ColumnFamilyDescriptor> column_families;
+  for (int i = 0; i < num_column_families; i++) {
+    column_families.push_back(ColumnFamilyDescriptor(
+        std::string(column_family_names[i]),
+        ColumnFamilyOptions(column_family_options[i]->rep)));
+  }
+
+  DB* db;
+  std::vector<ColumnFamilyHandle*> handles;
+  if (SaveError(errptr, DB::OpenAsSecondary(DBOptions(db_options->rep),
+                                            std::string(name),
+                                            std::string(secondary_path),
+                                            column_families, &handles, &db))) {
+    return nullptr;
+  }
+
+  for (size_t i = 0; i < handles.size(); i++) {
+    crocksdb_column_family_handle_t* c_handle =
+        new crocksdb_column_family_handle_t;
+    c_handle->rep = handles[i];
+    column_family_handles[i] = c_handle;
+  }
+  crocksdb_t* result = new crocksdb_t;
+  result->rep = db;
+  return result;
+}
+
+void crocksdb_try_catch_up_with_primary(crocksdb_t* db, char** errptr) {
+  SaveError(errptr, db->rep->TryCatchUpWithPrimary());
+}
+
 char** crocksdb_list_column_families(const crocksdb_options_t* options,
                                      const char* name, size_t* lencfs,
                                      char** errptr) {
@@ -1074,6 +1245,31 @@ void crocksdb_list_column_families_destroy(char** list, size_t len) {
   free(list);
 }
 
+char** crocksdb_get_live_file_names(crocksdb_t* db,
+                                    unsigned char flush_memtable,
+                                    size_t* len, uint64_t* manifest_size,
+                                    char** errptr) {
+  std::vector<std::string> files;
+  *len = 0;
+  if (SaveError(errptr,
+                db->rep->GetLiveFiles(files, manifest_size, flush_memtable))) {
+    return nullptr;
+  }
+  *len = files.size();
+  char** names = static_cast<char**>(malloc(sizeof(char*) * files.size()));
+  for (size_t i = 0; i < files.size(); i++) {
+    names[i] = strdup(files[i].c_str());
+  }
+  return names;
+}
+
+void crocksdb_live_file_names_destroy(char** list, size_t len) {
+  for (size_t i = 0; i < len; ++i) {
+    free(list[i]);
+  }
+  free(list);
+}
+
 crocksdb_column_family_handle_t* crocksdb_create_column_family(
     crocksdb_t* db, const crocksdb_options_t* column_family_options,
     const char* column_family_name, char** errptr) {
@@ -1095,6 +1291,11 @@ uint32_t crocksdb_column_family_handle_id(
   return handle->rep->GetID();
 }
 
+size_t crocksdb_column_family_handle_timestamp_size(
+    crocksdb_column_family_handle_t* handle) {
+  return handle->rep->GetComparator()->timestamp_size();
+}
+
 void crocksdb_column_family_handle_destroy(
     crocksdb_column_family_handle_t* handle) {
   delete handle->rep;
@@ -1301,6 +1502,115 @@ void crocksdb_multi_get_cf(
   }
 }
 
+void crocksdb_multi_get_cf_with_ts(
+    crocksdb_t* db, const crocksdb_readoptions_t* options,
+    const crocksdb_column_family_handle_t* const* column_families,
+    size_t num_keys, const char* const* keys_list,
+    const size_t* keys_list_sizes, void* ctx,
+    void (*on_result)(void*, size_t idx, const char* v, size_t vlen,
+                      const char* ts, size_t tslen),
+    char** errptr) {
+  std::vector<Slice> keys(num_keys);
+  std::vector<ColumnFamilyHandle*> cfs(num_keys);
+  for (size_t i = 0; i < num_keys; i++) {
+    keys[i] = Slice(keys_list[i], keys_list_sizes[i]);
+    cfs[i] = column_families[i]->rep;
+  }
+  std::vector<std::string> values(num_keys);
+  std::vector<std::string> timestamps(num_keys);
+  std::vector<Status> statuses =
+      db->rep->MultiGet(options->rep, cfs, keys, &values, &timestamps);
+  for (size_t i = 0; i < num_keys; i++) {
+    if (statuses[i].ok()) {
+      on_result(ctx, i, values[i].data(), values[i].size(),
+                timestamps[i].data(), timestamps[i].size());
+    } else {
+      on_result(ctx, i, nullptr, 0, nullptr, 0);
+      if (!statuses[i].IsNotFound() && SaveError(errptr, statuses[i])) {
+        return;
+      }
+    }
+  }
+}
+
+void crocksdb_get_versions_cf_with_ts(
+    crocksdb_t* db, const crocksdb_readoptions_t* options,
+    crocksdb_column_family_handle_t* column_family, const char* key,
+    size_t keylen, void* ctx,
+    void (*on_version)(void*, const char* v, size_t vlen, const char* ts,
+                       size_t tslen),
+    char** errptr) {
+  const size_t ts_sz = column_family->rep->GetComparator()->timestamp_size();
+  const bool all_versions = options->rep.iter_start_ts != nullptr;
+  const Slice target(key, keylen);
+  std::unique_ptr<Iterator> iter(
+      db->rep->NewIterator(options->rep, column_family->rep));
+  for (iter->Seek(target); iter->Valid(); iter->Next()) {
+    Slice user_key = iter->key();
+    bool is_value = true;
+    if (all_versions) {
+      // The iterator returns internal keys, tombstones included.
+      rocksdb::ParsedInternalKey parsed;
+      Status s = rocksdb::ParseInternalKey(user_key, &parsed,
+                                           false /* log_err_key */);
+      if (SaveError(errptr, s)) {
+        return;
+      }
+      user_key = parsed.user_key;
+      user_key.remove_suffix(ts_sz);
+      is_value = parsed.type == rocksdb::kTypeValue;
+    }
+    if (user_key != target) {
+      break;
+    }
+    if (is_value) {
+      Slice value = iter->value();
+      Slice ts = iter->timestamp();
+      on_version(ctx, value.data(), value.size(), ts.data(), ts.size());
+    }
+  }
+  SaveError(errptr, iter->status());
+}
+
+crocksdb_wal_iterator_t* crocksdb_get_updates_since(crocksdb_t* db,
+                                                    uint64_t seq_number,
+                                                    char** errptr) {
+  std::unique_ptr<TransactionLogIterator> iter;
+  if (SaveError(errptr, db->rep->GetUpdatesSince(seq_number, &iter))) {
+    return nullptr;
+  }
+  crocksdb_wal_iterator_t* result = new crocksdb_wal_iterator_t;
+  result->rep = iter.release();
+  return result;
+}
+
+unsigned char crocksdb_wal_iter_valid(const crocksdb_wal_iterator_t* iter) {
+  return iter->rep->Valid();
+}
+
+void crocksdb_wal_iter_next(crocksdb_wal_iterator_t* iter) {
+  iter->rep->Next();
+}
+
+void crocksdb_wal_iter_status(const crocksdb_wal_iterator_t* iter,
+                              char** errptr) {
+  SaveError(errptr, iter->rep->status());
+}
+
+crocksdb_writebatch_t* crocksdb_wal_iter_get_batch(
+    crocksdb_wal_iterator_t* iter, uint64_t* seq) {
+  BatchResult res = iter->rep->GetBatch();
+  crocksdb_writebatch_t* result = new crocksdb_writebatch_t;
+  result->rep = std::move(*res.writeBatchPtr);
+  *seq = res.sequence;
+  return result;
+}
+
+void crocksdb_wal_iter_destroy(crocksdb_wal_iterator_t* iter) {
+  delete iter->rep;
+  delete iter;
+}
+
 crocksdb_iterator_t* crocksdb_create_iterator(
     crocksdb_t* db, const crocksdb_readoptions_t* options) {
   crocksdb_iterator_t* result = new crocksdb_iterator_t;
@@ -1322,7 +1632,7 @@ void crocksdb_create_iterators(
     crocksdb_iterator_t** iterators, size_t size, char** errptr) {
   std::vector<ColumnFamilyHandle*> column_families_vec(size);
   for (size_t i = 0; i < size; i++) {
-    column_families_vec.push_back(column_families[i]->rep);
+    column_families_vec[i] = column_families[i]->rep;
   }
 
   std::vector<Iterator*> res;
@@ -1443,6 +1753,40 @@ void crocksdb_approximate_sizes_cf(
   delete[] ranges;
 }
 
+void crocksdb_approximate_sizes_cf_with_flags(
+    crocksdb_t* db, crocksdb_column_family_handle_t* column_family,
+    int num_ranges, const char* const* range_start_key,
+    const size_t* range_start_key_len, const char* const* range_limit_key,
+    const size_t* range_limit_key_len, unsigned char include_flags,
+    uint64_t* sizes, char** errptr) {
+  Range* ranges = new Range[num_ranges];
+  for (int i = 0; i < num_ranges; i++) {
+    ranges[i].start = Slice(range_start_key[i], range_start_key_len[i]);
+    ranges[i].limit = Slice(range_limit_key[i], range_limit_key_len[i]);
+  }
+  SaveError(errptr,
+            db->rep->GetApproximateSizes(column_family->rep, ranges,
+                                         num_ranges, sizes, include_flags));
+  delete[] ranges;
+}
+
+void crocksdb_approximate_sizes_cf_opt(
+    crocksdb_t* db, crocksdb_column_family_handle_t* column_family,
+    const crocksdb_size_approximation_options_t* options, int num_ranges,
+    const char* const* range_start_key, const size_t* range_start_key_len,
+    const char* const* range_limit_key, const size_t* range_limit_key_len,
+    uint64_t* sizes, char** errptr) {
+  Range* ranges = new Range[num_ranges];
+  for (int i = 0; i < num_ranges; i++) {
+    ranges[i].start = Slice(range_start_key[i], range_start_key_len[i]);
+    ranges[i].limit = Slice(range_limit_key[i], range_limit_key_len[i]);
+  }
+  SaveError(errptr,
+            db->rep->GetApproximateSizes(options->rep, column_family->rep,
+                                         ranges, num_ranges, sizes));
+  delete[] ranges;
+}
+
 void crocksdb_approximate_memtable_stats(const crocksdb_t* db,
                                          const char* range_start_key,
                                          size_t range_start_key_len,
@@ -1959,6 +2303,130 @@ void crocksdb_writebatch_iterate_cf(
   b->rep.Iterate(&handler);
 }
 
+void crocksdb_writebatch_iterate_handler(
+    crocksdb_writebatch_t* b, void* state,
+    void (*put_cf)(void*, uint32_t cf, const char* k, size_t klen,
+                   const char* v, size_t vlen),
+    void (*deleted_cf)(void*, uint32_t cf, const char* k, size_t klen),
+    void (*single_deleted_cf)(void*, uint32_t cf, const char* k, size_t klen),
+    void (*merge_cf)(void*, uint32_t cf, const char* k, size_t klen,
+                     const char* v, size_t vlen),
+    void (*delete_range_cf)(void*, uint32_t cf, const char* begin_key,
+                            size_t begin_keylen, const char* end_key,
+                            size_t end_keylen),
+    char** errptr) {
+  class HandlerWrapper : public WriteBatch::Handler {
+   public:
+    void* state_;
+    void (*put_cf_)(void*, uint32_t cf, const char* k, size_t klen,
+                    const char* v, size_t vlen);
+    void (*deleted_cf_)(void*, uint32_t cf, const char* k, size_t klen);
+    void (*single_deleted_cf_)(void*, uint32_t cf, const char* k, size_t klen);
+    void (*merge_cf_)(void*, uint32_t cf, const char* k, size_t klen,
+                      const char* v, size_t vlen);
+    void (*delete_range_cf_)(void*, uint32_t cf, const char* begin_key,
+                             size_t begin_keylen, const char* end_key,
+                             size_t end_keylen);
+
+    Status PutCF(uint32_t cf, const Slice& key, const Slice& value) override {
+      (*put_cf_)(state_, cf, key.data(), key.size(), value.data(),
+                 value.size());
+      return Status::OK();
+    }
+
+    Status DeleteCF(uint32_t cf, const Slice& key) override {
+      (*deleted_cf_)(state_, cf, key.data(), key.size());
+      return Status::OK();
+    }
+
+    Status SingleDeleteCF(uint32_t cf, const Slice& key) override {
+      (*single_deleted_cf_)(state_, cf, key.data(), key.size());
+      return Status::OK();
+    }
+
+    Status MergeCF(uint32_t cf, const Slice& key, const Slice& value) override {
+      (*merge_cf_)(state_, cf, key.data(), key.size(), value.data(),
+                   value.size());
+      return Status::OK();
+    }
+
+    Status DeleteRangeCF(uint32_t cf, const Slice& begin_key,
+                         const Slice& end_key) override {
+      (*delete_range_cf_)(state_, cf, begin_key.data(), begin_key.size(),
+                          end_key.data(), end_key.size());
+      return Status::OK();
+    }
+  };
+  HandlerWrapper handler;
+  handler.state_ = state;
+  handler.put_cf_ = put_cf;
+  handler.deleted_cf_ = deleted_cf;
+  handler.single_deleted_cf_ = single_deleted_cf;
+  handler.merge_cf_ = merge_cf;
+  handler.delete_range_cf_ = delete_range_cf;
+  SaveError(errptr, b->rep.Iterate(&handler));
+}
+
+crocksdb_writebatch_t* crocksdb_writebatch_filter_cf(
+    crocksdb_writebatch_t* b, const uint32_t* cf_ids, size_t num_cfs,
+    char** errptr) {
+  class FilterHandler : public WriteBatch::Handler {
+   public:
+    FilterHandler(const uint32_t* cf_ids, size_t num_cfs, WriteBatch* dst)
+        : cf_ids_(cf_ids), num_cfs_(num_cfs), dst_(dst) {}
+
+    Status PutCF(uint32_t cf, const Slice& key, const Slice& value) override {
+      return Keep(cf) ? rocksdb::WriteBatchInternal::Put(dst_, cf, key, value)
+                      : Status::OK();
+    }
+
+    Status DeleteCF(uint32_t cf, const Slice& key) override {
+      return Keep(cf) ? rocksdb::WriteBatchInternal::Delete(dst_, cf, key)
+                      : Status::OK();
+    }
+
+    Status SingleDeleteCF(uint32_t cf, const Slice& key) override {
+      return Keep(cf) ? rocksdb::WriteBatchInternal::SingleDelete(dst_, cf, key)
+                      : Status::OK();
+    }
+
+    Status DeleteRangeCF(uint32_t cf, const Slice& begin_key,
+                         const Slice& end_key) override {
+      return Keep(cf) ? rocksdb::WriteBatchInternal::DeleteRange(
+                            dst_, cf, begin_key, end_key)
+                      : Status::OK();
+    }
+
+    Status MergeCF(uint32_t cf, const Slice& key, const Slice& value) override {
+      return Keep(cf) ? rocksdb::WriteBatchInternal::Merge(dst_, cf, key, value)
+                      : Status::OK();
+    }
+
+    Status PutBlobIndexCF(uint32_t cf, const Slice& key,
+                          const Slice& value) override {
+      return Keep(cf)
+                 ? rocksdb::WriteBatchInternal::PutBlobIndex(dst_, cf, key, value)
+                 : Status::OK();
+    }
+
+   private:
+    bool Keep(uint32_t cf) const {
+      return std::find(cf_ids_, cf_ids_ + num_cfs_, cf) != cf_ids_ + num_cfs_;
+    }
+
+    const uint32_t* cf_ids_;
+    size_t num_cfs_;
+    WriteBatch* dst_;
+  };
+  crocksdb_writebatch_t* result = new crocksdb_writebatch_t;
+  FilterHandler handler(cf_ids, num_cfs, &result->rep);
+  if (SaveError(errptr, b->rep.Iterate(&handler))) {
+    delete result;
+    return nullptr;
+  }
+  return result;
+}
+
 const char* crocksdb_writebatch_data(crocksdb_writebatch_t* b, size_t* size) {
   *size = b->rep.GetDataSize();
   return b->rep.Data().c_str();
@@ -2048,6 +2516,129 @@ uint32_t crocksdb_writebatch_iterator_column_family_id(
   return it->rep->GetColumnFamilyId();
 }
 
+crocksdb_writebatch_wi_t* crocksdb_writebatch_wi_create(
+    size_t reserved_bytes, unsigned char overwrite_keys) {
+  crocksdb_writebatch_wi_t* b = new crocksdb_writebatch_wi_t;
+  b->rep = new WriteBatchWithIndex(rocksdb::BytewiseComparator(),
+                                   reserved_bytes, overwrite_keys);
+  return b;
+}
+
+void crocksdb_writebatch_wi_destroy(crocksdb_writebatch_wi_t* b) {
+  delete b->rep;
+  delete b;
+}
+
+void crocksdb_writebatch_wi_clear(crocksdb_writebatch_wi_t* b) {
+  b->rep->Clear();
+}
+
+int crocksdb_writebatch_wi_count(crocksdb_writebatch_wi_t* b) {
+  return b->rep->GetWriteBatch()->Count();
+}
+
+void crocksdb_writebatch_wi_put(crocksdb_writebatch_wi_t* b, const char* key,
+                                size_t klen, const char* val, size_t vlen,
+                                char** errptr) {
+  SaveError(errptr, b->rep->Put(Slice(key, klen), Slice(val, vlen)));
+}
+
+void crocksdb_writebatch_wi_put_cf(
+    crocksdb_writebatch_wi_t* b, crocksdb_column_family_handle_t* column_family,
+    const char* key, size_t klen, const char* val, size_t vlen, char** errptr) {
+  SaveError(errptr, b->rep->Put(column_family->rep, Slice(key, klen),
+                                Slice(val, vlen)));
+}
+
+void crocksdb_writebatch_wi_merge(crocksdb_writebatch_wi_t* b, const char* key,
+                                  size_t klen, const char* val, size_t vlen,
+                                  char** errptr) {
+  SaveError(errptr, b->rep->Merge(Slice(key, klen), Slice(val, vlen)));
+}
+
+void crocksdb_writebatch_wi_merge_cf(
+    crocksdb_writebatch_wi_t* b, crocksdb_column_family_handle_t* column_family,
+    const char* key, size_t klen, const char* val, size_t vlen, char** errptr) {
+  SaveError(errptr, b->rep->Merge(column_family->rep, Slice(key, klen),
+                                  Slice(val, vlen)));
+}
+
+void crocksdb_writebatch_wi_delete(crocksdb_writebatch_wi_t* b, const char* key,
+                                   size_t klen, char** errptr) {
+  SaveError(errptr, b->rep->Delete(Slice(key, klen)));
+}
+
+void crocksdb_writebatch_wi_delete_cf(
+    crocksdb_writebatch_wi_t* b, crocksdb_column_family_handle_t* column_family,
+    const char* key, size_t klen, char** errptr) {
+  SaveError(errptr, b->rep->Delete(column_family->rep, Slice(key, klen)));
+}
+
+void crocksdb_writebatch_wi_single_delete(crocksdb_writebatch_wi_t* b,
+                                          const char* key, size_t klen,
+                                          char** errptr) {
+  SaveError(errptr, b->rep->SingleDelete(Slice(key, klen)));
+}
+
+void crocksdb_writebatch_wi_single_delete_cf(
+    crocksdb_writebatch_wi_t* b, crocksdb_column_family_handle_t* column_family,
+    const char* key, size_t klen, char** errptr) {
+  SaveError(errptr, b->rep->SingleDelete(column_family->rep, Slice(key, klen)));
+}
+
+void crocksdb_writebatch_wi_delete_range(crocksdb_writebatch_wi_t* b,
+                                         const char* begin_key,
+                                         size_t begin_keylen,
+                                         const char* end_key,
+                                         size_t end_keylen, char** errptr) {
+  SaveError(errptr, b->rep->DeleteRange(Slice(begin_key, begin_keylen),
+                                        Slice(end_key, end_keylen)));
+}
+
+void crocksdb_writebatch_wi_delete_range_cf(
+    crocksdb_writebatch_wi_t* b, crocksdb_column_family_handle_t* column_family,
+    const char* begin_key, size_t begin_keylen, const char* end_key,
+    size_t end_keylen, char** errptr) {
+  SaveError(errptr, b->rep->DeleteRange(column_family->rep,
+                                        Slice(begin_key, begin_keylen),
+                                        Slice(end_key, end_keylen)));
+}
+
+crocksdb_pinnableslice_t* crocksdb_writebatch_wi_get_from_batch_and_db_cf(
+    crocksdb_writebatch_wi_t* wbwi, crocksdb_t* db,
+    const crocksdb_readoptions_t* options,
+    crocksdb_column_family_handle_t* column_family, const char* key,
+    size_t keylen, char** errptr) {
+  crocksdb_pinnableslice_t* v = new (crocksdb_pinnableslice_t);
+  Status s = wbwi->rep->GetFromBatchAndDB(
+      db->rep, options->rep, column_family->rep, Slice(key, keylen), &v->rep);
+  if (!s.ok()) {
+    delete v;
+    if (!s.IsNotFound()) {
+      SaveError(errptr, s);
+    }
+    return nullptr;
+  }
+  return v;
+}
+
+crocksdb_iterator_t* crocksdb_writebatch_wi_create_iterator_with_base_cf(
+    crocksdb_writebatch_wi_t* wbwi, crocksdb_iterator_t* base_iterator,
+    crocksdb_column_family_handle_t* column_family) {
+  crocksdb_iterator_t* result = new crocksdb_iterator_t;
+  result->rep =
+      wbwi->rep->NewIteratorWithBase(column_family->rep, base_iterator->rep);
+  delete base_iterator;
+  return result;
+}
+
+void crocksdb_write_writebatch_wi(crocksdb_t* db,
+                                  const crocksdb_writeoptions_t* options,
+                                  crocksdb_writebatch_wi_t* wbwi,
+                                  char** errptr) {
+  SaveError(errptr, db->rep->Write(options->rep, wbwi->rep->GetWriteBatch()));
+}
+
 crocksdb_block_based_table_options_t* crocksdb_block_based_options_create() {
   return new crocksdb_block_based_table_options_t;
 }
@@ -2276,6 +2867,10 @@ void crocksdb_reset_status(crocksdb_status_ptr_t* status_ptr) {
   *ptr = Status::OK();
 }
 
+uint32_t crocksdb_status_ptr_get_severity(crocksdb_status_ptr_t* status_ptr) {
+  return static_cast<uint32_t>(status_ptr->rep->severity());
+}
+
 /* CompactionJobInfo */
 
 void crocksdb_compactionjobinfo_status(const crocksdb_compactionjobinfo_t* info,
@@ -2495,6 +3090,7 @@ struct crocksdb_eventlistener_t : public EventListener {
   void (*on_background_error)(void*, uint32_t, crocksdb_status_ptr_t*);
   void (*on_stall_conditions_changed)(void*, const crocksdb_writestallinfo_t*);
   void (*on_memtable_sealed)(void*, const crocksdb_memtableinfo_t*);
+  void (*on_error_recovery_completed)(void*, crocksdb_status_ptr_t*);
 
   virtual void OnFlushBegin(DB* db, const FlushJobInfo& info) {
     crocksdb_t c_db = {db};
@@ -2559,6 +3155,11 @@ struct crocksdb_eventlistener_t : public EventListener {
                        reinterpret_cast<const crocksdb_memtableinfo_t*>(&info));
   }
 
+  virtual void OnErrorRecoveryCompleted(Status old_bg_error) {
+    crocksdb_status_ptr_t s = {&old_bg_error};
+    on_error_recovery_completed(state_, &s);
+  }
+
   virtual ~crocksdb_eventlistener_t() { destructor_(state_); }
 };
 
@@ -2572,7 +3173,8 @@ crocksdb_eventlistener_t* crocksdb_eventlistener_create(
     on_external_file_ingested_cb on_external_file_ingested,
     on_background_error_cb on_background_error,
     on_stall_conditions_changed_cb on_stall_conditions_changed,
-    on_memtable_sealed_cb on_memtable_sealed) {
+    on_memtable_sealed_cb on_memtable_sealed,
+    on_error_recovery_completed_cb on_error_recovery_completed) {
   crocksdb_eventlistener_t* et = new crocksdb_eventlistener_t;
   et->state_ = state_;
   et->destructor_ = destructor_;
@@ -2586,6 +3188,7 @@ crocksdb_eventlistener_t* crocksdb_eventlistener_create(
   et->on_background_error = on_background_error;
   et->on_stall_conditions_changed = on_stall_conditions_changed;
   et->on_memtable_sealed = on_memtable_sealed;
+  et->on_error_recovery_completed = on_error_recovery_completed;
   return et;
 }
 
@@ -2687,6 +3290,11 @@ void crocksdb_options_set_compaction_filter(
   opt->rep.compaction_filter = filter;
 }
 
+void crocksdb_options_set_wal_filter(crocksdb_options_t* opt,
+                                     crocksdb_walfilter_t* filter) {
+  opt->rep.wal_filter = filter;
+}
+
 void crocksdb_options_set_compaction_filter_factory(
     crocksdb_options_t* opt, crocksdb_compactionfilterfactory_t* factory) {
   opt->rep.compaction_filter_factory =
@@ -2703,6 +3311,11 @@ void crocksdb_options_set_comparator(crocksdb_options_t* opt,
   opt->rep.comparator = cmp;
 }
 
+void crocksdb_options_set_bytewise_comparator_with_u64_ts(
+    crocksdb_options_t* opt) {
+  opt->rep.comparator = BytewiseComparatorWithU64TsImpl::Instance();
+}
+
 void crocksdb_options_set_merge_operator(
     crocksdb_options_t* opt, crocksdb_mergeoperator_t* merge_operator) {
   opt->rep.merge_operator = std::shared_ptr<MergeOperator>(merge_operator);
@@ -2773,11 +3386,15 @@ void crocksdb_options_set_info_log(crocksdb_options_t* opt,
                                    crocksdb_logger_t* l) {
   if (l) {
     opt->rep.info_log = l->rep;
+    opt->rep.info_log->SetInfoLogLevel(opt->rep.info_log_level);
   }
 }
 
 void crocksdb_options_set_info_log_level(crocksdb_options_t* opt, uint32_t v) {
   opt->rep.info_log_level = static_cast<InfoLogLevel>(v);
+  if (opt->rep.info_log) {
+    opt->rep.info_log->SetInfoLogLevel(opt->rep.info_log_level);
+  }
 }
 
 void crocksdb_options_set_db_write_buffer_size(crocksdb_options_t* opt,
@@ -3135,6 +3752,11 @@ void crocksdb_options_set_unordered_write(crocksdb_options_t* opt,
   opt->rep.unordered_write = v;
 }
 
+void crocksdb_options_set_two_write_queues(crocksdb_options_t* opt,
+                                           unsigned char v) {
+  opt->rep.two_write_queues = v;
+}
+
 void crocksdb_options_set_allow_concurrent_memtable_write(
     crocksdb_options_t* opt, unsigned char v) {
   opt->rep.allow_concurrent_memtable_write = v;
@@ -3179,6 +3801,16 @@ void crocksdb_options_set_max_write_buffer_number_to_maintain(
   opt->rep.max_write_buffer_number_to_maintain = n;
 }
 
+void crocksdb_options_set_max_write_buffer_size_to_maintain(
+    crocksdb_options_t* opt, int64_t n) {
+  opt->rep.max_write_buffer_size_to_maintain = n;
+}
+
+int64_t crocksdb_options_get_max_write_buffer_size_to_maintain(
+    const crocksdb_options_t* opt) {
+  return opt->rep.max_write_buffer_size_to_maintain;
+}
+
 void crocksdb_options_set_max_background_jobs(crocksdb_options_t* opt, int n) {
   opt->rep.max_background_jobs = n;
 }
@@ -3296,6 +3928,25 @@ int crocksdb_options_get_disable_auto_compactions(
   return opt->rep.disable_auto_compactions;
 }
 
+void crocksdb_options_set_enable_blob_files(crocksdb_options_t* opt,
+                                            unsigned char v) {
+  opt->rep.enable_blob_files = v;
+}
+
+unsigned char crocksdb_options_get_enable_blob_files(
+    const crocksdb_options_t* opt) {
+  return opt->rep.enable_blob_files;
+}
+
+void crocksdb_options_set_min_blob_size(crocksdb_options_t* opt, uint64_t v) {
+  opt->rep.min_blob_size = v;
+}
+
+unsigned char crocksdb_options_has_merge_operator(
+    const crocksdb_options_t* opt) {
+  return opt->rep.merge_operator != nullptr;
+}
+
 void crocksdb_options_set_disable_write_stall(crocksdb_options_t* opt,
                                               unsigned char disable) {
   opt->rep.disable_write_stall = disable;
@@ -3328,6 +3979,10 @@ void crocksdb_options_set_memtable_huge_page_size(crocksdb_options_t* opt,
                                                   size_t v) {
   opt->rep.memtable_huge_page_size = v;
 }
+
+size_t crocksdb_options_get_memtable_huge_page_size(crocksdb_options_t* opt) {
+  return opt->rep.memtable_huge_page_size;
+}
 const char* crocksdb_options_get_memtable_factory_name(
     crocksdb_options_t* opt) {
   if (!opt->rep.memtable_factory) {
@@ -3718,6 +4373,16 @@ crocksdb_write_buffer_manager_t* crocksdb_write_buffer_manager_create(
   return wbm;
 }
 
+crocksdb_write_buffer_manager_t*
+crocksdb_write_buffer_manager_create_with_cache(
+    size_t flush_size, crocksdb_cache_t* cache, float stall_ratio,
+    unsigned char flush_oldest_first) {
+  crocksdb_write_buffer_manager_t* wbm = new crocksdb_write_buffer_manager_t;
+  wbm->rep = std::make_shared<WriteBufferManager>(
+      flush_size, cache->rep, stall_ratio, flush_oldest_first);
+  return wbm;
+}
+
 void crocksdb_write_buffer_manager_set_flush_size(
     crocksdb_write_buffer_manager_t* wbm, size_t flush_size) {
   wbm->rep->SetFlushSize(flush_size);
@@ -3795,6 +4460,22 @@ void crocksdb_compactionfilter_destroy(crocksdb_compactionfilter_t* filter) {
   delete filter;
 }
 
+crocksdb_walfilter_t* crocksdb_walfilter_create(
+    void* state, void (*destructor)(void*),
+    uint32_t (*log_record)(void*, const char* rep, size_t rep_length),
+    const char* (*name)(void*)) {
+  crocksdb_walfilter_t* result = new crocksdb_walfilter_t;
+  result->state_ = state;
+  result->destructor_ = destructor;
+  result->log_record_ = log_record;
+  result->name_ = name;
+  return result;
+}
+
+void crocksdb_walfilter_destroy(crocksdb_walfilter_t* filter) {
+  delete filter;
+}
+
 unsigned char crocksdb_compactionfiltercontext_is_full_compaction(
     crocksdb_compactionfiltercontext_t* context) {
   return context->rep.is_full_compaction;
@@ -4049,6 +4730,28 @@ void crocksdb_readoptions_set_iterate_upper_bound(crocksdb_readoptions_t* opt,
   }
 }
 
+void crocksdb_readoptions_set_timestamp(crocksdb_readoptions_t* opt,
+                                        const char* ts, size_t tslen) {
+  if (ts == nullptr) {
+    opt->timestamp = Slice();
+    opt->rep.timestamp = nullptr;
+  } else {
+    opt->timestamp = Slice(ts, tslen);
+    opt->rep.timestamp = &opt->timestamp;
+  }
+}
+
+void crocksdb_readoptions_set_iter_start_ts(crocksdb_readoptions_t* opt,
+                                            const char* ts, size_t tslen) {
+  if (ts == nullptr) {
+    opt->iter_start_ts = Slice();
+    opt->rep.iter_start_ts = nullptr;
+  } else {
+    opt->iter_start_ts = Slice(ts, tslen);
+    opt->rep.iter_start_ts = &opt->iter_start_ts;
+  }
+}
+
 void crocksdb_readoptions_set_read_tier(crocksdb_readoptions_t* opt, int v) {
   opt->rep.read_tier = static_cast<rocksdb::ReadTier>(v);
 }
@@ -4073,6 +4776,11 @@ void crocksdb_readoptions_set_max_skippable_internal_keys(
   opt->rep.max_skippable_internal_keys = n;
 }
 
+void crocksdb_readoptions_set_value_size_soft_limit(
+    crocksdb_readoptions_t* opt, uint64_t v) {
+  opt->rep.value_size_soft_limit = v;
+}
+
 void crocksdb_readoptions_set_total_order_seek(crocksdb_readoptions_t* opt,
                                                unsigned char v) {
   opt->rep.total_order_seek = v;
@@ -4177,6 +4885,17 @@ void crocksdb_writeoptions_set_memtable_insert_hint_per_batch(
   opt->rep.memtable_insert_hint_per_batch = v;
 }
 
+void crocksdb_writeoptions_set_timestamp(crocksdb_writeoptions_t* opt,
+                                         const char* ts, size_t tslen) {
+  if (ts == nullptr) {
+    opt->timestamp = Slice();
+    opt->rep.timestamp = nullptr;
+  } else {
+    opt->timestamp = Slice(ts, tslen);
+    opt->rep.timestamp = &opt->timestamp;
+  }
+}
+
 crocksdb_compactoptions_t* crocksdb_compactoptions_create() {
   return new crocksdb_compactoptions_t;
 }
@@ -4242,6 +4961,31 @@ void crocksdb_flushoptions_set_check_if_compaction_disabled(
   opt->rep.check_if_compaction_disabled = v;
 }
 
+crocksdb_size_approximation_options_t*
+crocksdb_size_approximation_options_create() {
+  return new crocksdb_size_approximation_options_t;
+}
+
+void crocksdb_size_approximation_options_destroy(
+    crocksdb_size_approximation_options_t* opt) {
+  delete opt;
+}
+
+void crocksdb_size_approximation_options_set_include_memtables(
+    crocksdb_size_approximation_options_t* opt, unsigned char v) {
+  opt->rep.include_memtabtles = v;
+}
+
+void crocksdb_size_approximation_options_set_include_files(
+    crocksdb_size_approximation_options_t* opt, unsigned char v) {
+  opt->rep.include_files = v;
+}
+
+void crocksdb_size_approximation_options_set_files_size_error_margin(
+    crocksdb_size_approximation_options_t* opt, double v) {
+  opt->rep.files_size_error_margin = v;
+}
+
 crocksdb_memory_allocator_t* crocksdb_jemalloc_nodump_allocator_create(
     char** errptr) {
   crocksdb_memory_allocator_t* allocator = new crocksdb_memory_allocator_t;
@@ -4299,12 +5043,36 @@ crocksdb_cache_t* crocksdb_cache_create_lru(crocksdb_lru_cache_options_t* opt) {
   return c;
 }
 
+crocksdb_cache_t* crocksdb_cache_create_clock(
+    size_t capacity, int num_shard_bits, unsigned char strict_capacity_limit) {
+  auto cache =
+      NewClockCache(capacity, num_shard_bits, strict_capacity_limit != 0);
+  if (cache == nullptr) {
+    return nullptr;
+  }
+  crocksdb_cache_t* c = new crocksdb_cache_t;
+  c->rep = cache;
+  return c;
+}
+
 void crocksdb_cache_destroy(crocksdb_cache_t* cache) { delete cache; }
 
 void crocksdb_cache_set_capacity(crocksdb_cache_t* cache, size_t capacity) {
   cache->rep->SetCapacity(capacity);
 }
 
+size_t crocksdb_cache_get_capacity(crocksdb_cache_t* cache) {
+  return cache->rep->GetCapacity();
+}
+
+size_t crocksdb_cache_get_usage(crocksdb_cache_t* cache) {
+  return cache->rep->GetUsage();
+}
+
+size_t crocksdb_cache_get_pinned_usage(crocksdb_cache_t* cache) {
+  return cache->rep->GetPinnedUsage();
+}
+
 crocksdb_env_t* crocksdb_default_env_create() {
   crocksdb_env_t* result = new crocksdb_env_t;
   result->rep = Env::Default();
@@ -4379,6 +5147,15 @@ int crocksdb_env_get_high_priority_background_threads(crocksdb_env_t* env) {
   return env->rep->GetBackgroundThreads(Env::HIGH);
 }
 
+void crocksdb_env_set_background_threads_with_priority(crocksdb_env_t* env,
+                                                       int n, int pri) {
+  env->rep->SetBackgroundThreads(n, static_cast<Env::Priority>(pri));
+}
+
+int crocksdb_env_get_background_threads(crocksdb_env_t* env, int pri) {
+  return env->rep->GetBackgroundThreads(static_cast<Env::Priority>(pri));
+}
+
 void crocksdb_env_join_all_threads(crocksdb_env_t* env) {
   env->rep->WaitForJoin();
 }
@@ -4810,6 +5587,7 @@ crocksdb_sstfilewriter_t* crocksdb_sstfilewriter_create(
     const crocksdb_envoptions_t* env, const crocksdb_options_t* io_options) {
   crocksdb_sstfilewriter_t* writer = new crocksdb_sstfilewriter_t;
   writer->rep = new SstFileWriter(env->rep, io_options->rep);
+  writer->ts_sz = io_options->rep.comparator->timestamp_size();
   return writer;
 }
 
@@ -4819,6 +5597,7 @@ crocksdb_sstfilewriter_t* crocksdb_sstfilewriter_create_cf(
   crocksdb_sstfilewriter_t* writer = new crocksdb_sstfilewriter_t;
   writer->rep =
       new SstFileWriter(env->rep, io_options->rep, column_family->rep);
+  writer->ts_sz = io_options->rep.comparator->timestamp_size();
   return writer;
 }
 
@@ -4833,6 +5612,24 @@ void crocksdb_sstfilewriter_put(crocksdb_sstfilewriter_t* writer,
   SaveError(errptr, writer->rep->Put(Slice(key, keylen), Slice(val, vallen)));
 }
 
+void crocksdb_sstfilewriter_put_with_ts(crocksdb_sstfilewriter_t* writer,
+                                        const char* key, size_t keylen,
+                                        const char* ts, size_t tslen,
+                                        const char* val, size_t vallen,
+                                        char** errptr) {
+  if (tslen != writer->ts_sz) {
+    SaveError(errptr,
+              Status::InvalidArgument(
+                  "timestamp size mismatch: the comparator expects " +
+                  std::to_string(writer->ts_sz) + " bytes, got " +
+                  std::to_string(tslen)));
+    return;
+  }
+  std::string key_with_ts(key, keylen);
+  key_with_ts.append(ts, tslen);
+  SaveError(errptr, writer->rep->Put(key_with_ts, Slice(val, vallen)));
+}
+
 void crocksdb_sstfilewriter_merge(crocksdb_sstfilewriter_t* writer,
                                   const char* key, size_t keylen,
                                   const char* val, size_t vallen,
@@ -4959,6 +5756,12 @@ void crocksdb_ingestexternalfileoptions_set_verify_checksums_before_ingest(
   opt->rep.verify_checksums_before_ingest = verify_checksums_before_ingest;
 }
 
+void crocksdb_ingestexternalfileoptions_set_fail_if_not_bottommost_level(
+    crocksdb_ingestexternalfileoptions_t* opt,
+    unsigned char fail_if_not_bottommost_level) {
+  opt->rep.fail_if_not_bottommost_level = fail_if_not_bottommost_level;
+}
+
 void crocksdb_ingestexternalfileoptions_destroy(
     crocksdb_ingestexternalfileoptions_t* opt) {
   delete opt;
@@ -5023,6 +5826,27 @@ unsigned char crocksdb_ingest_external_file_optimized(
   return has_flush;
 }
 
+void crocksdb_ingest_external_file_from_buffer_cf(
+    crocksdb_t* db, crocksdb_column_family_handle_t* handle, const char* data,
+    size_t data_len, const char* tmp_file,
+    const crocksdb_ingestexternalfileoptions_t* opt, char** errptr) {
+  // Write the file through the DB's env and move it into the DB, so that the
+  // data is only written once, to the file that ends up being ingested.
+  Env* env = db->rep->GetEnv();
+  auto s = WriteStringToFile(env, Slice(data, data_len), tmp_file);
+  if (s.ok()) {
+    auto ingest_opts = opt->rep;
+    ingest_opts.move_files = true;
+    s = db->rep->IngestExternalFile(handle->rep, {tmp_file}, ingest_opts);
+  }
+  // A successful ingestion already removes the moved file.
+  auto del = env->DeleteFile(tmp_file);
+  if (s.ok() && !del.ok() && !del.IsNotFound()) {
+    s = del;
+  }
+  SaveError(errptr, s);
+}
+
 crocksdb_slicetransform_t* crocksdb_slicetransform_create(
     void* state, void (*destructor)(void*),
     char* (*transform)(void*, const char* key, size_t length,
@@ -5323,6 +6147,43 @@ crocksdb_pinnableslice_t* crocksdb_get_pinned_cf(
   return v;
 }
 
+crocksdb_pinnableslice_t* crocksdb_get_pinned_cf_cache_only(
+    crocksdb_t* db, const crocksdb_readoptions_t* options,
+    crocksdb_column_family_handle_t* column_family, const char* key,
+    size_t keylen, char** errptr) {
+  ReadOptions read_options = options->rep;
+  read_options.read_tier = rocksdb::kBlockCacheTier;
+  crocksdb_pinnableslice_t* v = new (crocksdb_pinnableslice_t);
+  Status s = db->rep->Get(read_options, column_family->rep, Slice(key, keylen),
+                          &v->rep);
+  if (!s.ok()) {
+    delete v;
+    // Incomplete means the key may exist, but not in the cache.
+    if (!s.IsNotFound() && !s.IsIncomplete()) {
+      SaveError(errptr, s);
+    }
+    return NULL;
+  }
+  return v;
+}
+
+unsigned char crocksdb_key_may_exist_cf(
+    crocksdb_t* db, const crocksdb_readoptions_t* options,
+    crocksdb_column_family_handle_t* column_family, const char* key,
+    size_t keylen, crocksdb_pinnableslice_t** value) {
+  std::string tmp;
+  bool value_found = false;
+  bool may_exist = db->rep->KeyMayExist(options->rep, column_family->rep,
+                                        Slice(key, keylen), &tmp, &value_found);
+  *value = nullptr;
+  if (may_exist && value_found) {
+    crocksdb_pinnableslice_t* v = new (crocksdb_pinnableslice_t);
+    v->rep.PinSelf(tmp);
+    *value = v;
+  }
+  return may_exist;
+}
+
 void crocksdb_pinnableslice_destroy(crocksdb_pinnableslice_t* v) { delete v; }
 
 const char* crocksdb_pinnableslice_value(const crocksdb_pinnableslice_t* v,
@@ -5964,6 +6825,21 @@ const char* crocksdb_sst_file_meta_data_largestkey(
   return meta->rep.largestkey.data();
 }
 
+uint64_t crocksdb_sst_file_meta_data_num_entries(
+    const crocksdb_sst_file_meta_data_t* meta) {
+  return meta->rep.num_entries;
+}
+
+uint64_t crocksdb_sst_file_meta_data_num_deletions(
+    const crocksdb_sst_file_meta_data_t* meta) {
+  return meta->rep.num_deletions;
+}
+
+unsigned char crocksdb_sst_file_meta_data_being_compacted(
+    const crocksdb_sst_file_meta_data_t* meta) {
+  return meta->rep.being_compacted;
+}
+
 crocksdb_compaction_options_t* crocksdb_compaction_options_create() {
   return new crocksdb_compaction_options_t();
 }
@@ -7094,7 +7970,7 @@ void ctitandb_create_iterators(
     crocksdb_iterator_t** iterators, size_t size, char** errptr) {
   std::vector<ColumnFamilyHandle*> column_families_vec(size);
   for (size_t i = 0; i < size; i++) {
-    column_families_vec.push_back(column_families[i]->rep);
+    column_families_vec[i] = column_families[i]->rep;
   }
 
   std::vector<Iterator*> res;
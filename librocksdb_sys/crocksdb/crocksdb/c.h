@@ -92,7 +92,11 @@ typedef struct crocksdb_fifo_compaction_options_t
 typedef struct crocksdb_filelock_t crocksdb_filelock_t;
 typedef struct crocksdb_filterpolicy_t crocksdb_filterpolicy_t;
 typedef struct crocksdb_flushoptions_t crocksdb_flushoptions_t;
+typedef struct crocksdb_size_approximation_options_t
+    crocksdb_size_approximation_options_t;
 typedef struct crocksdb_iterator_t crocksdb_iterator_t;
+typedef struct crocksdb_wal_iterator_t crocksdb_wal_iterator_t;
+typedef struct crocksdb_walfilter_t crocksdb_walfilter_t;
 typedef struct crocksdb_logger_t crocksdb_logger_t;
 typedef struct crocksdb_logger_impl_t crocksdb_logger_impl_t;
 typedef struct crocksdb_mergeoperator_t crocksdb_mergeoperator_t;
@@ -110,6 +114,7 @@ typedef struct crocksdb_slicetransform_t crocksdb_slicetransform_t;
 typedef struct crocksdb_snapshot_t crocksdb_snapshot_t;
 typedef struct crocksdb_writablefile_t crocksdb_writablefile_t;
 typedef struct crocksdb_writebatch_t crocksdb_writebatch_t;
+typedef struct crocksdb_writebatch_wi_t crocksdb_writebatch_wi_t;
 typedef struct crocksdb_writeoptions_t crocksdb_writeoptions_t;
 typedef struct crocksdb_universal_compaction_options_t
     crocksdb_universal_compaction_options_t;
@@ -261,6 +266,10 @@ crocksdb_backup_engine_open(const crocksdb_options_t* options, const char* path,
 extern C_ROCKSDB_LIBRARY_API void crocksdb_backup_engine_create_new_backup(
     crocksdb_backup_engine_t* be, crocksdb_t* db, char** errptr);
 
+extern C_ROCKSDB_LIBRARY_API void crocksdb_backup_engine_create_new_backup_flush(
+    crocksdb_backup_engine_t* be, crocksdb_t* db,
+    unsigned char flush_before_backup, char** errptr);
+
 extern C_ROCKSDB_LIBRARY_API void crocksdb_backup_engine_purge_old_backups(
     crocksdb_backup_engine_t* be, uint32_t num_backups_to_keep, char** errptr);
 
@@ -276,6 +285,14 @@ crocksdb_backup_engine_restore_db_from_latest_backup(
     crocksdb_backup_engine_t* be, const char* db_dir, const char* wal_dir,
     const crocksdb_restore_options_t* restore_options, char** errptr);
 
+extern C_ROCKSDB_LIBRARY_API void crocksdb_backup_engine_restore_db_from_backup(
+    crocksdb_backup_engine_t* be, uint32_t backup_id, const char* db_dir,
+    const char* wal_dir, const crocksdb_restore_options_t* restore_options,
+    char** errptr);
+
+extern C_ROCKSDB_LIBRARY_API void crocksdb_backup_engine_verify_backup(
+    crocksdb_backup_engine_t* be, uint32_t backup_id, char** errptr);
+
 extern C_ROCKSDB_LIBRARY_API const crocksdb_backup_engine_info_t*
 crocksdb_backup_engine_get_backup_info(crocksdb_backup_engine_t* be);
 
@@ -321,12 +338,32 @@ crocksdb_open_for_read_only_column_families(
     crocksdb_column_family_handle_t** column_family_handles,
     unsigned char error_if_log_file_exist, char** errptr);
 
+extern C_ROCKSDB_LIBRARY_API crocksdb_t* crocksdb_open_as_secondary(
+    const crocksdb_options_t* options, const char* name,
+    const char* secondary_path, char** errptr);
+
+extern C_ROCKSDB_LIBRARY_API crocksdb_t*
+crocksdb_open_as_secondary_column_families(
+    const crocksdb_options_t* options, const char* name,
+    const char* secondary_path, int num_column_families,
+    const char** column_family_names,
+    const crocksdb_options_t** column_family_options,
+    crocksdb_column_family_handle_t** column_family_handles, char** errptr);
+
+extern C_ROCKSDB_LIBRARY_API void crocksdb_try_catch_up_with_primary(
+    crocksdb_t* db, char** errptr);
+
 extern C_ROCKSDB_LIBRARY_API char** crocksdb_list_column_families(
     const crocksdb_options_t* options, const char* name, size_t* lencf,
     char** errptr);
 
 extern C_ROCKSDB_LIBRARY_API void crocksdb_list_column_families_destroy(
     char** list, size_t len);
+extern C_ROCKSDB_LIBRARY_API char** crocksdb_get_live_file_names(
+    crocksdb_t* db, unsigned char flush_memtable, size_t* len,
+    uint64_t* manifest_size, char** errptr);
+extern C_ROCKSDB_LIBRARY_API void crocksdb_live_file_names_destroy(char** list,
+                                                                   size_t len);
 
 extern C_ROCKSDB_LIBRARY_API crocksdb_column_family_handle_t*
 crocksdb_create_column_family(crocksdb_t* db,
@@ -339,6 +376,9 @@ extern C_ROCKSDB_LIBRARY_API void crocksdb_drop_column_family(
 extern C_ROCKSDB_LIBRARY_API uint32_t
 crocksdb_column_family_handle_id(crocksdb_column_family_handle_t*);
 
+extern C_ROCKSDB_LIBRARY_API size_t
+crocksdb_column_family_handle_timestamp_size(crocksdb_column_family_handle_t*);
+
 extern C_ROCKSDB_LIBRARY_API void crocksdb_column_family_handle_destroy(
     crocksdb_column_family_handle_t*);
 
@@ -448,6 +488,42 @@ extern C_ROCKSDB_LIBRARY_API void crocksdb_multi_get_cf(
     const size_t* keys_list_sizes, char** values_list,
     size_t* values_list_sizes, char** errs);
 
+// Like crocksdb_multi_get_cf, but also returns the user timestamp of each
+// found key. on_result is called once per key in order, with a NULL value if
+// the key is not found. The first error other than NotFound is stored in
+// errptr.
+extern C_ROCKSDB_LIBRARY_API void crocksdb_multi_get_cf_with_ts(
+    crocksdb_t* db, const crocksdb_readoptions_t* options,
+    const crocksdb_column_family_handle_t* const* column_families,
+    size_t num_keys, const char* const* keys_list,
+    const size_t* keys_list_sizes, void* ctx,
+    void (*on_result)(void*, size_t idx, const char* v, size_t vlen,
+                      const char* ts, size_t tslen),
+    char** errptr);
+
+/* Calls `on_version` with the value and timestamp of each version of `key`
+ * visible at the timestamp of `options`, newest first. Older versions are
+ * only visited if `iter_start_ts` is set, down to that timestamp. */
+extern C_ROCKSDB_LIBRARY_API void crocksdb_get_versions_cf_with_ts(
+    crocksdb_t* db, const crocksdb_readoptions_t* options,
+    crocksdb_column_family_handle_t* column_family, const char* key,
+    size_t keylen, void* ctx,
+    void (*on_version)(void*, const char* v, size_t vlen, const char* ts,
+                       size_t tslen),
+    char** errptr);
+
+extern C_ROCKSDB_LIBRARY_API crocksdb_wal_iterator_t*
+crocksdb_get_updates_since(crocksdb_t* db, uint64_t seq_number, char** errptr);
+extern C_ROCKSDB_LIBRARY_API unsigned char crocksdb_wal_iter_valid(
+    const crocksdb_wal_iterator_t*);
+extern C_ROCKSDB_LIBRARY_API void crocksdb_wal_iter_next(
+    crocksdb_wal_iterator_t*);
+extern C_ROCKSDB_LIBRARY_API void crocksdb_wal_iter_status(
+    const crocksdb_wal_iterator_t*, char** errptr);
+extern C_ROCKSDB_LIBRARY_API crocksdb_writebatch_t* crocksdb_wal_iter_get_batch(
+    crocksdb_wal_iterator_t*, uint64_t* seq);
+extern C_ROCKSDB_LIBRARY_API void crocksdb_wal_iter_destroy(
+    crocksdb_wal_iterator_t*);
 extern C_ROCKSDB_LIBRARY_API crocksdb_iterator_t* crocksdb_create_iterator(
     crocksdb_t* db, const crocksdb_readoptions_t* options);
 
@@ -504,6 +580,20 @@ extern C_ROCKSDB_LIBRARY_API void crocksdb_approximate_sizes_cf(
     const size_t* range_start_key_len, const char* const* range_limit_key,
     const size_t* range_limit_key_len, uint64_t* sizes);
 
+extern C_ROCKSDB_LIBRARY_API void crocksdb_approximate_sizes_cf_with_flags(
+    crocksdb_t* db, crocksdb_column_family_handle_t* column_family,
+    int num_ranges, const char* const* range_start_key,
+    const size_t* range_start_key_len, const char* const* range_limit_key,
+    const size_t* range_limit_key_len, unsigned char include_flags,
+    uint64_t* sizes, char** errptr);
+
+extern C_ROCKSDB_LIBRARY_API void crocksdb_approximate_sizes_cf_opt(
+    crocksdb_t* db, crocksdb_column_family_handle_t* column_family,
+    const crocksdb_size_approximation_options_t* options, int num_ranges,
+    const char* const* range_start_key, const size_t* range_start_key_len,
+    const char* const* range_limit_key, const size_t* range_limit_key_len,
+    uint64_t* sizes, char** errptr);
+
 extern C_ROCKSDB_LIBRARY_API void crocksdb_approximate_memtable_stats(
     const crocksdb_t* db, const char* range_start_key,
     size_t range_start_key_len, const char* range_limit_key,
@@ -715,6 +805,27 @@ extern C_ROCKSDB_LIBRARY_API void crocksdb_writebatch_iterate_cf(
     void (*deleted)(void*, const char* k, size_t klen),
     void (*deleted_cf)(void*, uint32_t cf, const char* k, size_t klen));
 
+/* Calls the callback matching each operation in the batch, together with
+ * its column family id. */
+extern C_ROCKSDB_LIBRARY_API void crocksdb_writebatch_iterate_handler(
+    crocksdb_writebatch_t* b, void* state,
+    void (*put_cf)(void*, uint32_t cf, const char* k, size_t klen,
+                   const char* v, size_t vlen),
+    void (*deleted_cf)(void*, uint32_t cf, const char* k, size_t klen),
+    void (*single_deleted_cf)(void*, uint32_t cf, const char* k, size_t klen),
+    void (*merge_cf)(void*, uint32_t cf, const char* k, size_t klen,
+                     const char* v, size_t vlen),
+    void (*delete_range_cf)(void*, uint32_t cf, const char* begin_key,
+                            size_t begin_keylen, const char* end_key,
+                            size_t end_keylen),
+    char** errptr);
+
+/* Returns a new batch with only the operations on the column families in
+ * `cf_ids`. */
+extern C_ROCKSDB_LIBRARY_API crocksdb_writebatch_t*
+crocksdb_writebatch_filter_cf(crocksdb_writebatch_t* b, const uint32_t* cf_ids,
+                              size_t num_cfs, char** errptr);
+
 extern C_ROCKSDB_LIBRARY_API const char* crocksdb_writebatch_data(
     crocksdb_writebatch_t*, size_t* size);
 extern C_ROCKSDB_LIBRARY_API void crocksdb_writebatch_set_save_point(
@@ -751,6 +862,61 @@ extern C_ROCKSDB_LIBRARY_API uint32_t
 crocksdb_writebatch_iterator_column_family_id(
     crocksdb_writebatch_iterator_t* it);
 
+/* Write batch with index */
+
+extern C_ROCKSDB_LIBRARY_API crocksdb_writebatch_wi_t*
+crocksdb_writebatch_wi_create(size_t reserved_bytes,
+                              unsigned char overwrite_keys);
+extern C_ROCKSDB_LIBRARY_API void crocksdb_writebatch_wi_destroy(
+    crocksdb_writebatch_wi_t*);
+extern C_ROCKSDB_LIBRARY_API void crocksdb_writebatch_wi_clear(
+    crocksdb_writebatch_wi_t*);
+extern C_ROCKSDB_LIBRARY_API int crocksdb_writebatch_wi_count(
+    crocksdb_writebatch_wi_t* b);
+extern C_ROCKSDB_LIBRARY_API void crocksdb_writebatch_wi_put(
+    crocksdb_writebatch_wi_t*, const char* key, size_t klen, const char* val,
+    size_t vlen, char** errptr);
+extern C_ROCKSDB_LIBRARY_API void crocksdb_writebatch_wi_put_cf(
+    crocksdb_writebatch_wi_t*, crocksdb_column_family_handle_t* column_family,
+    const char* key, size_t klen, const char* val, size_t vlen, char** errptr);
+extern C_ROCKSDB_LIBRARY_API void crocksdb_writebatch_wi_merge(
+    crocksdb_writebatch_wi_t*, const char* key, size_t klen, const char* val,
+    size_t vlen, char** errptr);
+extern C_ROCKSDB_LIBRARY_API void crocksdb_writebatch_wi_merge_cf(
+    crocksdb_writebatch_wi_t*, crocksdb_column_family_handle_t* column_family,
+    const char* key, size_t klen, const char* val, size_t vlen, char** errptr);
+extern C_ROCKSDB_LIBRARY_API void crocksdb_writebatch_wi_delete(
+    crocksdb_writebatch_wi_t*, const char* key, size_t klen, char** errptr);
+extern C_ROCKSDB_LIBRARY_API void crocksdb_writebatch_wi_delete_cf(
+    crocksdb_writebatch_wi_t*, crocksdb_column_family_handle_t* column_family,
+    const char* key, size_t klen, char** errptr);
+extern C_ROCKSDB_LIBRARY_API void crocksdb_writebatch_wi_single_delete(
+    crocksdb_writebatch_wi_t*, const char* key, size_t klen, char** errptr);
+extern C_ROCKSDB_LIBRARY_API void crocksdb_writebatch_wi_single_delete_cf(
+    crocksdb_writebatch_wi_t*, crocksdb_column_family_handle_t* column_family,
+    const char* key, size_t klen, char** errptr);
+extern C_ROCKSDB_LIBRARY_API void crocksdb_writebatch_wi_delete_range(
+    crocksdb_writebatch_wi_t*, const char* begin_key, size_t begin_keylen,
+    const char* end_key, size_t end_keylen, char** errptr);
+extern C_ROCKSDB_LIBRARY_API void crocksdb_writebatch_wi_delete_range_cf(
+    crocksdb_writebatch_wi_t*, crocksdb_column_family_handle_t* column_family,
+    const char* begin_key, size_t begin_keylen, const char* end_key,
+    size_t end_keylen, char** errptr);
+extern C_ROCKSDB_LIBRARY_API crocksdb_pinnableslice_t*
+crocksdb_writebatch_wi_get_from_batch_and_db_cf(
+    crocksdb_writebatch_wi_t* wbwi, crocksdb_t* db,
+    const crocksdb_readoptions_t* options,
+    crocksdb_column_family_handle_t* column_family, const char* key,
+    size_t keylen, char** errptr);
+/* Takes the ownership of `base_iterator`. */
+extern C_ROCKSDB_LIBRARY_API crocksdb_iterator_t*
+crocksdb_writebatch_wi_create_iterator_with_base_cf(
+    crocksdb_writebatch_wi_t* wbwi, crocksdb_iterator_t* base_iterator,
+    crocksdb_column_family_handle_t* column_family);
+extern C_ROCKSDB_LIBRARY_API void crocksdb_write_writebatch_wi(
+    crocksdb_t* db, const crocksdb_writeoptions_t* options,
+    crocksdb_writebatch_wi_t* wbwi, char** errptr);
+
 /* Block based table options */
 
 extern C_ROCKSDB_LIBRARY_API crocksdb_block_based_table_options_t*
@@ -865,6 +1031,8 @@ crocksdb_flushjobinfo_smallest_seqno(const crocksdb_flushjobinfo_t*);
 
 extern C_ROCKSDB_LIBRARY_API void crocksdb_reset_status(
     crocksdb_status_ptr_t* status_ptr);
+extern C_ROCKSDB_LIBRARY_API uint32_t
+crocksdb_status_ptr_get_severity(crocksdb_status_ptr_t* status_ptr);
 
 /* Compaction job info */
 extern C_ROCKSDB_LIBRARY_API void crocksdb_compactionjobinfo_status(
@@ -981,6 +1149,7 @@ typedef void (*on_stall_conditions_changed_cb)(
     void*, const crocksdb_writestallinfo_t*);
 typedef void (*crocksdb_logger_logv_cb)(void*, uint32_t log_level, const char*);
 typedef void (*on_memtable_sealed_cb)(void*, const crocksdb_memtableinfo_t*);
+typedef void (*on_error_recovery_completed_cb)(void*, crocksdb_status_ptr_t*);
 extern C_ROCKSDB_LIBRARY_API crocksdb_eventlistener_t*
 crocksdb_eventlistener_create(
     void* state_, void (*destructor_)(void*), on_flush_begin_cb on_flush_begin,
@@ -992,7 +1161,8 @@ crocksdb_eventlistener_create(
     on_external_file_ingested_cb on_external_file_ingested,
     on_background_error_cb on_background_error,
     on_stall_conditions_changed_cb on_stall_conditions_changed,
-    on_memtable_sealed_cb on_memtable_sealed);
+    on_memtable_sealed_cb on_memtable_sealed,
+    on_error_recovery_completed_cb on_error_recovery_completed);
 extern C_ROCKSDB_LIBRARY_API void crocksdb_eventlistener_destroy(
     crocksdb_eventlistener_t*);
 extern C_ROCKSDB_LIBRARY_API void crocksdb_options_add_eventlistener(
@@ -1050,6 +1220,8 @@ crocksdb_options_optimize_universal_style_compaction(
     crocksdb_options_t* opt, uint64_t memtable_memory_budget);
 extern C_ROCKSDB_LIBRARY_API void crocksdb_options_set_compaction_filter(
     crocksdb_options_t*, crocksdb_compactionfilter_t*);
+extern C_ROCKSDB_LIBRARY_API void crocksdb_options_set_wal_filter(
+    crocksdb_options_t*, crocksdb_walfilter_t*);
 extern C_ROCKSDB_LIBRARY_API void
 crocksdb_options_set_compaction_filter_factory(
     crocksdb_options_t*, crocksdb_compactionfilterfactory_t*);
@@ -1057,6 +1229,9 @@ extern C_ROCKSDB_LIBRARY_API void crocksdb_options_compaction_readahead_size(
     crocksdb_options_t*, size_t);
 extern C_ROCKSDB_LIBRARY_API void crocksdb_options_set_comparator(
     crocksdb_options_t*, crocksdb_comparator_t*);
+/* Keys must be suffixed with a fixed64 encoded user timestamp. */
+extern C_ROCKSDB_LIBRARY_API void
+crocksdb_options_set_bytewise_comparator_with_u64_ts(crocksdb_options_t*);
 extern C_ROCKSDB_LIBRARY_API void crocksdb_options_set_merge_operator(
     crocksdb_options_t*, crocksdb_mergeoperator_t*);
 extern C_ROCKSDB_LIBRARY_API void crocksdb_options_set_compression_per_level(
@@ -1214,6 +1389,12 @@ crocksdb_options_get_min_write_buffer_number_to_merge(crocksdb_options_t*);
 extern C_ROCKSDB_LIBRARY_API void
 crocksdb_options_set_max_write_buffer_number_to_maintain(crocksdb_options_t*,
                                                          int);
+extern C_ROCKSDB_LIBRARY_API void
+crocksdb_options_set_max_write_buffer_size_to_maintain(crocksdb_options_t*,
+                                                       int64_t);
+extern C_ROCKSDB_LIBRARY_API int64_t
+crocksdb_options_get_max_write_buffer_size_to_maintain(
+    const crocksdb_options_t*);
 extern C_ROCKSDB_LIBRARY_API void crocksdb_options_set_max_background_jobs(
     crocksdb_options_t*, int);
 extern C_ROCKSDB_LIBRARY_API int crocksdb_options_get_max_background_jobs(
@@ -1316,6 +1497,8 @@ extern C_ROCKSDB_LIBRARY_API unsigned char
 crocksdb_options_is_enable_multi_batch_write(crocksdb_options_t* opt);
 extern C_ROCKSDB_LIBRARY_API void crocksdb_options_set_unordered_write(
     crocksdb_options_t*, unsigned char);
+extern C_ROCKSDB_LIBRARY_API void crocksdb_options_set_two_write_queues(
+    crocksdb_options_t*, unsigned char);
 extern C_ROCKSDB_LIBRARY_API void
 crocksdb_options_set_allow_concurrent_memtable_write(crocksdb_options_t*,
                                                      unsigned char);
@@ -1331,6 +1514,14 @@ extern C_ROCKSDB_LIBRARY_API void crocksdb_options_set_disable_auto_compactions(
     crocksdb_options_t*, int);
 extern C_ROCKSDB_LIBRARY_API int crocksdb_options_get_disable_auto_compactions(
     const crocksdb_options_t*);
+extern C_ROCKSDB_LIBRARY_API void crocksdb_options_set_enable_blob_files(
+    crocksdb_options_t*, unsigned char);
+extern C_ROCKSDB_LIBRARY_API unsigned char
+crocksdb_options_get_enable_blob_files(const crocksdb_options_t*);
+extern C_ROCKSDB_LIBRARY_API void crocksdb_options_set_min_blob_size(
+    crocksdb_options_t*, uint64_t);
+extern C_ROCKSDB_LIBRARY_API unsigned char crocksdb_options_has_merge_operator(
+    const crocksdb_options_t*);
 extern C_ROCKSDB_LIBRARY_API void crocksdb_options_set_disable_write_stall(
     crocksdb_options_t*, unsigned char);
 extern C_ROCKSDB_LIBRARY_API unsigned char
@@ -1365,6 +1556,8 @@ extern C_ROCKSDB_LIBRARY_API void crocksdb_options_set_min_level_to_compress(
 
 extern C_ROCKSDB_LIBRARY_API void crocksdb_options_set_memtable_huge_page_size(
     crocksdb_options_t*, size_t);
+extern C_ROCKSDB_LIBRARY_API size_t
+crocksdb_options_get_memtable_huge_page_size(crocksdb_options_t*);
 
 extern C_ROCKSDB_LIBRARY_API void crocksdb_options_set_max_successive_merges(
     crocksdb_options_t*, size_t);
@@ -1435,6 +1628,10 @@ crocksdb_options_set_track_and_verify_wals_in_manifest(
 extern C_ROCKSDB_LIBRARY_API crocksdb_write_buffer_manager_t*
 crocksdb_write_buffer_manager_create(size_t flush_size, float stall_ratio,
                                      unsigned char flush_oldest_first);
+extern C_ROCKSDB_LIBRARY_API crocksdb_write_buffer_manager_t*
+crocksdb_write_buffer_manager_create_with_cache(
+    size_t flush_size, crocksdb_cache_t* cache, float stall_ratio,
+    unsigned char flush_oldest_first);
 extern C_ROCKSDB_LIBRARY_API void crocksdb_write_buffer_manager_set_flush_size(
     crocksdb_write_buffer_manager_t* wbm, size_t flush_size);
 extern C_ROCKSDB_LIBRARY_API size_t
@@ -1535,6 +1732,16 @@ crocksdb_compactionfilter_create(
 extern C_ROCKSDB_LIBRARY_API void crocksdb_compactionfilter_destroy(
     crocksdb_compactionfilter_t* filter);
 
+/* WAL Filter */
+
+extern C_ROCKSDB_LIBRARY_API crocksdb_walfilter_t* crocksdb_walfilter_create(
+    void* state, void (*destructor)(void*),
+    uint32_t (*log_record)(void*, const char* rep, size_t rep_length),
+    const char* (*name)(void*));
+
+extern C_ROCKSDB_LIBRARY_API void crocksdb_walfilter_destroy(
+    crocksdb_walfilter_t* filter);
+
 /* Compaction Filter Context */
 
 extern C_ROCKSDB_LIBRARY_API unsigned char
@@ -1645,6 +1852,10 @@ extern C_ROCKSDB_LIBRARY_API void crocksdb_readoptions_set_iterate_lower_bound(
     crocksdb_readoptions_t*, const char* key, size_t keylen);
 extern C_ROCKSDB_LIBRARY_API void crocksdb_readoptions_set_iterate_upper_bound(
     crocksdb_readoptions_t*, const char* key, size_t keylen);
+extern C_ROCKSDB_LIBRARY_API void crocksdb_readoptions_set_timestamp(
+    crocksdb_readoptions_t*, const char* ts, size_t tslen);
+extern C_ROCKSDB_LIBRARY_API void crocksdb_readoptions_set_iter_start_ts(
+    crocksdb_readoptions_t*, const char* ts, size_t tslen);
 extern C_ROCKSDB_LIBRARY_API void crocksdb_readoptions_set_read_tier(
     crocksdb_readoptions_t*, int);
 extern C_ROCKSDB_LIBRARY_API void crocksdb_readoptions_set_tailing(
@@ -1656,6 +1867,9 @@ extern C_ROCKSDB_LIBRARY_API void crocksdb_readoptions_set_readahead_size(
 extern C_ROCKSDB_LIBRARY_API void
 crocksdb_readoptions_set_max_skippable_internal_keys(crocksdb_readoptions_t*,
                                                      uint64_t);
+extern C_ROCKSDB_LIBRARY_API void
+crocksdb_readoptions_set_value_size_soft_limit(crocksdb_readoptions_t*,
+                                               uint64_t);
 extern C_ROCKSDB_LIBRARY_API void crocksdb_readoptions_set_total_order_seek(
     crocksdb_readoptions_t*, unsigned char);
 extern C_ROCKSDB_LIBRARY_API void crocksdb_readoptions_set_prefix_same_as_start(
@@ -1693,6 +1907,8 @@ extern C_ROCKSDB_LIBRARY_API void crocksdb_writeoptions_set_low_pri(
 extern C_ROCKSDB_LIBRARY_API void
 crocksdb_writeoptions_set_memtable_insert_hint_per_batch(
     crocksdb_writeoptions_t*, unsigned char);
+extern C_ROCKSDB_LIBRARY_API void crocksdb_writeoptions_set_timestamp(
+    crocksdb_writeoptions_t*, const char* ts, size_t tslen);
 
 /* Compact range options */
 
@@ -1732,6 +1948,22 @@ extern C_ROCKSDB_LIBRARY_API void
 crocksdb_flushoptions_set_check_if_compaction_disabled(crocksdb_flushoptions_t*,
                                                        unsigned char);
 
+/* Size approximation options */
+
+extern C_ROCKSDB_LIBRARY_API crocksdb_size_approximation_options_t*
+crocksdb_size_approximation_options_create();
+extern C_ROCKSDB_LIBRARY_API void crocksdb_size_approximation_options_destroy(
+    crocksdb_size_approximation_options_t*);
+extern C_ROCKSDB_LIBRARY_API void
+crocksdb_size_approximation_options_set_include_memtables(
+    crocksdb_size_approximation_options_t*, unsigned char);
+extern C_ROCKSDB_LIBRARY_API void
+crocksdb_size_approximation_options_set_include_files(
+    crocksdb_size_approximation_options_t*, unsigned char);
+extern C_ROCKSDB_LIBRARY_API void
+crocksdb_size_approximation_options_set_files_size_error_margin(
+    crocksdb_size_approximation_options_t*, double);
+
 /* Memory allocator */
 
 extern C_ROCKSDB_LIBRARY_API crocksdb_memory_allocator_t*
@@ -1763,10 +1995,19 @@ crocksdb_lru_cache_options_set_memory_allocator(crocksdb_lru_cache_options_t*,
                                                 crocksdb_memory_allocator_t*);
 extern C_ROCKSDB_LIBRARY_API crocksdb_cache_t* crocksdb_cache_create_lru(
     crocksdb_lru_cache_options_t*);
+/* Returns nullptr if clock cache is not supported by the build. */
+extern C_ROCKSDB_LIBRARY_API crocksdb_cache_t* crocksdb_cache_create_clock(
+    size_t capacity, int num_shard_bits, unsigned char strict_capacity_limit);
 extern C_ROCKSDB_LIBRARY_API void crocksdb_cache_destroy(
     crocksdb_cache_t* cache);
 extern C_ROCKSDB_LIBRARY_API void crocksdb_cache_set_capacity(
     crocksdb_cache_t* cache, size_t capacity);
+extern C_ROCKSDB_LIBRARY_API size_t
+crocksdb_cache_get_capacity(crocksdb_cache_t* cache);
+extern C_ROCKSDB_LIBRARY_API size_t
+crocksdb_cache_get_usage(crocksdb_cache_t* cache);
+extern C_ROCKSDB_LIBRARY_API size_t
+crocksdb_cache_get_pinned_usage(crocksdb_cache_t* cache);
 
 /* Env */
 
@@ -1778,6 +2019,11 @@ extern C_ROCKSDB_LIBRARY_API void crocksdb_env_set_background_threads(
     crocksdb_env_t* env, int n);
 extern C_ROCKSDB_LIBRARY_API void
 crocksdb_env_set_high_priority_background_threads(crocksdb_env_t* env, int n);
+extern C_ROCKSDB_LIBRARY_API void
+crocksdb_env_set_background_threads_with_priority(crocksdb_env_t* env, int n,
+                                                  int pri);
+extern C_ROCKSDB_LIBRARY_API int crocksdb_env_get_background_threads(
+    crocksdb_env_t* env, int pri);
 extern C_ROCKSDB_LIBRARY_API void crocksdb_env_join_all_threads(
     crocksdb_env_t* env);
 extern C_ROCKSDB_LIBRARY_API void crocksdb_env_file_exists(crocksdb_env_t* env,
@@ -1928,6 +2174,10 @@ extern C_ROCKSDB_LIBRARY_API void crocksdb_sstfilewriter_open(
 extern C_ROCKSDB_LIBRARY_API void crocksdb_sstfilewriter_put(
     crocksdb_sstfilewriter_t* writer, const char* key, size_t keylen,
     const char* val, size_t vallen, char** errptr);
+extern C_ROCKSDB_LIBRARY_API void crocksdb_sstfilewriter_put_with_ts(
+    crocksdb_sstfilewriter_t* writer, const char* key, size_t keylen,
+    const char* ts, size_t tslen, const char* val, size_t vallen,
+    char** errptr);
 extern C_ROCKSDB_LIBRARY_API void crocksdb_sstfilewriter_merge(
     crocksdb_sstfilewriter_t* writer, const char* key, size_t keylen,
     const char* val, size_t vallen, char** errptr);
@@ -1994,6 +2244,10 @@ extern C_ROCKSDB_LIBRARY_API void
 crocksdb_ingestexternalfileoptions_set_verify_checksums_before_ingest(
     crocksdb_ingestexternalfileoptions_t* opt,
     unsigned char verify_checksums_before_ingest);
+extern C_ROCKSDB_LIBRARY_API void
+crocksdb_ingestexternalfileoptions_set_fail_if_not_bottommost_level(
+    crocksdb_ingestexternalfileoptions_t* opt,
+    unsigned char fail_if_not_bottommost_level);
 extern C_ROCKSDB_LIBRARY_API void crocksdb_ingestexternalfileoptions_destroy(
     crocksdb_ingestexternalfileoptions_t* opt);
 extern C_ROCKSDB_LIBRARY_API void crocksdb_ingest_external_file(
@@ -2008,6 +2262,10 @@ crocksdb_ingest_external_file_optimized(
     crocksdb_t* db, crocksdb_column_family_handle_t* handle,
     const char* const* file_list, const size_t list_len,
     const crocksdb_ingestexternalfileoptions_t* opt, char** errptr);
+extern C_ROCKSDB_LIBRARY_API void crocksdb_ingest_external_file_from_buffer_cf(
+    crocksdb_t* db, crocksdb_column_family_handle_t* handle, const char* data,
+    size_t data_len, const char* tmp_file,
+    const crocksdb_ingestexternalfileoptions_t* opt, char** errptr);
 
 /* SliceTransform */
 
@@ -2123,6 +2381,17 @@ extern C_ROCKSDB_LIBRARY_API crocksdb_pinnableslice_t* crocksdb_get_pinned_cf(
     crocksdb_t* db, const crocksdb_readoptions_t* options,
     crocksdb_column_family_handle_t* column_family, const char* key,
     size_t keylen, char** errptr);
+// Like crocksdb_get_pinned_cf, but only reads memtables and the block cache.
+// Returns NULL without an error if the key can't be found there.
+extern C_ROCKSDB_LIBRARY_API crocksdb_pinnableslice_t*
+crocksdb_get_pinned_cf_cache_only(
+    crocksdb_t* db, const crocksdb_readoptions_t* options,
+    crocksdb_column_family_handle_t* column_family, const char* key,
+    size_t keylen, char** errptr);
+extern C_ROCKSDB_LIBRARY_API unsigned char crocksdb_key_may_exist_cf(
+    crocksdb_t* db, const crocksdb_readoptions_t* options,
+    crocksdb_column_family_handle_t* column_family, const char* key,
+    size_t keylen, crocksdb_pinnableslice_t** value);
 extern C_ROCKSDB_LIBRARY_API void crocksdb_pinnableslice_destroy(
     crocksdb_pinnableslice_t* v);
 extern C_ROCKSDB_LIBRARY_API const char* crocksdb_pinnableslice_value(
@@ -2318,6 +2587,13 @@ crocksdb_sst_file_meta_data_smallestkey(const crocksdb_sst_file_meta_data_t*,
                                         size_t*);
 extern C_ROCKSDB_LIBRARY_API const char* crocksdb_sst_file_meta_data_largestkey(
     const crocksdb_sst_file_meta_data_t*, size_t*);
+extern C_ROCKSDB_LIBRARY_API uint64_t
+crocksdb_sst_file_meta_data_num_entries(const crocksdb_sst_file_meta_data_t*);
+extern C_ROCKSDB_LIBRARY_API uint64_t crocksdb_sst_file_meta_data_num_deletions(
+    const crocksdb_sst_file_meta_data_t*);
+extern C_ROCKSDB_LIBRARY_API unsigned char
+crocksdb_sst_file_meta_data_being_compacted(
+    const crocksdb_sst_file_meta_data_t*);
 
 /* CompactFiles */
 extern C_ROCKSDB_LIBRARY_API crocksdb_compaction_options_t*
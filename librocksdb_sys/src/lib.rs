@@ -76,14 +76,20 @@ pub struct DBCFHandle(c_void);
 #[repr(C)]
 pub struct DBWriteBatch(c_void);
 #[repr(C)]
+pub struct DBWriteBatchWithIndex(c_void);
+#[repr(C)]
 pub struct DBPostWriteCallback(c_void);
 #[repr(C)]
 pub struct DBComparator(c_void);
 #[repr(C)]
 pub struct DBFlushOptions(c_void);
 #[repr(C)]
+pub struct DBSizeApproximationOptions(c_void);
+#[repr(C)]
 pub struct DBCompactionFilter(c_void);
 #[repr(C)]
+pub struct DBWalFilter(c_void);
+#[repr(C)]
 pub struct DBCompactionFilterFactory(c_void);
 #[repr(C)]
 pub struct DBCompactionFilterContext(c_void);
@@ -102,6 +108,8 @@ pub struct IngestExternalFileOptions(c_void);
 #[repr(C)]
 pub struct DBBackupEngine(c_void);
 #[repr(C)]
+pub struct DBBackupEngineInfo(c_void);
+#[repr(C)]
 pub struct DBRestoreOptions(c_void);
 #[repr(C)]
 pub struct DBSliceTransform(c_void);
@@ -190,6 +198,8 @@ pub struct DBSstPartitionerFactory(c_void);
 #[repr(C)]
 pub struct DBWriteBatchIterator(c_void);
 #[repr(C)]
+pub struct DBWalIterator(c_void);
+#[repr(C)]
 pub struct DBFileSystemInspectorInstance(c_void);
 
 // @needs_manual_sync
@@ -258,6 +268,42 @@ pub enum DBCompressionType {
     Disable = 0xff,
 }
 
+impl fmt::Display for DBCompressionType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            DBCompressionType::No => "none",
+            DBCompressionType::Snappy => "snappy",
+            DBCompressionType::Zlib => "zlib",
+            DBCompressionType::Bz2 => "bzip2",
+            DBCompressionType::Lz4 => "lz4",
+            DBCompressionType::Lz4hc => "lz4hc",
+            DBCompressionType::Zstd => "zstd",
+            DBCompressionType::ZstdNotFinal => "zstd_not_final",
+            DBCompressionType::Disable => "disable",
+        };
+        f.write_str(name)
+    }
+}
+
+impl DBCompressionType {
+    /// Parses the names printed by `Display`, ignoring case.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<DBCompressionType> {
+        match s.to_ascii_lowercase().as_str() {
+            "none" => Some(DBCompressionType::No),
+            "snappy" => Some(DBCompressionType::Snappy),
+            "zlib" => Some(DBCompressionType::Zlib),
+            "bzip2" => Some(DBCompressionType::Bz2),
+            "lz4" => Some(DBCompressionType::Lz4),
+            "lz4hc" => Some(DBCompressionType::Lz4hc),
+            "zstd" => Some(DBCompressionType::Zstd),
+            "zstd_not_final" => Some(DBCompressionType::ZstdNotFinal),
+            "disable" => Some(DBCompressionType::Disable),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[repr(u32)]
 pub enum DBCompactionStyle {
@@ -276,6 +322,16 @@ pub enum DBRecoveryMode {
     SkipAnyCorruptedRecords = 3,
 }
 
+/// Priority of the background thread pools of an `Env`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum DBEnvPriority {
+    Bottom = 0,
+    Low = 1,
+    High = 2,
+    User = 3,
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[repr(u32)]
 pub enum CompactionPriority {
@@ -444,6 +500,21 @@ pub enum IndexType {
     TwoLevelIndexSearch = 2,
 }
 
+/// Where a read is allowed to look for data.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ReadTier {
+    /// Memtables, the block cache, the OS cache and storage.
+    ReadAllTier = 0,
+    /// Memtables and the block cache only. Reads that would need IO fail
+    /// with `Incomplete`.
+    BlockCacheTier = 1,
+    /// Persisted data only. Memtables are skipped if the WAL is disabled.
+    PersistedTier = 2,
+    /// Memtables only. Only supported by iterators.
+    MemtableTier = 3,
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[repr(u32)]
 pub enum PrepopulateBlockCache {
@@ -474,6 +545,17 @@ pub enum DBBackgroundErrorReason {
     ManifestWriteNoWAL = 6,
 }
 
+// @needs_manual_sync
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum DBStatusSeverity {
+    NoError = 0,
+    SoftError = 1,
+    HardError = 2,
+    FatalError = 3,
+    UnrecoverableError = 4,
+}
+
 #[cfg(feature = "encryption")]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[repr(u32)]
@@ -549,6 +631,17 @@ pub enum CompactionFilterDecision {
     RemoveAndSkipUntil = 3,
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum WalFilterResult {
+    /// Replays the record.
+    Continue = 0,
+    /// Skips the record and goes on with the next one.
+    IgnoreCurrentRecord = 1,
+    /// Stops the replay, the remaining records are dropped.
+    Stop = 2,
+}
+
 // @needs_manual_sync
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[repr(u32)]
@@ -656,7 +749,16 @@ extern "C" {
         allocator: *mut DBMemoryAllocator,
     );
     pub fn crocksdb_cache_create_lru(opt: *mut DBLRUCacheOptions) -> *mut DBCache;
+    pub fn crocksdb_cache_create_clock(
+        capacity: size_t,
+        num_shard_bits: c_int,
+        strict_capacity_limit: bool,
+    ) -> *mut DBCache;
     pub fn crocksdb_cache_destroy(cache: *mut DBCache);
+    pub fn crocksdb_cache_set_capacity(cache: *mut DBCache, capacity: size_t);
+    pub fn crocksdb_cache_get_capacity(cache: *mut DBCache) -> size_t;
+    pub fn crocksdb_cache_get_usage(cache: *mut DBCache) -> size_t;
+    pub fn crocksdb_cache_get_pinned_usage(cache: *mut DBCache) -> size_t;
 
     pub fn crocksdb_block_based_options_create() -> *mut DBBlockBasedTableOptions;
     pub fn crocksdb_block_based_options_destroy(opts: *mut DBBlockBasedTableOptions);
@@ -773,6 +875,7 @@ extern "C" {
         options: *mut Options,
         filter: *mut DBCompactionFilter,
     );
+    pub fn crocksdb_options_set_wal_filter(options: *mut Options, filter: *mut DBWalFilter);
     pub fn crocksdb_options_set_compaction_filter_factory(
         options: *mut Options,
         filter: *mut DBCompactionFilterFactory,
@@ -787,6 +890,7 @@ extern "C" {
     pub fn crocksdb_options_set_enable_multi_batch_write(options: *mut Options, v: bool);
     pub fn crocksdb_options_is_enable_multi_batch_write(options: *mut Options) -> bool;
     pub fn crocksdb_options_set_unordered_write(options: *mut Options, v: bool);
+    pub fn crocksdb_options_set_two_write_queues(options: *mut Options, v: bool);
     pub fn crocksdb_options_set_allow_concurrent_memtable_write(options: *mut Options, v: bool);
     pub fn crocksdb_options_set_manual_wal_flush(options: *mut Options, v: bool);
     pub fn crocksdb_options_optimize_for_point_lookup(
@@ -796,6 +900,8 @@ extern "C" {
     pub fn crocksdb_options_set_table_cache_numshardbits(options: *mut Options, bits: c_int);
     pub fn crocksdb_options_set_writable_file_max_buffer_size(options: *mut Options, nbytes: c_int);
     pub fn crocksdb_options_set_max_write_buffer_number(options: *mut Options, bufno: c_int);
+    pub fn crocksdb_options_set_max_write_buffer_size_to_maintain(options: *mut Options, size: i64);
+    pub fn crocksdb_options_get_max_write_buffer_size_to_maintain(options: *const Options) -> i64;
     pub fn crocksdb_options_get_max_write_buffer_number(options: *mut Options) -> c_int;
     pub fn crocksdb_options_set_min_write_buffer_number_to_merge(
         options: *mut Options,
@@ -813,6 +919,8 @@ extern "C" {
     pub fn crocksdb_options_get_level0_stop_writes_trigger(options: *mut Options) -> c_int;
     pub fn crocksdb_options_set_write_buffer_size(options: *mut Options, bytes: u64);
     pub fn crocksdb_options_get_write_buffer_size(options: *mut Options) -> u64;
+    pub fn crocksdb_options_set_memtable_huge_page_size(options: *mut Options, size: size_t);
+    pub fn crocksdb_options_get_memtable_huge_page_size(options: *mut Options) -> size_t;
     pub fn crocksdb_options_set_target_file_size_base(options: *mut Options, bytes: u64);
     pub fn crocksdb_options_get_target_file_size_base(options: *const Options) -> u64;
     pub fn crocksdb_options_set_target_file_size_multiplier(options: *mut Options, mul: c_int);
@@ -894,6 +1002,10 @@ extern "C" {
     pub fn crocksdb_options_get_max_background_flushes(options: *const Options) -> c_int;
     pub fn crocksdb_options_set_disable_auto_compactions(options: *mut Options, v: c_int);
     pub fn crocksdb_options_get_disable_auto_compactions(options: *const Options) -> c_int;
+    pub fn crocksdb_options_set_enable_blob_files(options: *mut Options, v: bool);
+    pub fn crocksdb_options_get_enable_blob_files(options: *const Options) -> bool;
+    pub fn crocksdb_options_set_min_blob_size(options: *mut Options, v: u64);
+    pub fn crocksdb_options_has_merge_operator(options: *const Options) -> bool;
     pub fn crocksdb_options_set_disable_write_stall(options: *mut Options, v: bool);
     pub fn crocksdb_options_get_disable_write_stall(options: *const Options) -> bool;
     pub fn crocksdb_options_set_report_bg_io_stats(options: *mut Options, v: c_int);
@@ -969,6 +1081,7 @@ extern "C" {
         options: *mut Options,
         ratio: c_double,
     );
+    pub fn crocksdb_options_set_bloom_locality(options: *mut Options, v: u32);
     pub fn crocksdb_options_set_delayed_write_rate(options: *mut Options, rate: u64);
     pub fn crocksdb_options_set_force_consistency_checks(options: *mut Options, v: bool);
     pub fn crocksdb_options_get_force_consistency_checks(options: *mut Options) -> bool;
@@ -1048,6 +1161,12 @@ extern "C" {
         stall_ratio: c_float,
         flush_oldest_first: bool,
     ) -> *mut DBWriteBufferManager;
+    pub fn crocksdb_write_buffer_manager_create_with_cache(
+        flush_size: size_t,
+        cache: *mut DBCache,
+        stall_ratio: c_float,
+        flush_oldest_first: bool,
+    ) -> *mut DBWriteBufferManager;
     pub fn crocksdb_write_buffer_manager_set_flush_size(
         wbm: *mut DBWriteBufferManager,
         flush_size: size_t,
@@ -1146,6 +1265,11 @@ extern "C" {
         writeopts: *mut DBWriteOptions,
         v: bool,
     );
+    pub fn crocksdb_writeoptions_set_timestamp(
+        writeopts: *mut DBWriteOptions,
+        ts: *const u8,
+        tsLen: size_t,
+    );
     pub fn crocksdb_put(
         db: *mut DBInstance,
         writeopts: *mut DBWriteOptions,
@@ -1185,7 +1309,17 @@ extern "C" {
         k: *const u8,
         kLen: size_t,
     );
-    pub fn crocksdb_readoptions_set_read_tier(readopts: *mut DBReadOptions, tier: c_int);
+    pub fn crocksdb_readoptions_set_timestamp(
+        readopts: *mut DBReadOptions,
+        ts: *const u8,
+        tsLen: size_t,
+    );
+    pub fn crocksdb_readoptions_set_iter_start_ts(
+        readopts: *mut DBReadOptions,
+        ts: *const u8,
+        tsLen: size_t,
+    );
+    pub fn crocksdb_readoptions_set_read_tier(readopts: *mut DBReadOptions, tier: ReadTier);
     pub fn crocksdb_readoptions_set_tailing(readopts: *mut DBReadOptions, v: bool);
     pub fn crocksdb_readoptions_set_managed(readopts: *mut DBReadOptions, v: bool);
     pub fn crocksdb_readoptions_set_readahead_size(readopts: *mut DBReadOptions, size: size_t);
@@ -1193,6 +1327,7 @@ extern "C" {
         readopts: *mut DBReadOptions,
         n: u64,
     );
+    pub fn crocksdb_readoptions_set_value_size_soft_limit(readopts: *mut DBReadOptions, v: u64);
     pub fn crocksdb_readoptions_set_total_order_seek(readopts: *mut DBReadOptions, v: bool);
     pub fn crocksdb_readoptions_set_prefix_same_as_start(readopts: *mut DBReadOptions, v: bool);
     pub fn crocksdb_readoptions_set_pin_data(readopts: *mut DBReadOptions, v: bool);
@@ -1225,6 +1360,58 @@ extern "C" {
         valLen: *const size_t,
         err: *mut *mut c_char,
     ) -> *mut u8;
+    pub fn crocksdb_multi_get_cf(
+        db: *mut DBInstance,
+        readopts: *const DBReadOptions,
+        column_families: *const *const DBCFHandle,
+        num_keys: size_t,
+        keys_list: *const *const u8,
+        keys_list_sizes: *const size_t,
+        values_list: *mut *mut u8,
+        values_list_sizes: *mut size_t,
+        errs: *mut *mut c_char,
+    );
+    pub fn crocksdb_multi_get_cf_with_ts(
+        db: *mut DBInstance,
+        readopts: *const DBReadOptions,
+        column_families: *const *const DBCFHandle,
+        num_keys: size_t,
+        keys_list: *const *const u8,
+        keys_list_sizes: *const size_t,
+        ctx: *mut c_void,
+        on_result: extern "C" fn(
+            ctx: *mut c_void,
+            idx: size_t,
+            v: *const u8,
+            vlen: size_t,
+            ts: *const u8,
+            tslen: size_t,
+        ),
+        err: *mut *mut c_char,
+    );
+    pub fn crocksdb_get_versions_cf_with_ts(
+        db: *mut DBInstance,
+        readopts: *const DBReadOptions,
+        cf_handle: *mut DBCFHandle,
+        key: *const u8,
+        keylen: size_t,
+        ctx: *mut c_void,
+        on_version: extern "C" fn(*mut c_void, *const u8, size_t, *const u8, size_t),
+        err: *mut *mut c_char,
+    );
+    pub fn crocksdb_get_updates_since(
+        db: *mut DBInstance,
+        seq_number: u64,
+        err: *mut *mut c_char,
+    ) -> *mut DBWalIterator;
+    pub fn crocksdb_wal_iter_valid(iter: *const DBWalIterator) -> bool;
+    pub fn crocksdb_wal_iter_next(iter: *mut DBWalIterator);
+    pub fn crocksdb_wal_iter_status(iter: *const DBWalIterator, err: *mut *mut c_char);
+    pub fn crocksdb_wal_iter_get_batch(
+        iter: *mut DBWalIterator,
+        seq: *mut u64,
+    ) -> *mut DBWriteBatch;
+    pub fn crocksdb_wal_iter_destroy(iter: *mut DBWalIterator);
     pub fn crocksdb_create_iterator(
         db: *mut DBInstance,
         readopts: *const DBReadOptions,
@@ -1234,6 +1421,14 @@ extern "C" {
         readopts: *const DBReadOptions,
         cf_handle: *mut DBCFHandle,
     ) -> *mut DBIterator;
+    pub fn crocksdb_create_iterators(
+        db: *mut DBInstance,
+        readopts: *const DBReadOptions,
+        column_families: *const *mut DBCFHandle,
+        iterators: *mut *mut DBIterator,
+        size: size_t,
+        err: *mut *mut c_char,
+    );
     pub fn crocksdb_create_snapshot(db: *mut DBInstance) -> *const DBSnapshot;
     pub fn crocksdb_release_snapshot(db: *mut DBInstance, snapshot: *const DBSnapshot);
     pub fn crocksdb_get_snapshot_sequence_number(snapshot: *const DBSnapshot) -> u64;
@@ -1487,6 +1682,48 @@ extern "C" {
             klen: size_t,
         ) -> (),
     );
+    pub fn crocksdb_writebatch_iterate_handler(
+        batch: *mut DBWriteBatch,
+        state: *mut c_void,
+        put_cf_fn: unsafe extern "C" fn(
+            state: *mut c_void,
+            cf: u32,
+            k: *const u8,
+            klen: size_t,
+            v: *const u8,
+            vlen: size_t,
+        ),
+        delete_cf_fn: unsafe extern "C" fn(state: *mut c_void, cf: u32, k: *const u8, klen: size_t),
+        single_delete_cf_fn: unsafe extern "C" fn(
+            state: *mut c_void,
+            cf: u32,
+            k: *const u8,
+            klen: size_t,
+        ),
+        merge_cf_fn: unsafe extern "C" fn(
+            state: *mut c_void,
+            cf: u32,
+            k: *const u8,
+            klen: size_t,
+            v: *const u8,
+            vlen: size_t,
+        ),
+        delete_range_cf_fn: unsafe extern "C" fn(
+            state: *mut c_void,
+            cf: u32,
+            begin_key: *const u8,
+            begin_keylen: size_t,
+            end_key: *const u8,
+            end_keylen: size_t,
+        ),
+        err: *mut *mut c_char,
+    );
+    pub fn crocksdb_writebatch_filter_cf(
+        batch: *mut DBWriteBatch,
+        cf_ids: *const u32,
+        num_cfs: size_t,
+        err: *mut *mut c_char,
+    ) -> *mut DBWriteBatch;
     pub fn crocksdb_writebatch_data(batch: *mut DBWriteBatch, size: *mut size_t) -> *const u8;
     pub fn crocksdb_writebatch_set_save_point(batch: *mut DBWriteBatch);
     pub fn crocksdb_writebatch_pop_save_point(batch: *mut DBWriteBatch, err: *mut *mut c_char);
@@ -1523,8 +1760,114 @@ extern "C" {
     ) -> *mut u8;
     pub fn crocksdb_writebatch_iterator_value_type(it: *mut DBWriteBatchIterator) -> DBValueType;
     pub fn crocksdb_writebatch_iterator_column_family_id(it: *mut DBWriteBatchIterator) -> u32;
+    // WriteBatchWithIndex
+    pub fn crocksdb_writebatch_wi_create(
+        reserved_bytes: size_t,
+        overwrite_keys: bool,
+    ) -> *mut DBWriteBatchWithIndex;
+    pub fn crocksdb_writebatch_wi_destroy(batch: *mut DBWriteBatchWithIndex);
+    pub fn crocksdb_writebatch_wi_clear(batch: *mut DBWriteBatchWithIndex);
+    pub fn crocksdb_writebatch_wi_count(batch: *mut DBWriteBatchWithIndex) -> c_int;
+    pub fn crocksdb_writebatch_wi_put(
+        batch: *mut DBWriteBatchWithIndex,
+        key: *const u8,
+        klen: size_t,
+        val: *const u8,
+        vlen: size_t,
+        err: *mut *mut c_char,
+    );
+    pub fn crocksdb_writebatch_wi_put_cf(
+        batch: *mut DBWriteBatchWithIndex,
+        cf: *mut DBCFHandle,
+        key: *const u8,
+        klen: size_t,
+        val: *const u8,
+        vlen: size_t,
+        err: *mut *mut c_char,
+    );
+    pub fn crocksdb_writebatch_wi_merge(
+        batch: *mut DBWriteBatchWithIndex,
+        key: *const u8,
+        klen: size_t,
+        val: *const u8,
+        vlen: size_t,
+        err: *mut *mut c_char,
+    );
+    pub fn crocksdb_writebatch_wi_merge_cf(
+        batch: *mut DBWriteBatchWithIndex,
+        cf: *mut DBCFHandle,
+        key: *const u8,
+        klen: size_t,
+        val: *const u8,
+        vlen: size_t,
+        err: *mut *mut c_char,
+    );
+    pub fn crocksdb_writebatch_wi_delete(
+        batch: *mut DBWriteBatchWithIndex,
+        key: *const u8,
+        klen: size_t,
+        err: *mut *mut c_char,
+    );
+    pub fn crocksdb_writebatch_wi_delete_cf(
+        batch: *mut DBWriteBatchWithIndex,
+        cf: *mut DBCFHandle,
+        key: *const u8,
+        klen: size_t,
+        err: *mut *mut c_char,
+    );
+    pub fn crocksdb_writebatch_wi_single_delete(
+        batch: *mut DBWriteBatchWithIndex,
+        key: *const u8,
+        klen: size_t,
+        err: *mut *mut c_char,
+    );
+    pub fn crocksdb_writebatch_wi_single_delete_cf(
+        batch: *mut DBWriteBatchWithIndex,
+        cf: *mut DBCFHandle,
+        key: *const u8,
+        klen: size_t,
+        err: *mut *mut c_char,
+    );
+    pub fn crocksdb_writebatch_wi_delete_range(
+        batch: *mut DBWriteBatchWithIndex,
+        begin_key: *const u8,
+        begin_keylen: size_t,
+        end_key: *const u8,
+        end_keylen: size_t,
+        err: *mut *mut c_char,
+    );
+    pub fn crocksdb_writebatch_wi_delete_range_cf(
+        batch: *mut DBWriteBatchWithIndex,
+        cf: *mut DBCFHandle,
+        begin_key: *const u8,
+        begin_keylen: size_t,
+        end_key: *const u8,
+        end_keylen: size_t,
+        err: *mut *mut c_char,
+    );
+    pub fn crocksdb_writebatch_wi_get_from_batch_and_db_cf(
+        batch: *mut DBWriteBatchWithIndex,
+        db: *mut DBInstance,
+        readopts: *const DBReadOptions,
+        cf: *mut DBCFHandle,
+        key: *const u8,
+        keylen: size_t,
+        err: *mut *mut c_char,
+    ) -> *mut DBPinnableSlice;
+    pub fn crocksdb_writebatch_wi_create_iterator_with_base_cf(
+        batch: *mut DBWriteBatchWithIndex,
+        base_iter: *mut DBIterator,
+        cf: *mut DBCFHandle,
+    ) -> *mut DBIterator;
+    pub fn crocksdb_write_writebatch_wi(
+        db: *mut DBInstance,
+        writeopts: *const DBWriteOptions,
+        batch: *mut DBWriteBatchWithIndex,
+        err: *mut *mut c_char,
+    );
     // Comparator
     pub fn crocksdb_options_set_comparator(options: *mut Options, cb: *mut DBComparator);
+    pub fn crocksdb_options_set_bytewise_comparator_with_u64_ts(options: *mut Options);
     pub fn crocksdb_comparator_create(
         state: *mut c_void,
         destroy: unsafe extern "C" fn(*mut c_void) -> (),
@@ -1570,6 +1913,23 @@ extern "C" {
         error_if_log_file_exist: bool,
         err: *mut *mut c_char,
     ) -> *mut DBInstance;
+    pub fn crocksdb_open_as_secondary(
+        options: *const Options,
+        path: *const c_char,
+        secondary_path: *const c_char,
+        err: *mut *mut c_char,
+    ) -> *mut DBInstance;
+    pub fn crocksdb_open_as_secondary_column_families(
+        options: *const Options,
+        path: *const c_char,
+        secondary_path: *const c_char,
+        num_column_families: c_int,
+        column_family_names: *const *const c_char,
+        column_family_options: *const *const Options,
+        column_family_handles: *const *mut DBCFHandle,
+        err: *mut *mut c_char,
+    ) -> *mut DBInstance;
+    pub fn crocksdb_try_catch_up_with_primary(db: *mut DBInstance, err: *mut *mut c_char);
     pub fn crocksdb_create_column_family(
         db: *mut DBInstance,
         column_family_options: *const Options,
@@ -1582,6 +1942,9 @@ extern "C" {
         err: *mut *mut c_char,
     );
     pub fn crocksdb_column_family_handle_id(column_family_handle: *mut DBCFHandle) -> u32;
+    pub fn crocksdb_column_family_handle_timestamp_size(
+        column_family_handle: *mut DBCFHandle,
+    ) -> size_t;
     pub fn crocksdb_column_family_handle_destroy(column_family_handle: *mut DBCFHandle);
     pub fn crocksdb_list_column_families(
         db: *const Options,
@@ -1590,6 +1953,14 @@ extern "C" {
         err: *mut *mut c_char,
     ) -> *mut *mut c_char;
     pub fn crocksdb_list_column_families_destroy(list: *mut *mut c_char, len: size_t);
+    pub fn crocksdb_get_live_file_names(
+        db: *mut DBInstance,
+        flush_memtable: bool,
+        len: *mut size_t,
+        manifest_size: *mut u64,
+        err: *mut *mut c_char,
+    ) -> *mut *mut c_char;
+    pub fn crocksdb_live_file_names_destroy(list: *mut *mut c_char, len: size_t);
 
     // Flush options
     pub fn crocksdb_flushoptions_create() -> *mut DBFlushOptions;
@@ -1602,6 +1973,21 @@ extern "C" {
         check: bool,
     );
 
+    pub fn crocksdb_size_approximation_options_create() -> *mut DBSizeApproximationOptions;
+    pub fn crocksdb_size_approximation_options_destroy(opt: *mut DBSizeApproximationOptions);
+    pub fn crocksdb_size_approximation_options_set_include_memtables(
+        opt: *mut DBSizeApproximationOptions,
+        include: bool,
+    );
+    pub fn crocksdb_size_approximation_options_set_include_files(
+        opt: *mut DBSizeApproximationOptions,
+        include: bool,
+    );
+    pub fn crocksdb_size_approximation_options_set_files_size_error_margin(
+        opt: *mut DBSizeApproximationOptions,
+        margin: c_double,
+    );
+
     pub fn crocksdb_flush(
         db: *mut DBInstance,
         options: *const DBFlushOptions,
@@ -1644,6 +2030,30 @@ extern "C" {
         range_limit_key_len: *const size_t,
         sizes: *mut u64,
     );
+    pub fn crocksdb_approximate_sizes_cf_with_flags(
+        db: *mut DBInstance,
+        cf: *mut DBCFHandle,
+        num_ranges: c_int,
+        range_start_key: *const *const u8,
+        range_start_key_len: *const size_t,
+        range_limit_key: *const *const u8,
+        range_limit_key_len: *const size_t,
+        include_flags: u8,
+        sizes: *mut u64,
+        err: *mut *mut c_char,
+    );
+    pub fn crocksdb_approximate_sizes_cf_opt(
+        db: *mut DBInstance,
+        cf: *mut DBCFHandle,
+        options: *const DBSizeApproximationOptions,
+        num_ranges: c_int,
+        range_start_key: *const *const u8,
+        range_start_key_len: *const size_t,
+        range_limit_key: *const *const u8,
+        range_limit_key_len: *const size_t,
+        sizes: *mut u64,
+        err: *mut *mut c_char,
+    );
     pub fn crocksdb_approximate_memtable_stats(
         db: *const DBInstance,
         range_start_key: *const u8,
@@ -1805,6 +2215,15 @@ extern "C" {
     ) -> *mut DBCompactionFilter;
     pub fn crocksdb_compactionfilter_destroy(filter: *mut DBCompactionFilter);
 
+    // WAL filter
+    pub fn crocksdb_walfilter_create(
+        state: *mut c_void,
+        destructor: extern "C" fn(*mut c_void),
+        log_record: extern "C" fn(*mut c_void, *const u8, size_t) -> WalFilterResult,
+        name: extern "C" fn(*mut c_void) -> *const c_char,
+    ) -> *mut DBWalFilter;
+    pub fn crocksdb_walfilter_destroy(filter: *mut DBWalFilter);
+
     // Compaction filter context
     pub fn crocksdb_compactionfiltercontext_is_full_compaction(
         context: *const DBCompactionFilterContext,
@@ -1871,6 +2290,12 @@ extern "C" {
     pub fn crocksdb_env_set_background_threads(env: *mut DBEnv, n: c_int);
     pub fn crocksdb_env_set_high_priority_background_threads(env: *mut DBEnv, n: c_int);
     pub fn crocksdb_env_get_high_priority_background_threads(env: *mut DBEnv) -> c_int;
+    pub fn crocksdb_env_set_background_threads_with_priority(
+        env: *mut DBEnv,
+        n: c_int,
+        pri: DBEnvPriority,
+    );
+    pub fn crocksdb_env_get_background_threads(env: *mut DBEnv, pri: DBEnvPriority) -> c_int;
 
     // EnvOptions
     pub fn crocksdb_envoptions_create() -> *mut EnvOptions;
@@ -1925,6 +2350,10 @@ extern "C" {
         opt: *mut IngestExternalFileOptions,
         verify_checksums_before_ingest: bool,
     );
+    pub fn crocksdb_ingestexternalfileoptions_set_fail_if_not_bottommost_level(
+        opt: *mut IngestExternalFileOptions,
+        fail_if_not_bottommost_level: bool,
+    );
     pub fn crocksdb_ingestexternalfileoptions_destroy(opt: *mut IngestExternalFileOptions);
 
     // KeyManagedEncryptedEnv
@@ -2095,6 +2524,16 @@ extern "C" {
         val_len: size_t,
         err: *mut *mut c_char,
     );
+    pub fn crocksdb_sstfilewriter_put_with_ts(
+        writer: *mut SstFileWriter,
+        key: *const u8,
+        key_len: size_t,
+        ts: *const u8,
+        ts_len: size_t,
+        val: *const u8,
+        val_len: size_t,
+        err: *mut *mut c_char,
+    );
     pub fn crocksdb_sstfilewriter_merge(
         writer: *mut SstFileWriter,
         key: *const u8,
@@ -2167,6 +2606,15 @@ extern "C" {
         opt: *const IngestExternalFileOptions,
         err: *mut *mut c_char,
     ) -> bool;
+    pub fn crocksdb_ingest_external_file_from_buffer_cf(
+        db: *mut DBInstance,
+        handle: *const DBCFHandle,
+        data: *const c_char,
+        data_len: size_t,
+        tmp_file: *const c_char,
+        opt: *const IngestExternalFileOptions,
+        err: *mut *mut c_char,
+    );
 
     // Restore Option
     pub fn crocksdb_restore_options_create() -> *mut DBRestoreOptions;
@@ -2185,6 +2633,48 @@ extern "C" {
         db: *mut DBInstance,
         err: *mut *mut c_char,
     );
+    pub fn crocksdb_backup_engine_create_new_backup_flush(
+        be: *mut DBBackupEngine,
+        db: *mut DBInstance,
+        flush_before_backup: bool,
+        err: *mut *mut c_char,
+    );
+    pub fn crocksdb_backup_engine_purge_old_backups(
+        be: *mut DBBackupEngine,
+        num_backups_to_keep: u32,
+        err: *mut *mut c_char,
+    );
+    pub fn crocksdb_backup_engine_restore_db_from_backup(
+        be: *mut DBBackupEngine,
+        backup_id: u32,
+        db_path: *const c_char,
+        wal_path: *const c_char,
+        ropts: *const DBRestoreOptions,
+        err: *mut *mut c_char,
+    );
+    pub fn crocksdb_backup_engine_verify_backup(
+        be: *mut DBBackupEngine,
+        backup_id: u32,
+        err: *mut *mut c_char,
+    );
+    pub fn crocksdb_backup_engine_get_backup_info(
+        be: *mut DBBackupEngine,
+    ) -> *const DBBackupEngineInfo;
+    pub fn crocksdb_backup_engine_info_count(info: *const DBBackupEngineInfo) -> c_int;
+    pub fn crocksdb_backup_engine_info_timestamp(
+        info: *const DBBackupEngineInfo,
+        index: c_int,
+    ) -> i64;
+    pub fn crocksdb_backup_engine_info_backup_id(
+        info: *const DBBackupEngineInfo,
+        index: c_int,
+    ) -> u32;
+    pub fn crocksdb_backup_engine_info_size(info: *const DBBackupEngineInfo, index: c_int) -> u64;
+    pub fn crocksdb_backup_engine_info_number_files(
+        info: *const DBBackupEngineInfo,
+        index: c_int,
+    ) -> u32;
+    pub fn crocksdb_backup_engine_info_destroy(info: *const DBBackupEngineInfo);
     pub fn crocksdb_backup_engine_close(be: *mut DBBackupEngine);
     pub fn crocksdb_backup_engine_restore_db_from_latest_backup(
         be: *mut DBBackupEngine,
@@ -2231,6 +2721,22 @@ extern "C" {
         kLen: size_t,
         err: *mut *mut c_char,
     ) -> *mut DBPinnableSlice;
+    pub fn crocksdb_get_pinned_cf_cache_only(
+        db: *mut DBInstance,
+        readopts: *const DBReadOptions,
+        cf_handle: *mut DBCFHandle,
+        k: *const u8,
+        kLen: size_t,
+        err: *mut *mut c_char,
+    ) -> *mut DBPinnableSlice;
+    pub fn crocksdb_key_may_exist_cf(
+        db: *mut DBInstance,
+        readopts: *const DBReadOptions,
+        cf_handle: *mut DBCFHandle,
+        k: *const u8,
+        kLen: size_t,
+        value: *mut *mut DBPinnableSlice,
+    ) -> bool;
     pub fn crocksdb_pinnableslice_value(
         s: *const DBPinnableSlice,
         valLen: *mut size_t,
@@ -2412,6 +2918,7 @@ extern "C" {
     pub fn crocksdb_flushjobinfo_smallest_seqno(info: *const DBFlushJobInfo) -> u64;
 
     pub fn crocksdb_reset_status(ptr: *mut DBStatusPtr);
+    pub fn crocksdb_status_ptr_get_severity(ptr: *mut DBStatusPtr) -> DBStatusSeverity;
 
     pub fn crocksdb_compactionjobinfo_status(
         info: *const DBCompactionJobInfo,
@@ -2516,6 +3023,7 @@ extern "C" {
         bg_error: extern "C" fn(*mut c_void, DBBackgroundErrorReason, *mut DBStatusPtr),
         stall_conditions: extern "C" fn(*mut c_void, *const DBWriteStallInfo),
         memtable_sealed: extern "C" fn(*mut c_void, *const DBMemTableInfo),
+        error_recovery_completed: extern "C" fn(*mut c_void, *mut DBStatusPtr),
     ) -> *mut DBEventListener;
     pub fn crocksdb_eventlistener_destroy(et: *mut DBEventListener);
     pub fn crocksdb_options_add_eventlistener(opt: *mut Options, et: *mut DBEventListener);
@@ -2603,6 +3111,9 @@ extern "C" {
         meta: *const DBSstFileMetaData,
         len: *mut size_t,
     ) -> *const c_char;
+    pub fn crocksdb_sst_file_meta_data_num_entries(meta: *const DBSstFileMetaData) -> u64;
+    pub fn crocksdb_sst_file_meta_data_num_deletions(meta: *const DBSstFileMetaData) -> u64;
+    pub fn crocksdb_sst_file_meta_data_being_compacted(meta: *const DBSstFileMetaData) -> bool;
 
     pub fn crocksdb_livefiles(db: *mut DBInstance) -> *mut DBLivefiles;
     pub fn crocksdb_livefiles_count(lf: *const DBLivefiles) -> size_t;
@@ -2996,6 +3507,15 @@ extern "C" {
         titan_readopts: *const DBTitanReadOptions,
         cf_handle: *mut DBCFHandle,
     ) -> *mut DBIterator;
+    pub fn ctitandb_create_iterators(
+        db: *mut DBInstance,
+        readopts: *const DBReadOptions,
+        titan_readopts: *const DBTitanReadOptions,
+        column_families: *const *mut DBCFHandle,
+        iterators: *mut *mut DBIterator,
+        size: size_t,
+        err: *mut *mut c_char,
+    );
     pub fn ctitandb_delete_files_in_range(
         db: *mut DBInstance,
         range_start_key: *const u8,
@@ -87,6 +87,34 @@ pub trait CompactionFilter {
             CompactionFilterDecision::Keep
         }
     }
+
+    /// The size of user timestamps of the column family this filter is used
+    /// for, 0 means keys don't carry timestamps.
+    ///
+    /// If it's not 0, the timestamp is split from the end of every key and
+    /// `unsafe_filter_with_ts` is called instead of `unsafe_filter`.
+    fn timestamp_size(&self) -> usize {
+        0
+    }
+
+    /// Like `unsafe_filter`, but `key` is the user key without timestamp and
+    /// `ts` is its timestamp. The key passed to `RemoveAndSkipUntil` must
+    /// include a timestamp.
+    /// RocksDB never rewrites keys during compaction, so `ChangeValue` keeps
+    /// the record's timestamp. To collapse a chain of versions, change the
+    /// value of the newest one, which is visited first, and remove the rest.
+    /// By default the key without timestamp is passed to `unsafe_filter`.
+    fn unsafe_filter_with_ts(
+        &mut self,
+        level: usize,
+        key: &[u8],
+        _ts: &[u8],
+        seqno: u64,
+        value: &[u8],
+        value_type: CompactionFilterValueType,
+    ) -> CompactionFilterDecision {
+        self.unsafe_filter(level, key, seqno, value, value_type)
+    }
 }
 
 #[repr(C)]
@@ -128,7 +156,14 @@ extern "C" fn filter<C: CompactionFilter>(
         let filter = &mut (*(filter as *mut CompactionFilterProxy<C>)).filter;
         let key = slice::from_raw_parts(key, key_len);
         let value = slice::from_raw_parts(value, value_len);
-        match filter.unsafe_filter(level as usize, key, seqno, value, value_type) {
+        let ts_size = filter.timestamp_size();
+        let decision = if ts_size > 0 && key.len() >= ts_size {
+            let (key, ts) = key.split_at(key.len() - ts_size);
+            filter.unsafe_filter_with_ts(level as usize, key, ts, seqno, value, value_type)
+        } else {
+            filter.unsafe_filter(level as usize, key, seqno, value, value_type)
+        };
+        match decision {
             CompactionFilterDecision::Keep => RawCompactionFilterDecision::Keep,
             CompactionFilterDecision::Remove => RawCompactionFilterDecision::Remove,
             CompactionFilterDecision::ChangeValue(new_v) => {
@@ -263,6 +298,11 @@ pub trait CompactionFilterFactory {
     /// Returns whether a thread creating table files for the specified `reason`
     /// should have invoke `create_compaction_filter` and pass KVs through the returned
     /// filter.
+    ///
+    /// Returning `true` only for `DBTableFileCreationReason::Flush` makes the
+    /// filter run on memtable flushes alone, e.g. for a GC that only needs to
+    /// look at L0. Compactions then copy KVs through unfiltered, and
+    /// `create_compaction_filter` is never called for them.
     fn should_filter_table_file_creation(&self, reason: DBTableFileCreationReason) -> bool {
         // For compatibility, `CompactionFilter`s by default apply during compaction.
         matches!(reason, DBTableFileCreationReason::Compaction)
@@ -13,7 +13,7 @@
 
 use crocksdb_ffi::{
     self, CompactionReason, DBBackgroundErrorReason, DBCompactionJobInfo, DBEventListener,
-    DBFlushJobInfo, DBIngestionInfo, DBInstance, DBMemTableInfo, DBStatusPtr,
+    DBFlushJobInfo, DBIngestionInfo, DBInstance, DBMemTableInfo, DBStatusPtr, DBStatusSeverity,
     DBSubcompactionJobInfo, DBWriteStallInfo, WriteStallCondition,
 };
 use libc::c_void;
@@ -77,6 +77,25 @@ impl MutableStatus {
         unsafe { crocksdb_ffi::crocksdb_reset_status(self.ptr) }
     }
 
+    /// Like `reset`, but refuses to clear errors that RocksDB can't recover
+    /// from, i.e. errors with a severity of `FatalError` or above.
+    pub fn try_reset(&self) -> Result<(), String> {
+        let severity = self.severity();
+        if severity >= DBStatusSeverity::FatalError {
+            return Err(format!(
+                "can't clear {:?} background error: {}",
+                severity,
+                self.result.as_ref().err().map_or("", |e| e.as_str())
+            ));
+        }
+        self.reset();
+        Ok(())
+    }
+
+    pub fn severity(&self) -> DBStatusSeverity {
+        unsafe { crocksdb_ffi::crocksdb_status_ptr_get_severity(self.ptr) }
+    }
+
     pub fn result(&self) -> Result<(), String> {
         self.result.clone()
     }
@@ -274,6 +293,9 @@ pub trait EventListener: Send + Sync {
     fn on_background_error(&self, _: DBBackgroundErrorReason, _: MutableStatus) {}
     fn on_stall_conditions_changed(&self, _: &WriteStallInfo) {}
     fn on_memtable_sealed(&self, _: &MemTableInfo) {}
+    /// Called after RocksDB has recovered from a background error, either
+    /// automatically or by `DB::resume`, with the error it recovered from.
+    fn on_error_recovery_completed(&self, _old_bg_error: Result<(), String>) {}
 }
 
 extern "C" fn destructor<E: EventListener>(ctx: *mut c_void) {
@@ -379,6 +401,22 @@ extern "C" fn on_memtable_sealed<E: EventListener>(ctx: *mut c_void, info: *cons
     ctx.on_memtable_sealed(info);
 }
 
+extern "C" fn on_error_recovery_completed<E: EventListener>(
+    ctx: *mut c_void,
+    status_ptr: *mut DBStatusPtr,
+) {
+    let (ctx, result) = unsafe {
+        (
+            &*(ctx as *mut E),
+            || -> Result<(), String> {
+                ffi_try!(crocksdb_status_ptr_get_error(status_ptr));
+                Ok(())
+            }(),
+        )
+    };
+    ctx.on_error_recovery_completed(result);
+}
+
 pub fn new_event_listener<E: EventListener>(e: E) -> *mut DBEventListener {
     let p: Box<dyn EventListener> = Box::new(e);
     unsafe {
@@ -395,6 +433,7 @@ pub fn new_event_listener<E: EventListener>(e: E) -> *mut DBEventListener {
             on_background_error::<E>,
             on_stall_conditions_changed::<E>,
             on_memtable_sealed::<E>,
+            on_error_recovery_completed::<E>,
         )
     }
 }
@@ -3,6 +3,7 @@
 pub use crocksdb_ffi::{self, DBFileSystemInspectorInstance};
 
 use libc::{c_char, c_void, size_t, strdup};
+use std::ffi::CString;
 
 // Inspect global IO flow. No per-file inspection for now.
 pub trait FileSystemInspector: Sync + Send {
@@ -26,8 +27,9 @@ extern "C" fn file_system_inspector_read<T: FileSystemInspector>(
     match file_system_inspector.read(len) {
         Ok(ret) => ret,
         Err(e) => {
+            let e = CString::new(e).unwrap_or_default();
             unsafe {
-                *errptr = strdup(e.as_ptr() as *const c_char);
+                *errptr = strdup(e.as_ptr());
             }
             0
         }
@@ -43,8 +45,9 @@ extern "C" fn file_system_inspector_write<T: FileSystemInspector>(
     match file_system_inspector.write(len) {
         Ok(ret) => ret,
         Err(e) => {
+            let e = CString::new(e).unwrap_or_default();
             unsafe {
-                *errptr = strdup(e.as_ptr() as *const c_char);
+                *errptr = strdup(e.as_ptr());
             }
             0
         }
@@ -46,32 +46,37 @@ pub use file_system::FileSystemInspector;
 pub use librocksdb_sys::{
     self as crocksdb_ffi, new_bloom_filter, ChecksumType, CompactionPriority, CompactionReason,
     DBBackgroundErrorReason, DBBottommostLevelCompaction, DBCompactionStyle, DBCompressionType,
-    DBEntryType, DBInfoLogLevel, DBRateLimiterMode, DBRecoveryMode,
+    DBEntryType, DBEnvPriority, DBInfoLogLevel, DBRateLimiterMode, DBRecoveryMode,
     DBSstPartitionerResult as SstPartitionerResult, DBStatisticsHistogramType,
-    DBStatisticsTickerType, DBStatusPtr, DBTableFileCreationReason, DBTitanDBBlobRunMode,
-    DBValueType, IndexType, PrepopulateBlockCache, WriteStallCondition,
+    DBStatisticsTickerType, DBStatusPtr, DBStatusSeverity, DBTableFileCreationReason,
+    DBTitanDBBlobRunMode, DBValueType, IndexType, PrepopulateBlockCache, ReadTier,
+    WriteStallCondition,
 };
 pub use logger::Logger;
 pub use merge_operator::MergeOperands;
-pub use metadata::{ColumnFamilyMetaData, LevelMetaData, SstFileMetaData};
+pub use metadata::{ColumnFamilyMetaData, FlushedSstFiles, LevelMetaData, SstFileMetaData};
 pub use perf_context::{
     get_perf_level, set_perf_flags, set_perf_level, IOStatsContext, PerfContext, PerfFlag,
     PerfFlags, PerfLevel,
 };
 pub use rocksdb::{
     load_latest_options, run_ldb_tool, run_sst_dump_tool, set_external_sst_file_global_seq_no,
-    BackupEngine, CFHandle, Cache, DBIterator, DBVector, Env, ExternalSstFileInfo, MapProperty,
-    MemoryAllocator, Range, SeekKey, SequentialFile, SstFileReader, SstFileWriter, Writable, DB,
+    BackgroundActivity, BackupEngine, BackupInfo, CFHandle, Cache, DBIntoIter, DBIterator,
+    DBVector, Env, ExternalSstFileInfo, MapProperty, MemoryAllocator, Range, SeekKey,
+    SequentialFile, SstFileReader, SstFileWriter, Writable, WriteBatchWithIndex, WriteStallStats,
+    DB,
 };
 pub use rocksdb_options::{
-    BlockBasedOptions, CColumnFamilyDescriptor, ColumnFamilyOptions, CompactOptions,
-    CompactionOptions, ConcurrentTaskLimiter, DBOptions, EnvOptions, FifoCompactionOptions,
-    FlushOptions, HistogramData, IngestExternalFileOptions, LRUCacheOptions, MergeInstanceOptions,
-    RateLimiter, ReadOptions, RestoreOptions, Statistics, WriteBufferManager, WriteOptions,
+    BlockBasedOptions, CColumnFamilyDescriptor, ClockCacheOptions, ColumnFamilyOptions,
+    CompactOptions, CompactionOptions, ConcurrentTaskLimiter, DBOptions, EnvOptions,
+    FifoCompactionOptions, FlushOptions, HistogramData, IngestExternalFileOptions, LRUCacheOptions,
+    MergeInstanceOptions, RateLimiter, ReadOptions, RestoreOptions, SizeApproximationOptions,
+    Statistics, WriteBufferManager, WriteOptions,
 };
 pub use slice_transform::SliceTransform;
 pub use sst_partitioner::{
-    SstPartitioner, SstPartitionerContext, SstPartitionerFactory, SstPartitionerRequest,
+    FixedPrefixSstPartitioner, FixedPrefixSstPartitionerFactory, SstPartitioner,
+    SstPartitionerContext, SstPartitionerFactory, SstPartitionerRequest,
 };
 pub use table_filter::TableFilter;
 pub use table_properties::{
@@ -80,8 +85,10 @@ pub use table_properties::{
 };
 pub use table_properties_collector::TablePropertiesCollector;
 pub use table_properties_collector_factory::TablePropertiesCollectorFactory;
-pub use titan::{TitanBlobIndex, TitanDBOptions};
-pub use write_batch::{WriteBatch, WriteBatchIter, WriteBatchRef};
+pub use titan::{TitanBlobIndex, TitanBlobStats, TitanDBOptions};
+pub use transaction_log::TransactionLogIterator;
+pub use wal_filter::{WalFilter, WalFilterResult};
+pub use write_batch::{BatchBuilder, WriteBatch, WriteBatchHandler, WriteBatchIter, WriteBatchRef};
 
 #[allow(deprecated)]
 pub use rocksdb::Kv;
@@ -108,6 +115,8 @@ mod table_properties_collector_factory;
 pub mod table_properties_rc;
 mod table_properties_rc_handles;
 mod titan;
+mod transaction_log;
+mod wal_filter;
 mod write_batch;
 
 #[cfg(test)]
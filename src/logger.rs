@@ -4,12 +4,24 @@ use crocksdb_ffi;
 use libc::{c_char, c_void};
 use librocksdb_sys::{DBEnv, DBInfoLogLevel as InfoLogLevel, DBLogger};
 use std::ffi::{CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
 use std::str;
 
+/// Receives the info log lines of a DB, see `DBOptions::set_info_log`. Lines
+/// below the level set by `DBOptions::set_info_log_level` are not passed on.
 pub trait Logger: Send + Sync {
     fn logv(&self, log_level: InfoLogLevel, log: &str);
 }
 
+impl<F> Logger for F
+where
+    F: Fn(InfoLogLevel, &str) + Send + Sync,
+{
+    fn logv(&self, log_level: InfoLogLevel, log: &str) {
+        self(log_level, log)
+    }
+}
+
 extern "C" fn destructor<L: Logger>(ctx: *mut c_void) {
     unsafe {
         let _ = Box::from_raw(ctx as *mut L);
@@ -17,11 +29,13 @@ extern "C" fn destructor<L: Logger>(ctx: *mut c_void) {
 }
 
 extern "C" fn logv<L: Logger>(ctx: *mut c_void, log_level: InfoLogLevel, log: *const c_char) {
-    unsafe {
-        let logger = &*(ctx as *mut L);
-        let log = CStr::from_ptr(log);
-        logger.logv(log_level, &log.to_string_lossy());
-    }
+    let logger = unsafe { &*(ctx as *mut L) };
+    let log = unsafe { CStr::from_ptr(log) };
+    // Unwinding into C++ is undefined behavior, a line whose logger panics is
+    // dropped instead.
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| {
+        logger.logv(log_level, &log.to_string_lossy())
+    }));
 }
 
 pub fn new_logger<L: Logger>(l: L) -> *mut DBLogger {
@@ -121,6 +121,58 @@ impl<'a> SstFileMetaData<'a> {
             slice::from_raw_parts(ptr as *const u8, len)
         }
     }
+
+    /// Returns an owned copy of the smallest user key in the file.
+    pub fn smallest_key(&self) -> Vec<u8> {
+        self.get_smallestkey().to_vec()
+    }
+
+    /// Returns an owned copy of the largest user key in the file.
+    pub fn largest_key(&self) -> Vec<u8> {
+        self.get_largestkey().to_vec()
+    }
+
+    pub fn get_num_entries(&self) -> u64 {
+        unsafe { crocksdb_ffi::crocksdb_sst_file_meta_data_num_entries(self.inner) }
+    }
+
+    pub fn get_num_deletions(&self) -> u64 {
+        unsafe { crocksdb_ffi::crocksdb_sst_file_meta_data_num_deletions(self.inner) }
+    }
+
+    /// Whether the file is currently being compacted.
+    pub fn is_being_compacted(&self) -> bool {
+        unsafe { crocksdb_ffi::crocksdb_sst_file_meta_data_being_compacted(self.inner) }
+    }
+}
+
+/// The L0 files created by `DB::flush_and_get_sst_cf`.
+pub struct FlushedSstFiles {
+    meta: ColumnFamilyMetaData,
+    names: Vec<String>,
+}
+
+impl FlushedSstFiles {
+    pub(crate) fn new(meta: ColumnFamilyMetaData, names: Vec<String>) -> FlushedSstFiles {
+        FlushedSstFiles { meta, names }
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn get_files(&self) -> Vec<SstFileMetaData> {
+        self.meta
+            .get_level(0)
+            .get_files()
+            .into_iter()
+            .filter(|f| self.names.contains(&f.get_name()))
+            .collect()
+    }
 }
 
 pub struct LiveFiles {
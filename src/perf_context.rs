@@ -724,6 +724,36 @@ mod test {
         assert!(ctx.logger_nanos() > 0);
     }
 
+    #[test]
+    fn test_perf_context_point_lookup() {
+        let temp_dir = tempdir_with_prefix("test_perf_context_point_lookup");
+        let mut opts = DBOptions::new();
+        opts.create_if_missing(true);
+        let db = DB::open(opts, temp_dir.path().to_str().unwrap()).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+
+        set_perf_level(PerfLevel::EnableTimeExceptForMutex);
+        let mut ctx = PerfContext::get();
+        ctx.reset();
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+        assert!(ctx.get_from_memtable_count() > 0);
+        assert_eq!(ctx.block_read_count(), 0);
+
+        let mut iter = db.iter();
+        assert!(iter.seek(SeekKey::Key(b"k1")).unwrap());
+        assert!(ctx.seek_on_memtable_count() > 0);
+        drop(iter);
+
+        let mut fopts = FlushOptions::default();
+        fopts.set_wait(true);
+        db.flush(&fopts).unwrap();
+        ctx.reset();
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+        assert!(ctx.block_read_count() > 0);
+        assert!(ctx.block_read_byte() > 0);
+        set_perf_level(PerfLevel::Disable);
+    }
+
     #[test]
     fn test_perf_flags() {
         let temp_dir = tempdir_with_prefix("test_perf_flags");
@@ -13,39 +13,44 @@
 // limitations under the License.
 
 use crocksdb_ffi::{
-    self, DBBackupEngine, DBCFHandle, DBCache, DBCompressionType, DBEnv, DBInstance, DBMapProperty,
-    DBPinnableSlice, DBPostWriteCallback, DBSequentialFile, DBTablePropertiesCollection,
-    DBTitanDBOptions, DBWriteBatch,
+    self, DBBackupEngine, DBCFHandle, DBCache, DBCompressionType, DBEnv, DBEnvPriority, DBInstance,
+    DBMapProperty, DBPinnableSlice, DBPostWriteCallback, DBSequentialFile,
+    DBTablePropertiesCollection, DBTitanDBOptions, DBWriteBatch, DBWriteBatchWithIndex,
 };
 use libc::{self, c_char, c_int, c_void, size_t};
 use librocksdb_sys::DBMemoryAllocator;
-use metadata::ColumnFamilyMetaData;
+use metadata::{ColumnFamilyMetaData, FlushedSstFiles};
+use perf_context::{get_perf_level, set_perf_level, PerfContext, PerfLevel};
 use rocksdb_options::{
-    CColumnFamilyDescriptor, ColumnFamilyDescriptor, ColumnFamilyOptions, CompactOptions,
-    CompactionOptions, DBOptions, EnvOptions, FlushOptions, IngestExternalFileOptions,
-    LRUCacheOptions, MergeInstanceOptions, ReadOptions, RestoreOptions, UnsafeSnap, WriteOptions,
+    CColumnFamilyDescriptor, ClockCacheOptions, ColumnFamilyDescriptor, ColumnFamilyOptions,
+    CompactOptions, CompactionOptions, DBOptions, EnvOptions, FlushOptions,
+    IngestExternalFileOptions, LRUCacheOptions, MergeInstanceOptions, ReadOptions, RestoreOptions,
+    SizeApproximationOptions, UnsafeSnap, WriteOptions,
 };
 use std::collections::BTreeMap;
 use std::ffi::{CStr, CString};
 use std::fmt::{self, Debug, Formatter};
 use std::io;
+use std::marker::PhantomData;
 use std::mem;
 use std::mem::MaybeUninit;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::str::from_utf8;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use std::{fs, ptr, slice};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::{fs, process, ptr, slice, thread};
 
 #[cfg(feature = "encryption")]
 use encryption::{DBEncryptionKeyManager, EncryptionKeyManager};
 use file_system::{DBFileSystemInspector, FileSystemInspector};
 use table_properties::{TableProperties, TablePropertiesCollection};
 use table_properties_rc::TablePropertiesCollection as RcTablePropertiesCollection;
-use titan::TitanDBOptions;
-use write_batch::WriteBatch;
+use titan::{TitanBlobStats, TitanDBOptions};
+use transaction_log::TransactionLogIterator;
+use write_batch::{BatchBuilder, WriteBatch};
 
 use crate::metadata::LiveFiles;
 
@@ -57,6 +62,12 @@ impl CFHandle {
     pub fn id(&self) -> u32 {
         unsafe { crocksdb_ffi::crocksdb_column_family_handle_id(self.inner) }
     }
+
+    /// Size of the user timestamp of the column family's comparator, 0 if
+    /// user timestamp is not enabled.
+    pub fn timestamp_size(&self) -> usize {
+        unsafe { crocksdb_ffi::crocksdb_column_family_handle_timestamp_size(self.inner) }
+    }
 }
 
 impl Drop for CFHandle {
@@ -67,6 +78,13 @@ impl Drop for CFHandle {
     }
 }
 
+/// How `DB::open_cf_internal` opens the db.
+enum OpenMode<'a> {
+    ReadWrite,
+    ReadOnly { error_if_log_file_exist: bool },
+    Secondary { secondary_path: &'a str },
+}
+
 fn ensure_default_cf_exists<'a>(
     list: &mut Vec<ColumnFamilyDescriptor<'a>>,
     ttls: &mut Vec<i32>,
@@ -101,6 +119,18 @@ fn split_descriptors(
     (v1, v2)
 }
 
+// Integrated BlobDB doesn't support merge operands yet, reject it up front
+// instead of failing on the first merge.
+fn check_blob_options(name: &str, opts: &ColumnFamilyOptions) -> Result<(), String> {
+    if opts.get_enable_blob_files() && opts.has_merge_operator() {
+        return Err(format!(
+            "column family {}: enable_blob_files can't be used with a merge operator",
+            name
+        ));
+    }
+    Ok(())
+}
+
 fn build_cstring_list(str_list: &[&str]) -> Vec<CString> {
     str_list
         .iter()
@@ -143,6 +173,51 @@ impl MapProperty {
     }
 }
 
+/// A snapshot of the background work of a column family.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct BackgroundActivity {
+    pub mem_table_flush_pending: bool,
+    pub compaction_pending: bool,
+    /// The number of running flushes of the whole DB.
+    pub num_running_flushes: u64,
+    /// The number of running compactions of the whole DB.
+    pub num_running_compactions: u64,
+}
+
+/// Write stall counters of a column family, collected from the
+/// `io_stalls.*` entries of the "rocksdb.cfstats" map property.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct WriteStallStats {
+    pub level0_slowdown: u64,
+    pub level0_slowdown_with_compaction: u64,
+    pub level0_numfiles: u64,
+    pub level0_numfiles_with_compaction: u64,
+    pub memtable_slowdown: u64,
+    pub memtable_compaction: u64,
+    pub slowdown_for_pending_compaction_bytes: u64,
+    pub stop_for_pending_compaction_bytes: u64,
+    pub total_slowdown: u64,
+    pub total_stop: u64,
+}
+
+impl WriteStallStats {
+    fn from_map_property(mp: &MapProperty) -> WriteStallStats {
+        let get = |name: &str| mp.get_property_int_value(&format!("io_stalls.{}", name));
+        WriteStallStats {
+            level0_slowdown: get("level0_slowdown"),
+            level0_slowdown_with_compaction: get("level0_slowdown_with_compaction"),
+            level0_numfiles: get("level0_numfiles"),
+            level0_numfiles_with_compaction: get("level0_numfiles_with_compaction"),
+            memtable_slowdown: get("memtable_slowdown"),
+            memtable_compaction: get("memtable_compaction"),
+            slowdown_for_pending_compaction_bytes: get("slowdown_for_pending_compaction_bytes"),
+            stop_for_pending_compaction_bytes: get("stop_for_pending_compaction_bytes"),
+            total_slowdown: get("total_slowdown"),
+            total_stop: get("total_stop"),
+        }
+    }
+}
+
 pub struct DB {
     inner: *mut DBInstance,
     cfs_by_name: BTreeMap<String, usize>,
@@ -173,9 +248,29 @@ pub struct Snapshot<D: Deref<Target = DB>> {
     snap: UnsafeSnap,
 }
 
+/// `ReadOptions` pinned to a `Snapshot`, created by `Snapshot::read_options`.
+///
+/// It derefs to `ReadOptions`, so it can be passed to any read taking
+/// `&ReadOptions`, and can't outlive the snapshot it reads from.
+pub struct SnapshotReadOptions<'a> {
+    opts: ReadOptions,
+    _snap: PhantomData<&'a UnsafeSnap>,
+}
+
+impl<'a> Deref for SnapshotReadOptions<'a> {
+    type Target = ReadOptions;
+
+    fn deref(&self) -> &ReadOptions {
+        &self.opts
+    }
+}
+
 pub struct DBIterator<D> {
     _db: D,
     _readopts: ReadOptions,
+    // Set if the iterator is created along with others from the same read
+    // options, see `DB::iter_cfs_opt`.
+    _shared_readopts: Option<Arc<ReadOptions>>,
     inner: *mut crocksdb_ffi::DBIterator,
 }
 
@@ -207,6 +302,7 @@ impl<D: Deref<Target = DB>> DBIterator<D> {
             DBIterator {
                 _db: db,
                 _readopts: readopts,
+                _shared_readopts: None,
                 inner: iterator,
             }
         }
@@ -231,6 +327,7 @@ impl<D: Deref<Target = DB>> DBIterator<D> {
             DBIterator {
                 _db: db,
                 _readopts: readopts,
+                _shared_readopts: None,
                 inner: iterator,
             }
         }
@@ -266,6 +363,28 @@ impl<D> DBIterator<D> {
         self.valid()
     }
 
+    /// Like `seek`, but reads the versions visible at timestamp `ts` from now
+    /// on. The iterator must have been created with
+    /// `ReadOptions::set_timestamp` and `ts` must have the same size.
+    pub fn seek_with_ts(&mut self, key: SeekKey, ts: &[u8]) -> Result<bool, String> {
+        self.update_timestamp(ts)?;
+        self.seek(key)
+    }
+
+    /// Like `seek_for_prev`, but reads the versions visible at timestamp `ts`
+    /// from now on. See `seek_with_ts`.
+    pub fn seek_for_prev_with_ts(&mut self, key: SeekKey, ts: &[u8]) -> Result<bool, String> {
+        self.update_timestamp(ts)?;
+        self.seek_for_prev(key)
+    }
+
+    fn update_timestamp(&mut self, ts: &[u8]) -> Result<(), String> {
+        if self._shared_readopts.is_some() {
+            return Err("can't change the timestamp of iterators sharing read options".to_owned());
+        }
+        self._readopts.update_timestamp(ts)
+    }
+
     pub fn prev(&mut self) -> Result<bool, String> {
         unsafe {
             crocksdb_ffi::crocksdb_iter_prev(self.inner);
@@ -331,6 +450,15 @@ impl<D> DBIterator<D> {
         Ok(valid)
     }
 
+    /// Returns whether the iterator is valid and the current key starts with
+    /// `prefix`, which makes it handy as the loop condition of a prefix scan.
+    ///
+    /// Keys are matched bytewise, so the column family is expected to order
+    /// keys sharing a prefix next to each other, as the default comparator does.
+    pub fn valid_for_prefix(&self, prefix: &[u8]) -> Result<bool, String> {
+        Ok(self.valid()? && self.key().starts_with(prefix))
+    }
+
     fn status(&self) -> Result<(), String> {
         unsafe {
             ffi_try!(crocksdb_iter_get_error(self.inner));
@@ -367,6 +495,73 @@ impl<D> Drop for DBIterator<D> {
     }
 }
 
+/// An owning iterator over the entries of a `DBIterator`, created by
+/// `DBIterator::into_iter` or `DBIterator::rev_iter`.
+///
+/// It starts from the current position of the underlying iterator, so the
+/// iterator must be positioned before being converted. Iteration stops at the
+/// first invalid position, errors are yielded once and then the iteration ends.
+pub struct DBIntoIter<D> {
+    iter: DBIterator<D>,
+    backward: bool,
+    done: bool,
+}
+
+impl<D> Iterator for DBIntoIter<D> {
+    type Item = Result<(Box<[u8]>, Box<[u8]>), String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.iter.valid() {
+            Ok(true) => {}
+            Ok(false) => {
+                self.done = true;
+                return None;
+            }
+            Err(e) => {
+                self.done = true;
+                return Some(Err(e));
+            }
+        }
+        let kv = (self.iter.key().into(), self.iter.value().into());
+        unsafe {
+            if self.backward {
+                crocksdb_ffi::crocksdb_iter_prev(self.iter.inner);
+            } else {
+                crocksdb_ffi::crocksdb_iter_next(self.iter.inner);
+            }
+        }
+        Some(Ok(kv))
+    }
+}
+
+impl<D> IntoIterator for DBIterator<D> {
+    type Item = Result<(Box<[u8]>, Box<[u8]>), String>;
+    type IntoIter = DBIntoIter<D>;
+
+    fn into_iter(self) -> DBIntoIter<D> {
+        DBIntoIter {
+            iter: self,
+            backward: false,
+            done: false,
+        }
+    }
+}
+
+impl<D> DBIterator<D> {
+    /// Converts the iterator into one that walks backward from the current
+    /// position, by calling `prev` until it becomes invalid.
+    pub fn rev_iter(self) -> DBIntoIter<D> {
+        DBIntoIter {
+            iter: self,
+            backward: true,
+            done: false,
+        }
+    }
+}
+
 unsafe impl<D: Send> Send for DBIterator<D> {}
 
 unsafe impl<D: Deref<Target = DB> + Send + Sync> Send for Snapshot<D> {}
@@ -431,6 +626,54 @@ impl<D: Deref<Target = DB>> Snapshot<D> {
         self.db.get_cf_opt(cf, key, &readopts)
     }
 
+    /// Get the value of `key` with the given read options, pinned to this snapshot.
+    pub fn get_opt(
+        &self,
+        key: &[u8],
+        mut readopts: ReadOptions,
+    ) -> Result<Option<DBVector>, String> {
+        unsafe {
+            readopts.set_snapshot(&self.snap);
+        }
+        self.db.get_opt(key, &readopts)
+    }
+
+    /// Get the value of `key` in `cf` with the given read options, pinned to this snapshot.
+    pub fn get_cf_opt(
+        &self,
+        cf: &CFHandle,
+        key: &[u8],
+        mut readopts: ReadOptions,
+    ) -> Result<Option<DBVector>, String> {
+        unsafe {
+            readopts.set_snapshot(&self.snap);
+        }
+        self.db.get_cf_opt(cf, key, &readopts)
+    }
+
+    /// Pins `readopts` to this snapshot. The returned options borrow the
+    /// snapshot, so they can't be used after it's released:
+    ///
+    /// ```compile_fail
+    /// use rocksdb::{ReadOptions, DB};
+    ///
+    /// fn read(db: &DB) {
+    ///     let snap = db.snapshot();
+    ///     let readopts = snap.read_options(ReadOptions::new());
+    ///     drop(snap);
+    ///     db.get_opt(b"k1", &readopts).unwrap();
+    /// }
+    /// ```
+    pub fn read_options(&self, mut readopts: ReadOptions) -> SnapshotReadOptions<'_> {
+        unsafe {
+            readopts.set_snapshot(&self.snap);
+        }
+        SnapshotReadOptions {
+            opts: readopts,
+            _snap: PhantomData,
+        }
+    }
+
     /// Get the snapshot's sequence number.
     pub fn get_sequence_number(&self) -> u64 {
         unsafe { self.snap.get_sequence_number() }
@@ -543,7 +786,29 @@ impl DB {
     where
         T: Into<ColumnFamilyDescriptor<'a>>,
     {
-        DB::open_cf_internal(opts, path, cfds, &[], None)
+        DB::open_cf_internal(opts, path, cfds, &[], OpenMode::ReadWrite)
+    }
+
+    /// Open the db with all of its existing column families, each of them is
+    /// configured with the options `cf_opts` returns for its name. If the db
+    /// doesn't exist yet, only the default column family is opened.
+    pub fn open_cf_auto<F>(opts: DBOptions, path: &str, cf_opts: F) -> Result<DB, String>
+    where
+        F: Fn(&str) -> ColumnFamilyOptions,
+    {
+        let env = opts.env().unwrap_or_default();
+        let current = format!("{}/CURRENT", path.trim_end_matches('/'));
+        let cf_names = match env.file_exists(&current) {
+            Ok(()) => DB::list_column_families(&opts, path)?,
+            // Leave it to `open_cf` to decide whether the db should be created.
+            Err(e) if e.starts_with("NotFound") => vec!["default".to_owned()],
+            Err(e) => return Err(e),
+        };
+        let cfds: Vec<_> = cf_names
+            .iter()
+            .map(|name| (name.as_str(), cf_opts(name)))
+            .collect();
+        DB::open_cf(opts, path, cfds)
     }
 
     pub fn open_cf_with_ttl<'a, T>(
@@ -558,7 +823,7 @@ impl DB {
         if ttls.is_empty() {
             return Err("ttls is empty in with_ttl function".to_owned());
         }
-        DB::open_cf_internal(opts, path, cfds, ttls, None)
+        DB::open_cf_internal(opts, path, cfds, ttls, OpenMode::ReadWrite)
     }
 
     pub fn open_for_read_only(
@@ -579,7 +844,47 @@ impl DB {
     where
         T: Into<ColumnFamilyDescriptor<'a>>,
     {
-        DB::open_cf_internal(opts, path, cfds, &[], Some(error_if_log_file_exist))
+        DB::open_cf_internal(
+            opts,
+            path,
+            cfds,
+            &[],
+            OpenMode::ReadOnly {
+                error_if_log_file_exist,
+            },
+        )
+    }
+
+    /// Opens a secondary instance that follows the primary db at `primary_path`.
+    ///
+    /// The secondary keeps its own info log and metadata under `secondary_path`
+    /// and only sees writes made to the primary after `try_catch_up_with_primary`
+    /// is called. Secondary mode requires `max_open_files` to be -1.
+    pub fn open_as_secondary(
+        opts: DBOptions,
+        primary_path: &str,
+        secondary_path: &str,
+    ) -> Result<DB, String> {
+        let cfds: Vec<&str> = vec![];
+        DB::open_cf_as_secondary(opts, primary_path, secondary_path, cfds)
+    }
+
+    pub fn open_cf_as_secondary<'a, T>(
+        opts: DBOptions,
+        primary_path: &str,
+        secondary_path: &str,
+        cfds: Vec<T>,
+    ) -> Result<DB, String>
+    where
+        T: Into<ColumnFamilyDescriptor<'a>>,
+    {
+        DB::open_cf_internal(
+            opts,
+            primary_path,
+            cfds,
+            &[],
+            OpenMode::Secondary { secondary_path },
+        )
     }
 
     fn open_cf_internal<'a, T>(
@@ -587,9 +892,7 @@ impl DB {
         path: &str,
         cfds: Vec<T>,
         ttls: &[i32],
-        // if none, open for read write mode.
-        // otherwise, open for read only.
-        error_if_log_file_exist: Option<bool>,
+        mode: OpenMode,
     ) -> Result<DB, String>
     where
         T: Into<ColumnFamilyDescriptor<'a>>,
@@ -599,20 +902,27 @@ impl DB {
         const ERR_NULL_CF_HANDLE: &str = "Received null column family handle from DB";
 
         let cpath = CString::new(path.as_bytes()).map_err(|_| ERR_CONVERT_PATH.to_owned())?;
-        fs::create_dir_all(Path::new(path)).map_err(|e| {
-            format!(
-                "Failed to create rocksdb directory: \
-                 src/rocksdb.rs:                              \
-                 {:?}",
-                e
-            )
-        })?;
+        // A DB opened on a mem env, see `Env::new_mem`, has its directory
+        // created in memory by RocksDB.
+        if !opts.env().map_or(false, |env| env.mem) {
+            fs::create_dir_all(Path::new(path)).map_err(|e| {
+                format!(
+                    "Failed to create rocksdb directory: \
+                     src/rocksdb.rs:                              \
+                     {:?}",
+                    e
+                )
+            })?;
+        }
 
         let mut descs = cfds.into_iter().map(|t| t.into()).collect();
         let mut ttls_vec = ttls.to_vec();
         ensure_default_cf_exists(&mut descs, &mut ttls_vec, !opts.titan_inner.is_null());
 
         let (names, options) = split_descriptors(descs, !opts.titan_inner.is_null());
+        for (name, cf_opts) in names.iter().zip(&options) {
+            check_blob_options(name, cf_opts)?;
+        }
         let cstrings = build_cstring_list(&names);
 
         let cf_names: Vec<*const _> = cstrings.iter().map(|cs| cs.as_ptr()).collect();
@@ -633,7 +943,19 @@ impl DB {
             })
             .collect();
 
-        let readonly = error_if_log_file_exist.is_some();
+        let csecondary_path = match mode {
+            OpenMode::Secondary { secondary_path } => {
+                fs::create_dir_all(Path::new(secondary_path))
+                    .map_err(|e| format!("Failed to create secondary directory: {:?}", e))?;
+                Some(
+                    CString::new(secondary_path.as_bytes())
+                        .map_err(|_| ERR_CONVERT_PATH.to_owned())?,
+                )
+            }
+            _ => None,
+        };
+
+        let readonly = !matches!(mode, OpenMode::ReadWrite);
 
         let with_ttl = if !ttls_vec.is_empty() {
             if ttls_vec.len() == cf_names.len() {
@@ -659,7 +981,7 @@ impl DB {
                 unsafe {
                     crocksdb_ffi::ctitandb_options_set_rocksdb_options(titan_options, db_options);
                 }
-                if error_if_log_file_exist.is_some() {
+                if readonly {
                     return Err("TitanDB doesn't support read only mode.".to_owned());
                 } else if with_ttl {
                     return Err("TitanDB doesn't support ttl.".to_owned());
@@ -667,7 +989,22 @@ impl DB {
             }
 
             if !with_ttl {
-                if let Some(flag) = error_if_log_file_exist {
+                if let Some(secondary_path) = &csecondary_path {
+                    unsafe {
+                        ffi_try!(crocksdb_open_as_secondary_column_families(
+                            db_options,
+                            db_path,
+                            secondary_path.as_ptr(),
+                            db_cfs_count,
+                            db_cf_ptrs,
+                            db_cf_opts,
+                            db_cf_handles
+                        ))
+                    }
+                } else if let OpenMode::ReadOnly {
+                    error_if_log_file_exist: flag,
+                } = mode
+                {
                     unsafe {
                         ffi_try!(crocksdb_open_for_read_only_column_families(
                             db_options,
@@ -853,6 +1190,12 @@ impl DB {
         &self.path
     }
 
+    /// Starts a batch of operations that are written atomically by
+    /// `BatchBuilder::commit`.
+    pub fn batch(&self) -> BatchBuilder {
+        BatchBuilder::new(self)
+    }
+
     pub fn write_opt(&self, batch: &WriteBatch, writeopts: &WriteOptions) -> Result<(), String> {
         unsafe {
             ffi_try!(crocksdb_write(self.inner, writeopts.inner, batch.inner));
@@ -919,6 +1262,22 @@ impl DB {
         Ok(())
     }
 
+    /// Writes the updates staged in `batch` atomically.
+    pub fn write_batch_with_index(
+        &self,
+        batch: &WriteBatchWithIndex,
+        writeopts: &WriteOptions,
+    ) -> Result<(), String> {
+        unsafe {
+            ffi_try!(crocksdb_write_writebatch_wi(
+                self.inner,
+                writeopts.inner,
+                batch.inner
+            ));
+        }
+        Ok(())
+    }
+
     pub fn write(&self, batch: &WriteBatch) -> Result<(), String> {
         self.write_opt(batch, &WriteOptions::new())
     }
@@ -975,11 +1334,223 @@ impl DB {
         self.get_cf_opt(cf, key, &ReadOptions::new())
     }
 
+    /// Get the value of `key` in `cf` from memtables and the block cache only,
+    /// never reading from disk.
+    ///
+    /// Returns `Ok(None)` if the key is not found in memory, even though it may
+    /// exist in an SST file.
+    pub fn get_cf_cache_only(&self, cf: &CFHandle, key: &[u8]) -> Result<Option<DBVector>, String> {
+        let readopts = ReadOptions::new();
+        unsafe {
+            let val = ffi_try!(crocksdb_get_pinned_cf_cache_only(
+                self.inner,
+                readopts.get_inner(),
+                cf.inner,
+                key.as_ptr(),
+                key.len() as size_t
+            ));
+            if val.is_null() {
+                Ok(None)
+            } else {
+                Ok(Some(DBVector::from_pinned_slice(val)))
+            }
+        }
+    }
+
+    /// Checks whether `key` may exist in `cf` by consulting only memtables,
+    /// bloom filters and cached blocks, without doing any IO.
+    ///
+    /// `false` means the key definitely doesn't exist, while `true` may be a
+    /// false positive. If the value was found along the way, it's returned as
+    /// well so the caller can skip a second lookup.
+    pub fn key_may_exist_cf(
+        &self,
+        cf: &CFHandle,
+        key: &[u8],
+        readopts: &ReadOptions,
+    ) -> (bool, Option<DBVector>) {
+        unsafe {
+            let mut val = ptr::null_mut();
+            let may_exist = crocksdb_ffi::crocksdb_key_may_exist_cf(
+                self.inner,
+                readopts.get_inner(),
+                cf.inner,
+                key.as_ptr(),
+                key.len() as size_t,
+                &mut val,
+            );
+            if val.is_null() {
+                (may_exist, None)
+            } else {
+                (may_exist, Some(DBVector::from_pinned_slice(val)))
+            }
+        }
+    }
+
+    /// Looks up `keys` in `cf` at the timestamp set in `readopts`, writing the
+    /// value and user timestamp of each key into `out`.
+    ///
+    /// `out` is resized to `keys.len()` and `out[i]` is set to `None` if
+    /// `keys[i]` is not found. Buffers of slots that are already `Some` are
+    /// reused, so repeatedly polling with the same `out` avoids reallocating.
+    pub fn multi_get_cf_opt_ts_into(
+        &self,
+        cf: &CFHandle,
+        keys: &[&[u8]],
+        readopts: &ReadOptions,
+        out: &mut Vec<Option<(Vec<u8>, Vec<u8>)>>,
+    ) -> Result<(), String> {
+        extern "C" fn on_result(
+            ctx: *mut c_void,
+            idx: size_t,
+            v: *const u8,
+            vlen: size_t,
+            ts: *const u8,
+            tslen: size_t,
+        ) {
+            let out = unsafe { &mut *(ctx as *mut Vec<Option<(Vec<u8>, Vec<u8>)>>) };
+            let slot = &mut out[idx];
+            if v.is_null() {
+                *slot = None;
+                return;
+            }
+            let (v, ts) = unsafe {
+                (
+                    slice::from_raw_parts(v, vlen),
+                    slice::from_raw_parts(ts, tslen),
+                )
+            };
+            match slot {
+                Some((value, timestamp)) => {
+                    value.clear();
+                    value.extend_from_slice(v);
+                    timestamp.clear();
+                    timestamp.extend_from_slice(ts);
+                }
+                None => *slot = Some((v.to_vec(), ts.to_vec())),
+            }
+        }
+
+        out.truncate(keys.len());
+        out.resize_with(keys.len(), || None);
+        let cfs: Vec<_> = keys.iter().map(|_| cf.inner as *const DBCFHandle).collect();
+        let keys_list: Vec<_> = keys.iter().map(|k| k.as_ptr()).collect();
+        let keys_list_sizes: Vec<_> = keys.iter().map(|k| k.len() as size_t).collect();
+        unsafe {
+            ffi_try!(crocksdb_multi_get_cf_with_ts(
+                self.inner,
+                readopts.get_inner(),
+                cfs.as_ptr(),
+                keys.len() as size_t,
+                keys_list.as_ptr(),
+                keys_list_sizes.as_ptr(),
+                out as *mut Vec<Option<(Vec<u8>, Vec<u8>)>> as *mut c_void,
+                on_result
+            ));
+        }
+        Ok(())
+    }
+
+    /// Gets the value and user timestamp of `key` in `cf` at the timestamp
+    /// set in `readopts`.
+    pub fn get_cf_opt_ts(
+        &self,
+        cf: &CFHandle,
+        key: &[u8],
+        readopts: &ReadOptions,
+    ) -> Result<Option<(Vec<u8>, Vec<u8>)>, String> {
+        let mut out = Vec::with_capacity(1);
+        self.multi_get_cf_opt_ts_into(cf, &[key], readopts, &mut out)?;
+        Ok(out.pop().unwrap())
+    }
+
+    /// Same as `get_cf_opt_ts` on the default column family.
+    pub fn get_opt_ts(
+        &self,
+        key: &[u8],
+        readopts: &ReadOptions,
+    ) -> Result<Option<(Vec<u8>, Vec<u8>)>, String> {
+        let cf = self.cf_handle("default").unwrap();
+        self.get_cf_opt_ts(cf, key, readopts)
+    }
+
+    /// Returns the value and user timestamp of every version of `key` in `cf`
+    /// written between `ReadOptions::set_iter_start_ts` and the timestamp set
+    /// in `readopts`, newest first. Deleted versions are skipped. Only the
+    /// latest version is returned if the start timestamp isn't set.
+    pub fn get_versions_cf_opt(
+        &self,
+        cf: &CFHandle,
+        key: &[u8],
+        readopts: &ReadOptions,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, String> {
+        extern "C" fn on_version(
+            ctx: *mut c_void,
+            v: *const u8,
+            vlen: size_t,
+            ts: *const u8,
+            tslen: size_t,
+        ) {
+            let out = unsafe { &mut *(ctx as *mut Vec<(Vec<u8>, Vec<u8>)>) };
+            let (v, ts) = unsafe {
+                (
+                    slice::from_raw_parts(v, vlen),
+                    slice::from_raw_parts(ts, tslen),
+                )
+            };
+            out.push((v.to_vec(), ts.to_vec()));
+        }
+
+        let mut out: Vec<(Vec<u8>, Vec<u8>)> = vec![];
+        unsafe {
+            ffi_try!(crocksdb_get_versions_cf_with_ts(
+                self.inner,
+                readopts.get_inner(),
+                cf.inner,
+                key.as_ptr(),
+                key.len() as size_t,
+                &mut out as *mut Vec<(Vec<u8>, Vec<u8>)> as *mut c_void,
+                on_version
+            ));
+        }
+        Ok(out)
+    }
+
+    /// Like `get_cf_opt`, but also returns the number of blocks read from disk
+    /// while serving the request.
+    ///
+    /// The count is taken from the thread local perf context, the perf level is
+    /// raised to `EnableCount` for the duration of the call if necessary.
+    pub fn get_cf_traced(
+        &self,
+        cf: &CFHandle,
+        key: &[u8],
+        readopts: &ReadOptions,
+    ) -> Result<(Option<DBVector>, u64), String> {
+        let level = get_perf_level();
+        let raise_level = matches!(
+            level,
+            PerfLevel::Uninitialized | PerfLevel::Disable | PerfLevel::OutOfBounds
+        );
+        if raise_level {
+            set_perf_level(PerfLevel::EnableCount);
+        }
+        let ctx = PerfContext::get();
+        let before = ctx.block_read_count();
+        let res = self.get_cf_opt(cf, key, readopts);
+        let block_reads = ctx.block_read_count().saturating_sub(before);
+        if raise_level {
+            set_perf_level(level);
+        }
+        res.map(|v| (v, block_reads))
+    }
+
     pub fn create_cf<'a, T>(&mut self, cfd: T) -> Result<&CFHandle, String>
     where
         T: Into<ColumnFamilyDescriptor<'a>>,
     {
         let mut cfd = cfd.into();
+        check_blob_options(cfd.name, &cfd.options)?;
         let cname = match CString::new(cfd.name.as_bytes()) {
             Ok(c) => c,
             Err(_) => {
@@ -1045,6 +1616,12 @@ impl DB {
         self.cfs[idx].as_ref().map(|h| &h.1)
     }
 
+    /// Returns the user timestamp size of the column family, 0 if it doesn't
+    /// use user timestamps.
+    pub fn cf_timestamp_size(&self, cf: &CFHandle) -> usize {
+        cf.timestamp_size()
+    }
+
     /// get all column family names, including 'default'.
     pub fn cf_names(&self) -> Vec<&str> {
         self.cfs
@@ -1092,11 +1669,118 @@ impl DB {
         crocksdb_ffi::crocksdb_release_snapshot(self.inner, snap.get_inner())
     }
 
-    pub fn put_opt(
+    /// Creates iterators over `cfs` that read from one consistent view of the
+    /// DB, as if they were created under the same snapshot.
+    ///
+    /// All iterators share `opts`, so `seek_with_ts` and
+    /// `seek_for_prev_with_ts` are not supported by them.
+    pub fn iter_cfs_opt(
         &self,
-        key: &[u8],
-        value: &[u8],
-        writeopts: &WriteOptions,
+        cfs: &[&CFHandle],
+        opts: ReadOptions,
+    ) -> Result<Vec<DBIterator<&DB>>, String> {
+        let cf_handles: Vec<_> = cfs.iter().map(|cf| cf.inner).collect();
+        let mut iterators = vec![ptr::null_mut(); cfs.len()];
+        unsafe {
+            if self.is_titan() {
+                ffi_try!(ctitandb_create_iterators(
+                    self.inner,
+                    opts.get_inner(),
+                    opts.get_titan_inner(),
+                    cf_handles.as_ptr(),
+                    iterators.as_mut_ptr(),
+                    cfs.len() as size_t
+                ));
+            } else {
+                ffi_try!(crocksdb_create_iterators(
+                    self.inner,
+                    opts.get_inner(),
+                    cf_handles.as_ptr(),
+                    iterators.as_mut_ptr(),
+                    cfs.len() as size_t
+                ));
+            }
+        }
+        // The options are only kept alive here and never accessed through the
+        // `Arc`, so sharing them between iterators on different threads is fine.
+        #[allow(clippy::arc_with_non_send_sync)]
+        let opts = Arc::new(opts);
+        Ok(iterators
+            .into_iter()
+            .map(|inner| DBIterator {
+                _db: self,
+                _readopts: ReadOptions::new(),
+                _shared_readopts: Some(opts.clone()),
+                inner,
+            })
+            .collect())
+    }
+
+    /// Calls `f` with every entry of `cf` in `[start, end)` in key order, until
+    /// `f` returns false or the range is exhausted.
+    ///
+    /// The upper bound of `readopts` is replaced by `end`. Iterator errors are
+    /// returned as is.
+    pub fn scan_cf<F>(
+        &self,
+        cf: &CFHandle,
+        start: &[u8],
+        end: &[u8],
+        mut readopts: ReadOptions,
+        mut f: F,
+    ) -> Result<(), String>
+    where
+        F: FnMut(&[u8], &[u8]) -> bool,
+    {
+        readopts.set_iterate_upper_bound(end.to_vec());
+        let mut iter = self.iter_cf_opt(cf, readopts);
+        let mut valid = iter.seek(SeekKey::Key(start))?;
+        while valid {
+            if !f(iter.key(), iter.value()) {
+                break;
+            }
+            valid = iter.next()?;
+        }
+        Ok(())
+    }
+
+    /// Returns up to `limit` entries of `cf` from `start` in key order,
+    /// stopping before `end` if it's given.
+    ///
+    /// The upper bound of `readopts` is replaced by `end` if it's given. Keys
+    /// of column families using user timestamps are returned without them.
+    pub fn scan_cf_limit(
+        &self,
+        cf: &CFHandle,
+        start: SeekKey,
+        end: Option<&[u8]>,
+        limit: usize,
+        mut readopts: ReadOptions,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, String> {
+        if let Some(end) = end {
+            readopts.set_iterate_upper_bound(end.to_vec());
+        }
+        let mut entries = vec![];
+        if limit == 0 {
+            return Ok(entries);
+        }
+        let mut iter = self.iter_cf_opt(cf, readopts);
+        let mut valid = iter.seek(start)?;
+        while valid {
+            entries.push((iter.key().to_vec(), iter.value().to_vec()));
+            if entries.len() == limit {
+                break;
+            }
+            valid = iter.next()?;
+        }
+        Ok(entries)
+    }
+
+    pub fn put_opt(
+        &self,
+        key: &[u8],
+        value: &[u8],
+        writeopts: &WriteOptions,
     ) -> Result<(), String> {
         unsafe {
             ffi_try!(crocksdb_put(
@@ -1131,6 +1815,22 @@ impl DB {
             Ok(())
         }
     }
+
+    /// Like `put_cf_opt`, but returns the sequence number assigned to the write.
+    pub fn put_cf_returning_seq(
+        &self,
+        cf: &CFHandle,
+        key: &[u8],
+        value: &[u8],
+        writeopts: &WriteOptions,
+    ) -> Result<u64, String> {
+        let batch = WriteBatch::new();
+        batch.put_cf(cf, key, value)?;
+        let mut seq = 0;
+        self.write_callback(&batch, writeopts, |s| seq = s)?;
+        Ok(seq)
+    }
+
     pub fn merge_opt(
         &self,
         key: &[u8],
@@ -1268,6 +1968,31 @@ impl DB {
         }
     }
 
+    /// Flushes `cf` and returns the metadata of the L0 files the flush
+    /// created, found by comparing the files of L0 before and after it.
+    ///
+    /// Files that are compacted away before the flush returns are missing,
+    /// so auto compactions should be disabled if every file is expected.
+    pub fn flush_and_get_sst_cf(&self, cf: &CFHandle) -> Result<FlushedSstFiles, String> {
+        let l0_files = |meta: &ColumnFamilyMetaData| -> Vec<String> {
+            meta.get_level(0)
+                .get_files()
+                .iter()
+                .map(|f| f.get_name())
+                .collect()
+        };
+        let before = l0_files(&self.get_column_family_meta_data(cf));
+        let mut opts = FlushOptions::default();
+        opts.set_wait(true);
+        self.flush_cf(cf, &opts)?;
+        let meta = self.get_column_family_meta_data(cf);
+        let names = l0_files(&meta)
+            .into_iter()
+            .filter(|name| !before.contains(name))
+            .collect();
+        Ok(FlushedSstFiles::new(meta, names))
+    }
+
     /// Flushes multiple column families.
     /// If atomic flush is not enabled, flush_cfs is equivalent to
     /// calling flush_cf multiple times.
@@ -1287,6 +2012,67 @@ impl DB {
         }
     }
 
+    /// Blocks until there are no pending or running flushes and compactions.
+    ///
+    /// The bundled RocksDB predates `DB::WaitForCompact`, so this is emulated
+    /// by polling the background work properties every 10ms.
+    ///
+    /// If `flush` is true, memtables of all column families are flushed first.
+    /// Pending compactions of column families with auto compactions disabled
+    /// are never run, so they are not waited for. Returns an error if
+    /// background work doesn't settle within `timeout`, or if the DB has hit a
+    /// background error, after which pending work would never drain. Without
+    /// a timeout, this blocks for as long as background work is held up, for
+    /// example by a write stall.
+    pub fn wait_for_compact(&self, flush: bool, timeout: Option<Duration>) -> Result<(), String> {
+        if flush {
+            let mut opts = FlushOptions::default();
+            opts.set_wait(true);
+            let cfs: Vec<_> = self.cfs.iter().flatten().map(|(_, cf)| cf).collect();
+            self.flush_cfs(&cfs, &opts)?;
+        }
+        let property = |cf: Option<&CFHandle>, name: &str| {
+            self.get_property_int_cf_opt(cf, name)
+                .ok_or_else(|| format!("failed to get property {}", name))
+        };
+        let cfs: Vec<_> = self
+            .cfs
+            .iter()
+            .flatten()
+            .map(|(_, cf)| (cf, !self.get_options_cf(cf).get_disable_auto_compactions()))
+            .collect();
+        let start = Instant::now();
+        loop {
+            let errors = property(None, "rocksdb.background-errors")?;
+            if errors > 0 {
+                return Err(format!(
+                    "background work stopped after {} background errors",
+                    errors
+                ));
+            }
+            let mut settled = property(None, "rocksdb.num-running-flushes")? == 0
+                && property(None, "rocksdb.num-running-compactions")? == 0;
+            for &(cf, auto_compactions) in &cfs {
+                settled = settled
+                    && property(Some(cf), "rocksdb.mem-table-flush-pending")? == 0
+                    && (!auto_compactions
+                        || property(Some(cf), "rocksdb.compaction-pending")? == 0);
+            }
+            if settled {
+                return Ok(());
+            }
+            if let Some(timeout) = timeout {
+                if start.elapsed() >= timeout {
+                    return Err(format!(
+                        "background work didn't finish within {:?}",
+                        timeout
+                    ));
+                }
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
     /// Flush the WAL memory buffer to the file. If sync is true, it calls SyncWAL
     /// afterwards.
     pub fn flush_wal(&self, sync: bool) -> Result<(), String> {
@@ -1307,6 +2093,16 @@ impl DB {
         }
     }
 
+    /// Make a secondary instance replay the MANIFEST and WAL of the primary
+    /// so that it can read the latest writes. Only valid on a db opened with
+    /// `open_as_secondary`.
+    pub fn try_catch_up_with_primary(&self) -> Result<(), String> {
+        unsafe {
+            ffi_try!(crocksdb_try_catch_up_with_primary(self.inner));
+            Ok(())
+        }
+    }
+
     /// Ref https://github.com/facebook/rocksdb/wiki/Background-Error-Handling#recovery
     pub fn resume(&self) -> Result<(), String> {
         unsafe {
@@ -1320,6 +2116,32 @@ impl DB {
         unsafe { crocksdb_ffi::crocksdb_get_latest_sequence_number(self.inner) }
     }
 
+    /// Returns an iterator over the write batches in the WAL starting from the
+    /// batch that contains `seq_number`. The WAL files must still exist, see
+    /// `DBOptions::set_wal_ttl_seconds` and `set_wal_size_limit_mb`.
+    pub fn get_updates_since(&self, seq_number: u64) -> Result<TransactionLogIterator, String> {
+        unsafe {
+            let iter = ffi_try!(crocksdb_get_updates_since(self.inner, seq_number));
+            Ok(TransactionLogIterator::from_raw(iter, None))
+        }
+    }
+
+    /// Same as `get_updates_since`, but only yields the operations on the
+    /// column families in `cf_ids`, see `CFHandle::id`.
+    pub fn get_updates_since_cf(
+        &self,
+        seq_number: u64,
+        cf_ids: &[u32],
+    ) -> Result<TransactionLogIterator, String> {
+        unsafe {
+            let iter = ffi_try!(crocksdb_get_updates_since(self.inner, seq_number));
+            Ok(TransactionLogIterator::from_raw(
+                iter,
+                Some(cf_ids.to_vec()),
+            ))
+        }
+    }
+
     /// Return the approximate file system space used by keys in each ranges.
     ///
     /// Note that the returned sizes measure file system space usage, so
@@ -1377,6 +2199,82 @@ impl DB {
         sizes
     }
 
+    /// Like `get_approximate_sizes_cf`, but allows choosing whether data in
+    /// memtables and in SST files should be taken into account. At least one
+    /// of them must be included.
+    pub fn get_approximate_sizes_cf_with_flags(
+        &self,
+        cf: &CFHandle,
+        ranges: &[Range],
+        include_memtables: bool,
+        include_files: bool,
+    ) -> Result<Vec<u64>, String> {
+        let start_keys: Vec<*const u8> = ranges.iter().map(|x| x.start_key.as_ptr()).collect();
+        let start_key_lens: Vec<_> = ranges.iter().map(|x| x.start_key.len()).collect();
+        let end_keys: Vec<*const u8> = ranges.iter().map(|x| x.end_key.as_ptr()).collect();
+        let end_key_lens: Vec<_> = ranges.iter().map(|x| x.end_key.len()).collect();
+        let mut sizes: Vec<u64> = vec![0; ranges.len()];
+        // Matches `DB::SizeApproximationFlags`.
+        let mut include_flags = 0;
+        if include_memtables {
+            include_flags |= 1;
+        }
+        if include_files {
+            include_flags |= 1 << 1;
+        }
+        unsafe {
+            ffi_try!(crocksdb_approximate_sizes_cf_with_flags(
+                self.inner,
+                cf.inner,
+                ranges.len() as i32,
+                start_keys.as_ptr(),
+                start_key_lens.as_ptr(),
+                end_keys.as_ptr(),
+                end_key_lens.as_ptr(),
+                include_flags,
+                sizes.as_mut_ptr()
+            ));
+        }
+        Ok(sizes)
+    }
+
+    /// Like `get_approximate_sizes`, but controlled by `opts`.
+    pub fn get_approximate_sizes_opt(
+        &self,
+        ranges: &[Range],
+        opts: &SizeApproximationOptions,
+    ) -> Result<Vec<u64>, String> {
+        let cf = self.cf_handle("default").unwrap();
+        self.get_approximate_sizes_cf_opt(cf, ranges, opts)
+    }
+
+    pub fn get_approximate_sizes_cf_opt(
+        &self,
+        cf: &CFHandle,
+        ranges: &[Range],
+        opts: &SizeApproximationOptions,
+    ) -> Result<Vec<u64>, String> {
+        let start_keys: Vec<*const u8> = ranges.iter().map(|x| x.start_key.as_ptr()).collect();
+        let start_key_lens: Vec<_> = ranges.iter().map(|x| x.start_key.len()).collect();
+        let end_keys: Vec<*const u8> = ranges.iter().map(|x| x.end_key.as_ptr()).collect();
+        let end_key_lens: Vec<_> = ranges.iter().map(|x| x.end_key.len()).collect();
+        let mut sizes: Vec<u64> = vec![0; ranges.len()];
+        unsafe {
+            ffi_try!(crocksdb_approximate_sizes_cf_opt(
+                self.inner,
+                cf.inner,
+                opts.inner,
+                ranges.len() as i32,
+                start_keys.as_ptr(),
+                start_key_lens.as_ptr(),
+                end_keys.as_ptr(),
+                end_key_lens.as_ptr(),
+                sizes.as_mut_ptr()
+            ));
+        }
+        Ok(sizes)
+    }
+
     // Return the approximate number of records and size in the range of memtables.
     pub fn get_approximate_memtable_stats(&self, range: &Range) -> (u64, u64) {
         let (mut count, mut size) = (0, 0);
@@ -1682,6 +2580,20 @@ impl DB {
         self.get_property_value_cf_opt(Some(cf), name)
     }
 
+    /// Returns the "rocksdb.levelstats" summary of the column family, which
+    /// lists the number of files and total size of every level.
+    pub fn level_stats_cf(&self, cf: &CFHandle) -> String {
+        self.get_property_value_cf(cf, "rocksdb.levelstats")
+            .unwrap_or_default()
+    }
+
+    /// Returns the "rocksdb.sstables" dump of the column family, which lists
+    /// every SST file of each level with its size and key range.
+    pub fn sstables_cf(&self, cf: &CFHandle) -> String {
+        self.get_property_value_cf(cf, "rocksdb.sstables")
+            .unwrap_or_default()
+    }
+
     /// Return the int property in rocksdb.
     /// Return None if the property not exists or not int type.
     pub fn get_property_int(&self, name: &str) -> Option<u64> {
@@ -1751,6 +2663,45 @@ impl DB {
         }
     }
 
+    /// Returns the write stall counters of `cf`.
+    ///
+    /// The bundled RocksDB has no "rocksdb.cf-write-stall-stats" property, so
+    /// the counters are read from the `io_stalls.*` entries of the
+    /// "rocksdb.cfstats" map property instead.
+    pub fn get_cf_write_stall_stats(&self, cf: &CFHandle) -> Result<WriteStallStats, String> {
+        self.get_map_property_cf(cf, "rocksdb.cfstats")
+            .map(|mp| WriteStallStats::from_map_property(&mp))
+            .ok_or_else(|| "failed to get map property rocksdb.cfstats".to_owned())
+    }
+
+    pub fn background_activity_cf(&self, cf: &CFHandle) -> Option<BackgroundActivity> {
+        let get = |name: &str| self.get_property_int_cf(cf, &format!("rocksdb.{}", name));
+        Some(BackgroundActivity {
+            mem_table_flush_pending: get("mem-table-flush-pending")? != 0,
+            compaction_pending: get("compaction-pending")? != 0,
+            num_running_flushes: get("num-running-flushes")?,
+            num_running_compactions: get("num-running-compactions")?,
+        })
+    }
+
+    /// Returns the blob file statistics of `cf`, or `None` if the db is not
+    /// opened with Titan.
+    pub fn get_titan_blob_stats_cf(&self, cf: &CFHandle) -> Option<TitanBlobStats> {
+        let get = |name: &str| self.get_property_int_cf(cf, &format!("rocksdb.titandb.{}", name));
+        Some(TitanBlobStats {
+            num_live_blob_file: get("num-live-blob-file")?,
+            num_obsolete_blob_file: get("num-obsolete-blob-file")?,
+            live_blob_size: get("live-blob-size")?,
+            live_blob_file_size: get("live-blob-file-size")?,
+            obsolete_blob_file_size: get("obsolete-blob-file-size")?,
+            num_discardable_ratio_le0_file: get("num-discardable-ratio-le0-file")?,
+            num_discardable_ratio_le20_file: get("num-discardable-ratio-le20-file")?,
+            num_discardable_ratio_le50_file: get("num-discardable-ratio-le50-file")?,
+            num_discardable_ratio_le80_file: get("num-discardable-ratio-le80-file")?,
+            num_discardable_ratio_le100_file: get("num-discardable-ratio-le100-file")?,
+        })
+    }
+
     pub fn set_db_options(&self, options: &[(&str, &str)]) -> Result<(), String> {
         unsafe {
             let name_strs: Vec<_> = options
@@ -1883,6 +2834,39 @@ impl DB {
         Ok(has_flush)
     }
 
+    /// Ingests an SST file whose content is `data` into `cf`, e.g. a file
+    /// built by an `SstFileWriter` on `Env::new_mem`.
+    ///
+    /// RocksDB only ingests files, so `data` is written through the DB's env
+    /// to a file in the DB directory, which is then moved into the DB instead
+    /// of being copied; `move_files` of `opt` is ignored. The file may be left
+    /// behind if the process crashes during the ingestion.
+    pub fn ingest_external_file_from_buffer_cf(
+        &self,
+        cf: &CFHandle,
+        opt: &IngestExternalFileOptions,
+        data: &[u8],
+    ) -> Result<(), String> {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        let path = Path::new(&self.path).join(format!(
+            "ingest-{}-{}.sst.tmp",
+            process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        let c_path = CString::new(path.to_str().unwrap()).unwrap();
+        unsafe {
+            ffi_try!(crocksdb_ingest_external_file_from_buffer_cf(
+                self.inner,
+                cf.inner,
+                data.as_ptr() as *const c_char,
+                data.len() as size_t,
+                c_path.as_ptr(),
+                opt.inner
+            ));
+        }
+        Ok(())
+    }
+
     pub fn backup_at(&self, path: &str) -> Result<BackupEngine, String> {
         let backup_engine = BackupEngine::open(DBOptions::new(), path).unwrap();
         unsafe {
@@ -2076,6 +3060,29 @@ impl DB {
         }
     }
 
+    /// Returns the names of the files a consistent copy of the DB needs,
+    /// i.e. the SST files, the current MANIFEST and CURRENT, together with
+    /// the valid size of the MANIFEST. The names are relative to the DB path
+    /// and start with a '/'. WAL files are not included.
+    pub fn get_live_file_names(&self, flush_memtable: bool) -> Result<(Vec<String>, u64), String> {
+        unsafe {
+            let mut len: size_t = 0;
+            let mut manifest_size = 0;
+            let list = ffi_try!(crocksdb_get_live_file_names(
+                self.inner,
+                flush_memtable,
+                &mut len,
+                &mut manifest_size
+            ));
+            let names = slice::from_raw_parts(list, len)
+                .iter()
+                .map(|&name| CStr::from_ptr(name).to_string_lossy().into_owned())
+                .collect();
+            crocksdb_ffi::crocksdb_live_file_names_destroy(list, len);
+            Ok((names, manifest_size))
+        }
+    }
+
     pub fn compact_files_cf(
         &self,
         cf: &CFHandle,
@@ -2296,106 +3303,429 @@ impl Writable for WriteBatch {
     }
 }
 
-pub struct DBVector {
-    pinned_slice: *mut DBPinnableSlice,
-}
-
-impl Debug for DBVector {
-    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
-        write!(formatter, "{:?}", &**self)
-    }
+/// A `WriteBatch` with a searchable index of its contents, so reads can
+/// see the writes staged in it before they are written to the DB.
+pub struct WriteBatchWithIndex {
+    inner: *mut DBWriteBatchWithIndex,
 }
 
-impl<'a> PartialEq<&'a [u8]> for DBVector {
-    fn eq(&self, rhs: &&[u8]) -> bool {
-        **rhs == **self
-    }
-}
+unsafe impl Send for WriteBatchWithIndex {}
 
-impl Deref for DBVector {
-    type Target = [u8];
-    fn deref(&self) -> &[u8] {
-        let mut val_len: size_t = 0;
-        let val_len_ptr = &mut val_len as *mut size_t;
-        unsafe {
-            let val = crocksdb_ffi::crocksdb_pinnableslice_value(self.pinned_slice, val_len_ptr);
-            slice::from_raw_parts(val, val_len)
+impl WriteBatchWithIndex {
+    /// If `overwrite_key` is true, a later update to a key replaces the
+    /// earlier ones in the index, so the iterator only returns the latest
+    /// update of each key. Otherwise all the updates are kept.
+    pub fn new(reserved_bytes: usize, overwrite_key: bool) -> WriteBatchWithIndex {
+        WriteBatchWithIndex {
+            inner: unsafe {
+                crocksdb_ffi::crocksdb_writebatch_wi_create(reserved_bytes, overwrite_key)
+            },
         }
     }
-}
 
-impl Drop for DBVector {
-    fn drop(&mut self) {
-        unsafe {
-            crocksdb_ffi::crocksdb_pinnableslice_destroy(self.pinned_slice);
-        }
+    pub fn count(&self) -> usize {
+        unsafe { crocksdb_ffi::crocksdb_writebatch_wi_count(self.inner) as usize }
     }
-}
 
-impl DBVector {
-    pub fn from_pinned_slice(s: *mut DBPinnableSlice) -> DBVector {
-        DBVector { pinned_slice: s }
+    pub fn is_empty(&self) -> bool {
+        self.count() == 0
     }
 
-    pub fn to_utf8(&self) -> Option<&str> {
-        from_utf8(self.deref()).ok()
+    pub fn clear(&self) {
+        unsafe {
+            crocksdb_ffi::crocksdb_writebatch_wi_clear(self.inner);
+        }
     }
-}
-
-pub struct BackupEngine {
-    inner: *mut DBBackupEngine,
-}
-
-impl BackupEngine {
-    pub fn open(opts: DBOptions, path: &str) -> Result<BackupEngine, String> {
-        let cpath = match CString::new(path.as_bytes()) {
-            Ok(c) => c,
-            Err(_) => {
-                return Err(
-                    "Failed to convert path to CString when opening rocksdb backup engine"
-                        .to_owned(),
-                );
-            }
-        };
 
-        if let Err(e) = fs::create_dir_all(path) {
-            return Err(format!(
-                "Failed to create rocksdb backup directory: {:?}",
-                e
+    /// Gets the value of `key` in `cf` as if the batch was written to `db`.
+    pub fn get_from_batch_and_db(
+        &self,
+        db: &DB,
+        cf: &CFHandle,
+        key: &[u8],
+        readopts: &ReadOptions,
+    ) -> Result<Option<DBVector>, String> {
+        unsafe {
+            let val = ffi_try!(crocksdb_writebatch_wi_get_from_batch_and_db_cf(
+                self.inner,
+                db.inner,
+                readopts.get_inner(),
+                cf.inner,
+                key.as_ptr(),
+                key.len() as size_t
             ));
+            if val.is_null() {
+                Ok(None)
+            } else {
+                Ok(Some(DBVector::from_pinned_slice(val)))
+            }
         }
-
-        let backup_engine =
-            unsafe { ffi_try!(crocksdb_backup_engine_open(opts.inner, cpath.as_ptr())) };
-
-        Ok(BackupEngine {
-            inner: backup_engine,
-        })
     }
-}
 
-impl Drop for BackupEngine {
-    fn drop(&mut self) {
+    /// Returns an iterator over `cf` of `db` with the updates in the batch
+    /// applied. The batch is borrowed mutably, so it can't be modified while
+    /// the iterator is alive:
+    ///
+    /// ```compile_fail
+    /// use rocksdb::{ReadOptions, WriteBatchWithIndex, DB};
+    ///
+    /// fn scan(db: &DB, wbwi: &mut WriteBatchWithIndex) {
+    ///     let cf = db.cf_handle("default").unwrap();
+    ///     let iter = wbwi.iter_with_base_cf(db, cf, ReadOptions::new());
+    ///     wbwi.clear();
+    ///     drop(iter);
+    /// }
+    /// ```
+    pub fn iter_with_base_cf<'a>(
+        &'a mut self,
+        db: &'a DB,
+        cf: &CFHandle,
+        readopts: ReadOptions,
+    ) -> DBIterator<&'a DB> {
+        let mut iter = DBIterator::new_cf(db, cf, readopts);
         unsafe {
-            crocksdb_ffi::crocksdb_backup_engine_close(self.inner);
+            iter.inner = crocksdb_ffi::crocksdb_writebatch_wi_create_iterator_with_base_cf(
+                self.inner, iter.inner, cf.inner,
+            );
         }
+        iter
     }
 }
 
-// SstFileReader is used to read sst files that are generated by DB or
-// SstFileWriter.
-pub struct SstFileReader {
-    inner: *mut crocksdb_ffi::SstFileReader,
-    _opt: ColumnFamilyOptions,
+impl Drop for WriteBatchWithIndex {
+    fn drop(&mut self) {
+        unsafe { crocksdb_ffi::crocksdb_writebatch_wi_destroy(self.inner) }
+    }
 }
 
-unsafe impl Send for SstFileReader {}
-
-impl SstFileReader {
-    /// Creates a new SstFileReader.
-    pub fn new(opt: ColumnFamilyOptions) -> Self {
+impl Writable for WriteBatchWithIndex {
+    fn put(&self, key: &[u8], value: &[u8]) -> Result<(), String> {
         unsafe {
-            Self {
+            ffi_try!(crocksdb_writebatch_wi_put(
+                self.inner,
+                key.as_ptr(),
+                key.len() as size_t,
+                value.as_ptr(),
+                value.len() as size_t
+            ));
+            Ok(())
+        }
+    }
+
+    fn put_cf(&self, cf: &CFHandle, key: &[u8], value: &[u8]) -> Result<(), String> {
+        unsafe {
+            ffi_try!(crocksdb_writebatch_wi_put_cf(
+                self.inner,
+                cf.inner,
+                key.as_ptr(),
+                key.len() as size_t,
+                value.as_ptr(),
+                value.len() as size_t
+            ));
+            Ok(())
+        }
+    }
+
+    fn merge(&self, key: &[u8], value: &[u8]) -> Result<(), String> {
+        unsafe {
+            ffi_try!(crocksdb_writebatch_wi_merge(
+                self.inner,
+                key.as_ptr(),
+                key.len() as size_t,
+                value.as_ptr(),
+                value.len() as size_t
+            ));
+            Ok(())
+        }
+    }
+
+    fn merge_cf(&self, cf: &CFHandle, key: &[u8], value: &[u8]) -> Result<(), String> {
+        unsafe {
+            ffi_try!(crocksdb_writebatch_wi_merge_cf(
+                self.inner,
+                cf.inner,
+                key.as_ptr(),
+                key.len() as size_t,
+                value.as_ptr(),
+                value.len() as size_t
+            ));
+            Ok(())
+        }
+    }
+
+    fn delete(&self, key: &[u8]) -> Result<(), String> {
+        unsafe {
+            ffi_try!(crocksdb_writebatch_wi_delete(
+                self.inner,
+                key.as_ptr(),
+                key.len() as size_t
+            ));
+            Ok(())
+        }
+    }
+
+    fn delete_cf(&self, cf: &CFHandle, key: &[u8]) -> Result<(), String> {
+        unsafe {
+            ffi_try!(crocksdb_writebatch_wi_delete_cf(
+                self.inner,
+                cf.inner,
+                key.as_ptr(),
+                key.len() as size_t
+            ));
+            Ok(())
+        }
+    }
+
+    fn single_delete(&self, key: &[u8]) -> Result<(), String> {
+        unsafe {
+            ffi_try!(crocksdb_writebatch_wi_single_delete(
+                self.inner,
+                key.as_ptr(),
+                key.len() as size_t
+            ));
+            Ok(())
+        }
+    }
+
+    fn single_delete_cf(&self, cf: &CFHandle, key: &[u8]) -> Result<(), String> {
+        unsafe {
+            ffi_try!(crocksdb_writebatch_wi_single_delete_cf(
+                self.inner,
+                cf.inner,
+                key.as_ptr(),
+                key.len() as size_t
+            ));
+            Ok(())
+        }
+    }
+
+    fn delete_range(&self, begin_key: &[u8], end_key: &[u8]) -> Result<(), String> {
+        unsafe {
+            ffi_try!(crocksdb_writebatch_wi_delete_range(
+                self.inner,
+                begin_key.as_ptr(),
+                begin_key.len(),
+                end_key.as_ptr(),
+                end_key.len()
+            ));
+            Ok(())
+        }
+    }
+
+    fn delete_range_cf(
+        &self,
+        cf: &CFHandle,
+        begin_key: &[u8],
+        end_key: &[u8],
+    ) -> Result<(), String> {
+        unsafe {
+            ffi_try!(crocksdb_writebatch_wi_delete_range_cf(
+                self.inner,
+                cf.inner,
+                begin_key.as_ptr(),
+                begin_key.len(),
+                end_key.as_ptr(),
+                end_key.len()
+            ));
+            Ok(())
+        }
+    }
+}
+
+pub struct DBVector {
+    pinned_slice: *mut DBPinnableSlice,
+}
+
+impl Debug for DBVector {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "{:?}", &**self)
+    }
+}
+
+impl<'a> PartialEq<&'a [u8]> for DBVector {
+    fn eq(&self, rhs: &&[u8]) -> bool {
+        **rhs == **self
+    }
+}
+
+impl Deref for DBVector {
+    type Target = [u8];
+    fn deref(&self) -> &[u8] {
+        let mut val_len: size_t = 0;
+        let val_len_ptr = &mut val_len as *mut size_t;
+        unsafe {
+            let val = crocksdb_ffi::crocksdb_pinnableslice_value(self.pinned_slice, val_len_ptr);
+            slice::from_raw_parts(val, val_len)
+        }
+    }
+}
+
+impl Drop for DBVector {
+    fn drop(&mut self) {
+        unsafe {
+            crocksdb_ffi::crocksdb_pinnableslice_destroy(self.pinned_slice);
+        }
+    }
+}
+
+impl DBVector {
+    pub fn from_pinned_slice(s: *mut DBPinnableSlice) -> DBVector {
+        DBVector { pinned_slice: s }
+    }
+
+    pub fn to_utf8(&self) -> Option<&str> {
+        from_utf8(self.deref()).ok()
+    }
+}
+
+pub struct BackupEngine {
+    inner: *mut DBBackupEngine,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackupInfo {
+    pub backup_id: u32,
+    /// Seconds since the epoch when the backup was created.
+    pub timestamp: i64,
+    pub size: u64,
+    pub num_files: u32,
+}
+
+impl BackupEngine {
+    pub fn open(opts: DBOptions, path: &str) -> Result<BackupEngine, String> {
+        let cpath = match CString::new(path.as_bytes()) {
+            Ok(c) => c,
+            Err(_) => {
+                return Err(
+                    "Failed to convert path to CString when opening rocksdb backup engine"
+                        .to_owned(),
+                );
+            }
+        };
+
+        if let Err(e) = fs::create_dir_all(path) {
+            return Err(format!(
+                "Failed to create rocksdb backup directory: {:?}",
+                e
+            ));
+        }
+
+        let backup_engine =
+            unsafe { ffi_try!(crocksdb_backup_engine_open(opts.inner, cpath.as_ptr())) };
+
+        Ok(BackupEngine {
+            inner: backup_engine,
+        })
+    }
+
+    /// Backs up `db`. SST files already in the backup directory are shared
+    /// with the new backup instead of being copied again. If
+    /// `flush_before_backup` is false, the memtables are backed up through
+    /// the WAL files instead of being flushed.
+    pub fn create_new_backup_flush(
+        &self,
+        db: &DB,
+        flush_before_backup: bool,
+    ) -> Result<(), String> {
+        unsafe {
+            ffi_try!(crocksdb_backup_engine_create_new_backup_flush(
+                self.inner,
+                db.inner,
+                flush_before_backup
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the backups in the backup directory, oldest first.
+    pub fn get_backup_info(&self) -> Vec<BackupInfo> {
+        unsafe {
+            let info = crocksdb_ffi::crocksdb_backup_engine_get_backup_info(self.inner);
+            let count = crocksdb_ffi::crocksdb_backup_engine_info_count(info);
+            let mut backups = Vec::with_capacity(count as usize);
+            for i in 0..count {
+                backups.push(BackupInfo {
+                    backup_id: crocksdb_ffi::crocksdb_backup_engine_info_backup_id(info, i),
+                    timestamp: crocksdb_ffi::crocksdb_backup_engine_info_timestamp(info, i),
+                    size: crocksdb_ffi::crocksdb_backup_engine_info_size(info, i),
+                    num_files: crocksdb_ffi::crocksdb_backup_engine_info_number_files(info, i),
+                });
+            }
+            crocksdb_ffi::crocksdb_backup_engine_info_destroy(info);
+            backups
+        }
+    }
+
+    /// Checks that the files of backup `backup_id` exist and match the sizes
+    /// and checksums recorded when the backup was created.
+    pub fn verify_backup(&self, backup_id: u32) -> Result<(), String> {
+        unsafe {
+            ffi_try!(crocksdb_backup_engine_verify_backup(self.inner, backup_id));
+        }
+        Ok(())
+    }
+
+    /// Restores backup `backup_id` to `db_dir` and `wal_dir`, the restored
+    /// DB isn't opened.
+    pub fn restore_db_from_backup(
+        &self,
+        backup_id: u32,
+        db_dir: &str,
+        wal_dir: &str,
+        ropts: &RestoreOptions,
+    ) -> Result<(), String> {
+        let c_db_dir = CString::new(db_dir.as_bytes())
+            .map_err(|_| "Failed to convert db_dir to CString when restoring rocksdb".to_owned())?;
+        let c_wal_dir = CString::new(wal_dir.as_bytes()).map_err(|_| {
+            "Failed to convert wal_dir to CString when restoring rocksdb".to_owned()
+        })?;
+        unsafe {
+            ffi_try!(crocksdb_backup_engine_restore_db_from_backup(
+                self.inner,
+                backup_id,
+                c_db_dir.as_ptr(),
+                c_wal_dir.as_ptr(),
+                ropts.inner
+            ));
+        }
+        Ok(())
+    }
+
+    /// Deletes all but the latest `num_backups_to_keep` backups, together
+    /// with the files no remaining backup refers to.
+    pub fn purge_old_backups(&self, num_backups_to_keep: u32) -> Result<(), String> {
+        unsafe {
+            ffi_try!(crocksdb_backup_engine_purge_old_backups(
+                self.inner,
+                num_backups_to_keep
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for BackupEngine {
+    fn drop(&mut self) {
+        unsafe {
+            crocksdb_ffi::crocksdb_backup_engine_close(self.inner);
+        }
+    }
+}
+
+// SstFileReader is used to read sst files that are generated by DB or
+// SstFileWriter.
+pub struct SstFileReader {
+    inner: *mut crocksdb_ffi::SstFileReader,
+    _opt: ColumnFamilyOptions,
+}
+
+unsafe impl Send for SstFileReader {}
+
+impl SstFileReader {
+    /// Creates a new SstFileReader.
+    pub fn new(opt: ColumnFamilyOptions) -> Self {
+        unsafe {
+            Self {
                 inner: crocksdb_ffi::crocksdb_sstfilereader_create(opt.inner),
                 _opt: opt,
             }
@@ -2412,6 +3742,8 @@ impl SstFileReader {
         Ok(())
     }
 
+    /// Returns an iterator over the entries of the file, which is navigated
+    /// the same way as an iterator of a DB.
     pub fn iter(&self) -> DBIterator<&Self> {
         self.iter_opt(ReadOptions::new())
     }
@@ -2425,6 +3757,7 @@ impl SstFileReader {
                 ),
                 _db: self,
                 _readopts: readopts,
+                _shared_readopts: None,
             }
         }
     }
@@ -2446,6 +3779,7 @@ impl SstFileReader {
                 ),
                 _db: this,
                 _readopts: readopts,
+                _shared_readopts: None,
             }
         }
     }
@@ -2545,6 +3879,23 @@ impl SstFileWriter {
         }
     }
 
+    /// Add a Put key with user timestamp `ts`. Returns an error if the length
+    /// of `ts` doesn't match the timestamp size of the writer's comparator.
+    pub fn put_with_ts(&mut self, key: &[u8], ts: &[u8], val: &[u8]) -> Result<(), String> {
+        unsafe {
+            ffi_try!(crocksdb_sstfilewriter_put_with_ts(
+                self.inner,
+                key.as_ptr(),
+                key.len(),
+                ts.as_ptr(),
+                ts.len(),
+                val.as_ptr(),
+                val.len()
+            ));
+            Ok(())
+        }
+    }
+
     pub fn merge(&mut self, key: &[u8], val: &[u8]) -> Result<(), String> {
         unsafe {
             ffi_try!(crocksdb_sstfilewriter_merge(
@@ -2676,6 +4027,8 @@ pub struct Env {
     pub(crate) inner: *mut DBEnv,
     #[allow(dead_code)]
     base: Option<Arc<Env>>,
+    // Whether the files are kept in memory rather than on the local disk.
+    mem: bool,
 }
 
 unsafe impl Send for Env {}
@@ -2688,6 +4041,7 @@ impl Default for Env {
             Env {
                 inner: crocksdb_ffi::crocksdb_default_env_create(),
                 base: None,
+                mem: false,
             }
         }
     }
@@ -2699,6 +4053,7 @@ impl Env {
             Env {
                 inner: crocksdb_ffi::crocksdb_mem_env_create(),
                 base: None,
+                mem: true,
             }
         }
     }
@@ -2723,6 +4078,7 @@ impl Env {
         };
         Ok(Env {
             inner: env,
+            mem: base_env.mem,
             base: Some(base_env),
         })
     }
@@ -2747,6 +4103,7 @@ impl Env {
         };
         Ok(Env {
             inner: env,
+            mem: base_env.mem,
             base: Some(base_env),
         })
     }
@@ -2764,6 +4121,7 @@ impl Env {
         };
         Ok(Env {
             inner: env,
+            mem: base_env.mem,
             base: Some(base_env),
         })
     }
@@ -2823,6 +4181,21 @@ impl Env {
     pub fn get_high_priority_background_threads(&self) -> i32 {
         unsafe { crocksdb_ffi::crocksdb_env_get_high_priority_background_threads(self.inner) }
     }
+
+    /// Sets the size of the thread pool of the given priority. Flushes run in
+    /// the `High` pool and compactions in the `Low` pool. The pools are shared
+    /// by all DBs using this env.
+    pub fn set_background_threads_with_priority(&self, n: i32, priority: DBEnvPriority) {
+        unsafe {
+            crocksdb_ffi::crocksdb_env_set_background_threads_with_priority(
+                self.inner, n, priority,
+            );
+        }
+    }
+
+    pub fn get_background_threads(&self, priority: DBEnvPriority) -> i32 {
+        unsafe { crocksdb_ffi::crocksdb_env_get_background_threads(self.inner, priority) }
+    }
 }
 
 impl Drop for Env {
@@ -2897,6 +4270,44 @@ impl Cache {
             }
         }
     }
+
+    /// Creates a clock cache, which scales better than LRU cache under
+    /// high concurrency. Returns an error if clock cache is not supported,
+    /// it requires RocksDB to be built with TBB.
+    pub fn new_clock_cache(opt: ClockCacheOptions) -> Result<Cache, String> {
+        let inner = unsafe {
+            crocksdb_ffi::crocksdb_cache_create_clock(
+                opt.capacity,
+                opt.num_shard_bits,
+                opt.strict_capacity_limit,
+            )
+        };
+        if inner.is_null() {
+            return Err("clock cache is not supported".to_owned());
+        }
+        Ok(Cache { inner })
+    }
+
+    pub fn get_capacity(&self) -> usize {
+        unsafe { crocksdb_ffi::crocksdb_cache_get_capacity(self.inner) }
+    }
+
+    /// Changes the capacity of the cache. If it's shrunk, unpinned entries
+    /// are evicted right away until the usage fits.
+    pub fn set_capacity(&self, capacity: usize) {
+        unsafe { crocksdb_ffi::crocksdb_cache_set_capacity(self.inner, capacity) }
+    }
+
+    /// Returns the memory size of all entries in the cache.
+    pub fn get_usage(&self) -> usize {
+        unsafe { crocksdb_ffi::crocksdb_cache_get_usage(self.inner) }
+    }
+
+    /// Returns the memory size of the entries that are in use and can't be
+    /// evicted.
+    pub fn get_pinned_usage(&self) -> usize {
+        unsafe { crocksdb_ffi::crocksdb_cache_get_pinned_usage(self.inner) }
+    }
 }
 
 impl Drop for Cache {
@@ -3023,10 +4434,12 @@ mod test {
     use std::str;
     use std::string::String;
     use std::thread;
-    use write_batch::WriteBatchRef;
+    use write_batch::{WriteBatchHandler, WriteBatchRef};
 
     use super::*;
-    use crate::{tempdir_with_prefix, ConcurrentTaskLimiter, FlushOptions};
+    use crate::{
+        tempdir_with_prefix, BlockBasedOptions, ConcurrentTaskLimiter, FlushOptions, MergeOperands,
+    };
 
     #[test]
     fn external() {
@@ -3180,6 +4593,80 @@ mod test {
         assert_eq!(sizes[4], 0);
     }
 
+    #[test]
+    fn approximate_size_with_flags_test() {
+        let path = tempdir_with_prefix("_rust_rocksdb_approximate_size_with_flags");
+        let db = DB::open_default(path.path().to_str().unwrap()).unwrap();
+        let cf = db.cf_handle("default").unwrap();
+        // Use values that can't be compressed, so the size on disk is predictable.
+        let mut seed: u32 = 1;
+        for i in 0..1000 {
+            let value: Vec<u8> = (0..1024)
+                .map(|_| {
+                    seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+                    (seed >> 16) as u8
+                })
+                .collect();
+            db.put(format!("{:04}", i).as_bytes(), &value).unwrap();
+        }
+        let full_range = [Range::new(b"0000", b"9999")];
+
+        // Unflushed data is only visible when memtables are included.
+        let sizes = db
+            .get_approximate_sizes_cf_with_flags(cf, &full_range, false, true)
+            .unwrap();
+        assert_eq!(sizes[0], 0);
+        let sizes = db
+            .get_approximate_sizes_cf_with_flags(cf, &full_range, true, false)
+            .unwrap();
+        assert!(sizes[0] > 0);
+        let (count, _) = db.get_approximate_memtable_stats_cf(cf, &full_range[0]);
+        assert!(count > 0);
+
+        let mut fopts = FlushOptions::default();
+        fopts.set_wait(true);
+        db.flush_cf(cf, &fopts).unwrap();
+        let sizes = db
+            .get_approximate_sizes_cf_with_flags(cf, &full_range, true, true)
+            .unwrap();
+        // 1000 values of 1KiB each, allow for some metadata overhead.
+        assert!(sizes[0] > 500 * 1024, "{}", sizes[0]);
+        assert!(sizes[0] < 2 * 1024 * 1024, "{}", sizes[0]);
+        assert_eq!(db.get_approximate_sizes_cf(cf, &full_range), sizes);
+
+        assert!(db
+            .get_approximate_sizes_cf_with_flags(cf, &full_range, false, false)
+            .is_err());
+    }
+
+    #[test]
+    fn approximate_size_opt_test() {
+        let path = tempdir_with_prefix("_rust_rocksdb_approximate_size_opt");
+        let db = DB::open_default(path.path().to_str().unwrap()).unwrap();
+        for i in 0..1000 {
+            db.put(format!("{:04}", i).as_bytes(), &[b'v'; 1024])
+                .unwrap();
+        }
+        let full_range = [Range::new(b"0000", b"9999")];
+
+        // Only SST files are included by default.
+        let default_opts = SizeApproximationOptions::default();
+        let sizes = db
+            .get_approximate_sizes_opt(&full_range, &default_opts)
+            .unwrap();
+        assert_eq!(sizes[0], 0);
+
+        let mut opts = SizeApproximationOptions::default();
+        opts.set_include_memtables(true);
+        opts.set_files_size_error_margin(0.1);
+        let sizes = db.get_approximate_sizes_opt(&full_range, &opts).unwrap();
+        assert!(sizes[0] > 0);
+
+        opts.set_include_memtables(false);
+        opts.set_include_files(false);
+        assert!(db.get_approximate_sizes_opt(&full_range, &opts).is_err());
+    }
+
     #[test]
     fn property_test() {
         let path = tempdir_with_prefix("_rust_rocksdb_propertytest");
@@ -3259,6 +4746,91 @@ mod test {
         }
     }
 
+    #[test]
+    fn incremental_backup_test() {
+        let db_dir = tempdir_with_prefix("_rust_rocksdb_incremental_backup");
+        let db = DB::open_default(db_dir.path().to_str().unwrap()).unwrap();
+        let backup_dir = tempdir_with_prefix("_rust_rocksdb_incremental_backup_backup");
+        let backup_engine =
+            BackupEngine::open(DBOptions::new(), backup_dir.path().to_str().unwrap()).unwrap();
+        assert!(backup_engine.get_backup_info().is_empty());
+
+        db.put(b"k1", b"v1").unwrap();
+        backup_engine.create_new_backup_flush(&db, true).unwrap();
+        db.put(b"k2", b"v2").unwrap();
+        backup_engine.create_new_backup_flush(&db, true).unwrap();
+
+        let backups = backup_engine.get_backup_info();
+        let ids: Vec<_> = backups.iter().map(|b| b.backup_id).collect();
+        assert_eq!(ids, vec![1, 2]);
+        assert!(backups[0].timestamp <= backups[1].timestamp);
+        // The second backup shares the SST of the first one.
+        assert!(backups[1].num_files > backups[0].num_files);
+        assert!(backups[1].size > backups[0].size);
+
+        backup_engine.purge_old_backups(1).unwrap();
+        let backups = backup_engine.get_backup_info();
+        assert_eq!(backups.len(), 1);
+        assert_eq!(backups[0].backup_id, 2);
+
+        let restore_dir = tempdir_with_prefix("_rust_rocksdb_incremental_backup_restore");
+        let restore_path = restore_dir.path().to_str().unwrap();
+        let mut ropts = RestoreOptions::new();
+        ropts.set_keep_log_files(false);
+        let restored_db =
+            DB::restore_from(&backup_engine, restore_path, restore_path, &ropts).unwrap();
+        assert_eq!(restored_db.get(b"k1").unwrap().unwrap(), b"v1");
+        assert_eq!(restored_db.get(b"k2").unwrap().unwrap(), b"v2");
+    }
+
+    #[test]
+    fn verify_and_restore_backup_test() {
+        let db_dir = tempdir_with_prefix("_rust_rocksdb_verify_backup");
+        let db = DB::open_default(db_dir.path().to_str().unwrap()).unwrap();
+        let backup_dir = tempdir_with_prefix("_rust_rocksdb_verify_backup_backup");
+        let backup_engine =
+            BackupEngine::open(DBOptions::new(), backup_dir.path().to_str().unwrap()).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        backup_engine.create_new_backup_flush(&db, true).unwrap();
+        db.put(b"k2", b"v2").unwrap();
+        backup_engine.create_new_backup_flush(&db, true).unwrap();
+        backup_engine.verify_backup(1).unwrap();
+        backup_engine.verify_backup(2).unwrap();
+        assert!(backup_engine.verify_backup(3).is_err());
+
+        // Restore the older backup.
+        let restore_dir = tempdir_with_prefix("_rust_rocksdb_verify_backup_restore");
+        let restore_path = restore_dir.path().to_str().unwrap();
+        backup_engine
+            .restore_db_from_backup(1, restore_path, restore_path, &RestoreOptions::new())
+            .unwrap();
+        let restored_db = DB::open_default(restore_path).unwrap();
+        assert_eq!(restored_db.get(b"k1").unwrap().unwrap(), b"v1");
+        assert!(restored_db.get(b"k2").unwrap().is_none());
+        drop(restored_db);
+
+        // Corrupt the MANIFEST of the first backup without changing its size.
+        let private_dir = backup_dir.path().join("private").join("1");
+        let manifest = fs::read_dir(&private_dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .find(|p| {
+                p.file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .starts_with("MANIFEST")
+            })
+            .unwrap();
+        let mut data = fs::read(&manifest).unwrap();
+        for b in data.iter_mut().take(16) {
+            *b = !*b;
+        }
+        fs::write(&manifest, &data).unwrap();
+        let err = backup_engine.verify_backup(1).unwrap_err();
+        assert!(err.to_lowercase().contains("checksum"), "{}", err);
+        backup_engine.verify_backup(2).unwrap();
+    }
+
     #[test]
     fn log_dir_test() {
         let db_dir = tempdir_with_prefix("_rust_rocksdb_logdirtest");
@@ -3377,6 +4949,67 @@ mod test {
         assert!(DB::destroy(&opts, path).is_ok());
     }
 
+    #[test]
+    fn test_get_cf_traced() {
+        let path = tempdir_with_prefix("_rust_rocksdb_get_cf_traced");
+        let db = DB::open_default(path.path().to_str().unwrap()).unwrap();
+        let cf = db.cf_handle("default").unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        let mut fopts = FlushOptions::default();
+        fopts.set_wait(true);
+        db.flush_cf(cf, &fopts).unwrap();
+        db.put(b"k2", b"v2").unwrap();
+
+        let readopts = ReadOptions::new();
+        let (v, block_reads) = db.get_cf_traced(cf, b"k2", &readopts).unwrap();
+        assert_eq!(&*v.unwrap(), b"v2");
+        assert_eq!(block_reads, 0);
+
+        let (v, block_reads) = db.get_cf_traced(cf, b"k1", &readopts).unwrap();
+        assert_eq!(&*v.unwrap(), b"v1");
+        assert!(block_reads > 0);
+    }
+
+    #[test]
+    fn snapshot_overwrite_test() {
+        let path = tempdir_with_prefix("_rust_rocksdb_snapshot_overwrite");
+        let db = DB::open_default(path.path().to_str().unwrap()).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+
+        let snap = db.snapshot();
+        db.put(b"k1", b"v2").unwrap();
+        db.delete(b"k1").unwrap();
+        db.put(b"k1", b"v3").unwrap();
+
+        assert_eq!(snap.get(b"k1").unwrap().unwrap().to_utf8().unwrap(), "v1");
+        let mut readopts = ReadOptions::new();
+        readopts.set_fill_cache(false);
+        assert_eq!(
+            snap.get_opt(b"k1", readopts)
+                .unwrap()
+                .unwrap()
+                .to_utf8()
+                .unwrap(),
+            "v1"
+        );
+        assert_eq!(db.get(b"k1").unwrap().unwrap().to_utf8().unwrap(), "v3");
+
+        let readopts = snap.read_options(ReadOptions::new());
+        assert_eq!(
+            db.get_opt(b"k1", &readopts)
+                .unwrap()
+                .unwrap()
+                .to_utf8()
+                .unwrap(),
+            "v1"
+        );
+
+        let mut iter = snap.iter();
+        assert!(iter.seek(SeekKey::Start).unwrap());
+        assert_eq!(iter.key(), b"k1");
+        assert_eq!(iter.value(), b"v1");
+    }
+
     #[test]
     fn block_cache_usage() {
         let path = tempdir_with_prefix("_rust_rocksdb_block_cache_usage");
@@ -3397,6 +5030,48 @@ mod test {
         assert!(db.get_block_cache_usage_cf(cf_handle) > 0);
     }
 
+    #[test]
+    fn clock_cache_usage() {
+        let mut cache_opts = ClockCacheOptions::new();
+        cache_opts.set_capacity(8 * 1024 * 1024);
+        cache_opts.set_num_shard_bits(4);
+        let cache = match Cache::new_clock_cache(cache_opts) {
+            Ok(cache) => cache,
+            // Clock cache is only available when RocksDB is built with TBB.
+            Err(e) => {
+                assert_eq!(e, "clock cache is not supported");
+                return;
+            }
+        };
+        let path = tempdir_with_prefix("_rust_rocksdb_clock_cache_usage");
+        let mut opts = DBOptions::new();
+        opts.create_if_missing(true);
+        let mut block_opts = BlockBasedOptions::new();
+        block_opts.set_block_cache(&cache);
+        let mut cf_opts = ColumnFamilyOptions::new();
+        cf_opts.set_block_based_table_factory(&block_opts);
+        let db = DB::open_cf(
+            opts,
+            path.path().to_str().unwrap(),
+            vec![("default", cf_opts)],
+        )
+        .unwrap();
+
+        for i in 0..200 {
+            db.put(format!("k_{}", i).as_bytes(), b"v").unwrap();
+        }
+        let mut fopts = FlushOptions::default();
+        fopts.set_wait(true);
+        db.flush(&fopts).unwrap();
+        for i in 0..200 {
+            assert_eq!(
+                db.get(format!("k_{}", i).as_bytes()).unwrap().unwrap(),
+                b"v"
+            );
+        }
+        assert!(db.get_block_cache_usage() > 0);
+    }
+
     #[test]
     fn flush_cf() {
         let path = tempdir_with_prefix("_rust_rocksdb_flush_cf");
@@ -3420,6 +5095,64 @@ mod test {
         assert!(total_sst_files_size > 0);
     }
 
+    #[test]
+    fn flush_cf_subset() {
+        let path = tempdir_with_prefix("_rust_rocksdb_flush_cf_subset");
+        let mut opts = DBOptions::new();
+        opts.create_if_missing(true);
+        let mut db = DB::open(opts, path.path().to_str().unwrap()).unwrap();
+        db.create_cf("cf1").unwrap();
+        db.create_cf("cf2").unwrap();
+        for name in &["cf1", "cf2"] {
+            let cf_handle = db.cf_handle(name).unwrap();
+            db.put_cf(cf_handle, b"k", b"v").unwrap();
+        }
+
+        let cf1 = db.cf_handle("cf1").unwrap();
+        let cf2 = db.cf_handle("cf2").unwrap();
+        let mut fopts = FlushOptions::default();
+        fopts.set_wait(true);
+        fopts.set_allow_write_stall(true);
+        db.flush_cfs(&[cf1], &fopts).unwrap();
+        assert_eq!(
+            db.get_property_int_cf(cf1, "rocksdb.num-immutable-mem-table")
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            db.get_property_int_cf(cf1, "rocksdb.num-entries-active-mem-table")
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            db.get_property_int_cf(cf2, "rocksdb.num-entries-active-mem-table")
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            db.get_property_int_cf(cf2, "rocksdb.num-files-at-level0")
+                .unwrap(),
+            0
+        );
+
+        // A non-waiting flush only schedules the job.
+        let mut fopts = FlushOptions::default();
+        fopts.set_wait(false);
+        db.flush_cf(cf2, &fopts).unwrap();
+        fopts.set_wait(true);
+        db.flush_cf(cf2, &fopts).unwrap();
+        assert_eq!(
+            db.get_property_int_cf(cf2, "rocksdb.num-immutable-mem-table")
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            db.get_property_int_cf(cf2, "rocksdb.num-files-at-level0")
+                .unwrap(),
+            1
+        );
+    }
+
     #[test]
     fn test_supported_compression() {
         let mut com = supported_compression();
@@ -3527,6 +5260,56 @@ mod test {
         assert_eq!(cf_opts.get_disable_auto_compactions(), true);
     }
 
+    #[test]
+    fn test_set_options_toggle_auto_compactions() {
+        let path = tempdir_with_prefix("_rust_rocksdb_set_options_toggle_auto_compactions");
+        let mut opts = DBOptions::new();
+        opts.create_if_missing(true);
+        let mut cf_opts = ColumnFamilyOptions::new();
+        cf_opts.set_level_zero_file_num_compaction_trigger(2);
+        let db = DB::open_cf(
+            opts,
+            path.path().to_str().unwrap(),
+            vec![("default", cf_opts)],
+        )
+        .unwrap();
+        let cf = db.cf_handle("default").unwrap();
+
+        let err = db
+            .set_options_cf(cf, &[("no_such_option", "1")])
+            .unwrap_err();
+        assert!(err.contains("no_such_option"), "{}", err);
+        assert!(db
+            .set_options_cf(cf, &[("disable_auto_compactions", "maybe")])
+            .is_err());
+        assert!(db.set_db_options(&[("max_background_jobs", "x")]).is_err());
+
+        db.set_options_cf(cf, &[("disable_auto_compactions", "true")])
+            .unwrap();
+        let mut fopts = FlushOptions::default();
+        fopts.set_wait(true);
+        for i in 0..4 {
+            db.put(format!("k{}", i).as_bytes(), b"v").unwrap();
+            db.flush_cf(cf, &fopts).unwrap();
+        }
+        // The compaction stays pending, so there is nothing to wait for.
+        thread::sleep(Duration::from_millis(200));
+        assert_eq!(
+            db.get_property_int_cf(cf, "rocksdb.num-files-at-level0"),
+            Some(4)
+        );
+
+        db.set_options_cf(cf, &[("disable_auto_compactions", "false")])
+            .unwrap();
+        db.wait_for_compact(false, Some(Duration::from_secs(30)))
+            .unwrap();
+        assert!(
+            db.get_property_int_cf(cf, "rocksdb.num-files-at-level0")
+                .unwrap()
+                < 2
+        );
+    }
+
     #[test]
     fn test_load_latest_options() {
         let path = tempdir_with_prefix("_rust_rocksdb_load_latest_option");
@@ -3580,6 +5363,29 @@ mod test {
         assert!(seq2 > seq1);
     }
 
+    #[test]
+    fn test_sequence_number_advances_by_batch_count() {
+        let path = tempdir_with_prefix("_rust_rocksdb_sequence_number_advances_by_batch_count");
+        let mut opts = DBOptions::new();
+        opts.create_if_missing(true);
+        let db = DB::open(opts, path.path().to_str().unwrap()).unwrap();
+
+        let mut last = db.get_latest_sequence_number();
+        for n in 1..5 {
+            let wb = WriteBatch::new();
+            for i in 0..n {
+                wb.put(format!("k{}", i).as_bytes(), b"v").unwrap();
+            }
+            wb.delete(b"k0").unwrap();
+            db.write(&wb).unwrap();
+            let seq = db.get_latest_sequence_number();
+            assert_eq!(seq, last + wb.count() as u64);
+            let snap = db.snapshot();
+            assert_eq!(snap.get_sequence_number(), seq);
+            last = seq;
+        }
+    }
+
     #[test]
     fn test_atomic_flush() {
         let path = tempdir_with_prefix("_rust_rocksdb_test_atomic_flush");
@@ -3638,6 +5444,164 @@ mod test {
         assert!(mp.is_some());
     }
 
+    #[test]
+    fn test_level_stats_cf() {
+        let path = tempdir_with_prefix("_rust_rocksdb_level_stats_cf");
+        let mut opts = DBOptions::new();
+        opts.create_if_missing(true);
+        let mut cf_opts = ColumnFamilyOptions::new();
+        cf_opts.set_disable_auto_compactions(true);
+        let db = DB::open_cf(
+            opts,
+            path.path().to_str().unwrap(),
+            vec![("default", cf_opts)],
+        )
+        .unwrap();
+        let cf = db.cf_handle("default").unwrap();
+        let mut fopts = FlushOptions::default();
+        fopts.set_wait(true);
+        for i in 0..3 {
+            db.put(format!("k{}", i).as_bytes(), b"v").unwrap();
+            db.flush_cf(cf, &fopts).unwrap();
+        }
+
+        let stats = db.level_stats_cf(cf);
+        assert!(stats.contains("Level"), "{}", stats);
+        // Each level is a row of "<level> <files> <size>".
+        let l0_files = stats
+            .lines()
+            .map(|l| l.split_whitespace().collect::<Vec<_>>())
+            .find(|cols| cols.first() == Some(&"0"))
+            .map(|cols| cols[1].to_owned())
+            .unwrap();
+        assert_eq!(l0_files, "3");
+    }
+
+    #[test]
+    fn test_sstables_cf() {
+        let path = tempdir_with_prefix("_rust_rocksdb_sstables_cf");
+        let db = DB::open_default(path.path().to_str().unwrap()).unwrap();
+        let cf = db.cf_handle("default").unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        let mut fopts = FlushOptions::default();
+        fopts.set_wait(true);
+        db.flush_cf(cf, &fopts).unwrap();
+
+        let sstables = db.sstables_cf(cf);
+        assert!(sstables.contains("level 0"), "{}", sstables);
+        // Files are listed by number, e.g. "7:1024[1 .. 1]['k1' ...]".
+        let meta = db.get_column_family_meta_data(cf);
+        let name = meta.get_levels()[0].get_files()[0].get_name();
+        let number: u64 = name
+            .trim_start_matches('/')
+            .trim_end_matches(".sst")
+            .parse()
+            .unwrap();
+        assert!(sstables.contains(&format!("{}:", number)), "{}", sstables);
+    }
+
+    #[test]
+    fn test_cf_write_stall_stats() {
+        let path = tempdir_with_prefix("_rust_rocksdb_cf_write_stall_stats");
+        let mut opts = DBOptions::new();
+        opts.create_if_missing(true);
+        let mut cf_opts = ColumnFamilyOptions::new();
+        cf_opts
+            .set_level_zero_file_num_compaction_trigger(2)
+            .set_level_zero_slowdown_writes_trigger(2)
+            .set_level_zero_stop_writes_trigger(100);
+        let db = DB::open_cf(
+            opts,
+            path.path().to_str().unwrap(),
+            vec![("default", cf_opts)],
+        )
+        .unwrap();
+        let cf = db.cf_handle("default").unwrap();
+        assert_eq!(
+            db.get_cf_write_stall_stats(cf).unwrap(),
+            WriteStallStats::default()
+        );
+
+        let mut fopts = FlushOptions::default();
+        fopts.set_wait(true);
+        for i in 0..4 {
+            db.put(format!("k{}", i).as_bytes(), b"v").unwrap();
+            db.flush_cf(cf, &fopts).unwrap();
+        }
+        let stats = db.get_cf_write_stall_stats(cf).unwrap();
+        assert!(stats.level0_slowdown > 0);
+        assert!(stats.total_slowdown >= stats.level0_slowdown);
+    }
+
+    #[test]
+    fn test_wait_for_compact() {
+        let path = tempdir_with_prefix("_rust_rocksdb_wait_for_compact");
+        let mut opts = DBOptions::new();
+        opts.create_if_missing(true);
+        let mut cf_opts = ColumnFamilyOptions::new();
+        cf_opts.set_level_zero_file_num_compaction_trigger(4);
+        let db = DB::open_cf(
+            opts,
+            path.path().to_str().unwrap(),
+            vec![("default", cf_opts)],
+        )
+        .unwrap();
+        let mut fopts = FlushOptions::default();
+        fopts.set_wait(true);
+        for i in 0..8 {
+            for j in 0..100 {
+                db.put(
+                    format!("k{:03}", j).as_bytes(),
+                    format!("v{}", i).as_bytes(),
+                )
+                .unwrap();
+            }
+            db.flush(&fopts).unwrap();
+        }
+        // Unflushed data is flushed before waiting.
+        db.put(b"k100", b"v").unwrap();
+
+        db.wait_for_compact(true, Some(Duration::from_secs(30)))
+            .unwrap();
+        assert_eq!(
+            db.get_property_int("rocksdb.num-running-compactions"),
+            Some(0)
+        );
+        assert!(db.get_property_int("rocksdb.num-files-at-level0").unwrap() < 4);
+        assert_eq!(
+            db.get_property_int("rocksdb.num-entries-active-mem-table"),
+            Some(0)
+        );
+        assert_eq!(db.get(b"k000").unwrap().unwrap(), b"v7");
+    }
+
+    #[test]
+    fn test_wait_for_compact_auto_compactions_disabled() {
+        let path = tempdir_with_prefix("_rust_rocksdb_wait_for_compact_disabled");
+        let mut opts = DBOptions::new();
+        opts.create_if_missing(true);
+        let mut cf_opts = ColumnFamilyOptions::new();
+        cf_opts.set_level_zero_file_num_compaction_trigger(2);
+        cf_opts.set_disable_auto_compactions(true);
+        let db = DB::open_cf(
+            opts,
+            path.path().to_str().unwrap(),
+            vec![("default", cf_opts)],
+        )
+        .unwrap();
+        let mut fopts = FlushOptions::default();
+        fopts.set_wait(true);
+        for i in 0..4 {
+            db.put(format!("k{}", i).as_bytes(), b"v").unwrap();
+            db.flush(&fopts).unwrap();
+        }
+        assert_eq!(db.get_property_int("rocksdb.compaction-pending"), Some(1));
+
+        // Returns instead of waiting for a compaction that never runs.
+        db.wait_for_compact(true, None).unwrap();
+        assert_eq!(db.get_property_int("rocksdb.num-files-at-level0"), Some(4));
+    }
+
     #[test]
     fn test_multi_batch_write() {
         let mut opts = DBOptions::new();
@@ -3701,6 +5665,66 @@ mod test {
         }
     }
 
+    fn counter_merge(
+        _: &[u8],
+        existing_val: Option<&[u8]>,
+        operands: &mut MergeOperands,
+    ) -> Vec<u8> {
+        let mut counter = existing_val.map_or(0, |v| {
+            let mut buf = [0; 8];
+            buf.copy_from_slice(v);
+            u64::from_le_bytes(buf)
+        });
+        for op in operands {
+            let mut buf = [0; 8];
+            buf.copy_from_slice(op);
+            counter += u64::from_le_bytes(buf);
+        }
+        counter.to_le_bytes().to_vec()
+    }
+
+    #[test]
+    fn test_write_batch_merge_and_append_batch() {
+        let path = tempdir_with_prefix("_rust_rocksdb_write_batch_merge");
+        let mut opts = DBOptions::new();
+        opts.create_if_missing(true);
+        let mut cf_opts = ColumnFamilyOptions::new();
+        cf_opts.add_merge_operator("counter", counter_merge);
+        let db = DB::open_cf(
+            opts,
+            path.path().to_str().unwrap(),
+            vec![("default", cf_opts)],
+        )
+        .unwrap();
+        let cf = db.cf_handle("default").unwrap();
+
+        let mut wb = WriteBatch::new();
+        for i in 1..=3u64 {
+            let shard = WriteBatch::new();
+            shard.merge(b"counter", &i.to_le_bytes()).unwrap();
+            shard.merge_cf(cf, b"counter", &i.to_le_bytes()).unwrap();
+            wb.append_batch(&shard);
+        }
+        assert_eq!(wb.count(), 6);
+        db.write(&wb).unwrap();
+        let v = db.get(b"counter").unwrap().unwrap();
+        assert_eq!(&*v, &12u64.to_le_bytes());
+
+        // Operations from later batches must be applied after earlier ones.
+        let mut wb = WriteBatch::new();
+        let first = WriteBatch::new();
+        first.put(b"k1", b"v1").unwrap();
+        first.delete(b"k2").unwrap();
+        let second = WriteBatch::new();
+        second.delete(b"k1").unwrap();
+        second.put(b"k2", b"v2").unwrap();
+        wb.append_batch(&first);
+        wb.append_batch(&second);
+        db.write(&wb).unwrap();
+        assert!(db.get(b"k1").unwrap().is_none());
+        assert_eq!(&*db.get(b"k2").unwrap().unwrap(), b"v2");
+    }
+
     fn inner_test_write_batch_iter<F>(iter_fn: F)
     where
         F: FnOnce(&DB, &mut WriteBatch),
@@ -3763,6 +5787,77 @@ mod test {
         });
     }
 
+    #[derive(Default)]
+    struct CountingHandler {
+        puts: Vec<(u32, Vec<u8>, Vec<u8>)>,
+        deletes: usize,
+        single_deletes: usize,
+        merges: usize,
+        delete_ranges: Vec<(Vec<u8>, Vec<u8>)>,
+    }
+
+    impl WriteBatchHandler for CountingHandler {
+        fn put(&mut self, cf: u32, key: &[u8], value: &[u8]) {
+            self.puts.push((cf, key.to_vec(), value.to_vec()));
+        }
+        fn delete(&mut self, _: u32, _: &[u8]) {
+            self.deletes += 1;
+        }
+        fn single_delete(&mut self, _: u32, _: &[u8]) {
+            self.single_deletes += 1;
+        }
+        fn merge(&mut self, _: u32, _: &[u8], _: &[u8]) {
+            self.merges += 1;
+        }
+        fn delete_range(&mut self, _: u32, begin_key: &[u8], end_key: &[u8]) {
+            self.delete_ranges
+                .push((begin_key.to_vec(), end_key.to_vec()));
+        }
+    }
+
+    #[test]
+    fn test_write_batch_iterate_with_handler() {
+        let path = tempdir_with_prefix("_rust_rocksdb_write_batch_iterate_with_handler");
+        let mut opts = DBOptions::new();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(
+            opts,
+            path.path().to_str().unwrap(),
+            vec![
+                ("default", ColumnFamilyOptions::new()),
+                ("cf1", ColumnFamilyOptions::new()),
+            ],
+        )
+        .unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+
+        let wb = WriteBatch::new();
+        wb.put(b"k1", b"v1").unwrap();
+        wb.put_cf(cf1, b"k2", b"v2").unwrap();
+        wb.delete(b"k3").unwrap();
+        wb.delete_cf(cf1, b"k4").unwrap();
+        wb.single_delete(b"k5").unwrap();
+        wb.merge_cf(cf1, b"k6", b"v6").unwrap();
+        wb.merge(b"k7", b"v7").unwrap();
+        wb.delete_range_cf(cf1, b"a", b"b").unwrap();
+
+        let mut handler = CountingHandler::default();
+        wb.iterate_with_handler(&mut handler).unwrap();
+        assert_eq!(
+            handler.puts,
+            vec![
+                (0, b"k1".to_vec(), b"v1".to_vec()),
+                (cf1.id(), b"k2".to_vec(), b"v2".to_vec()),
+            ]
+        );
+        assert_eq!(handler.deletes, 2);
+        assert_eq!(handler.single_deletes, 1);
+        assert_eq!(handler.merges, 2);
+        assert_eq!(handler.delete_ranges, vec![(b"a".to_vec(), b"b".to_vec())]);
+        assert_eq!(wb.count(), 8);
+    }
+
     #[test]
     fn test_write_batch_ref_iter() {
         inner_test_write_batch_iter(|db, wb| {
@@ -3995,4 +6090,254 @@ mod test {
             10
         );
     }
+
+    #[test]
+    fn test_db_path() {
+        let path_dir = tempdir_with_prefix("_rust_rocksdb_db_path");
+        let path = path_dir.path().to_str().unwrap();
+        {
+            let db = DB::open_default(path).unwrap();
+            assert_eq!(db.path(), path);
+        }
+        let db = DB::open_for_read_only(DBOptions::new(), path, false).unwrap();
+        assert_eq!(db.path(), path);
+    }
+
+    #[test]
+    fn test_get_cf_cache_only() {
+        let path_dir = tempdir_with_prefix("_rust_rocksdb_get_cf_cache_only");
+        let db = DB::open_default(path_dir.path().to_str().unwrap()).unwrap();
+        let cf = db.cf_handle("default").unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        // Served from the memtable.
+        assert_eq!(db.get_cf_cache_only(cf, b"k1").unwrap().unwrap(), b"v1");
+
+        let mut fopts = FlushOptions::default();
+        fopts.set_wait(true);
+        db.flush_cf(cf, &fopts).unwrap();
+        assert!(db.get_cf_cache_only(cf, b"k1").unwrap().is_none());
+        assert!(db.get_cf_cache_only(cf, b"k2").unwrap().is_none());
+
+        // A normal read loads the data block into the block cache.
+        assert_eq!(db.get_cf(cf, b"k1").unwrap().unwrap(), b"v1");
+        assert_eq!(db.get_cf_cache_only(cf, b"k1").unwrap().unwrap(), b"v1");
+    }
+
+    #[test]
+    fn test_key_may_exist_cf() {
+        let path_dir = tempdir_with_prefix("_rust_rocksdb_key_may_exist_cf");
+        let mut opts = DBOptions::new();
+        opts.create_if_missing(true);
+        let mut block_opts = BlockBasedOptions::new();
+        block_opts.set_bloom_filter(10.0, false);
+        let mut cf_opts = ColumnFamilyOptions::new();
+        cf_opts.set_block_based_table_factory(&block_opts);
+        let db = DB::open_cf(
+            opts,
+            path_dir.path().to_str().unwrap(),
+            vec![("default", cf_opts)],
+        )
+        .unwrap();
+        let cf = db.cf_handle("default").unwrap();
+        let readopts = ReadOptions::new();
+        for i in 0..100 {
+            db.put(format!("k{:03}", i).as_bytes(), b"v").unwrap();
+        }
+        // Keys in the memtable come back with their values.
+        let (may_exist, value) = db.key_may_exist_cf(cf, b"k001", &readopts);
+        assert!(may_exist);
+        assert_eq!(value.unwrap(), b"v");
+        let (may_exist, value) = db.key_may_exist_cf(cf, b"k001x", &readopts);
+        assert!(!may_exist);
+        assert!(value.is_none());
+
+        let mut fopts = FlushOptions::default();
+        fopts.set_wait(true);
+        db.flush_cf(cf, &fopts).unwrap();
+        for i in 0..100 {
+            let key = format!("k{:03}", i);
+            assert!(db.key_may_exist_cf(cf, key.as_bytes(), &readopts).0);
+        }
+        // Keys outside of any file are definitely absent, and the bloom
+        // filter rules out most of the others.
+        assert!(!db.key_may_exist_cf(cf, b"z", &readopts).0);
+        let absent = (0..100)
+            .filter(|i| {
+                let key = format!("k{:03}x", i);
+                !db.key_may_exist_cf(cf, key.as_bytes(), &readopts).0
+            })
+            .count();
+        assert!(absent > 90, "{}", absent);
+    }
+
+    #[test]
+    fn test_scan_cf() {
+        let path_dir = tempdir_with_prefix("_rust_rocksdb_scan_cf");
+        let db = DB::open_default(path_dir.path().to_str().unwrap()).unwrap();
+        let cf = db.cf_handle("default").unwrap();
+        for i in 1..=5 {
+            let k = format!("k{}", i);
+            db.put(k.as_bytes(), k.as_bytes()).unwrap();
+        }
+
+        let mut scanned = vec![];
+        db.scan_cf(cf, b"k2", b"k5", ReadOptions::new(), |k, v| {
+            assert_eq!(k, v);
+            scanned.push(k.to_vec());
+            true
+        })
+        .unwrap();
+        assert_eq!(
+            scanned,
+            vec![b"k2".to_vec(), b"k3".to_vec(), b"k4".to_vec()]
+        );
+
+        let mut calls = 0;
+        db.scan_cf(cf, b"k1", b"k9", ReadOptions::new(), |_, _| {
+            calls += 1;
+            calls < 2
+        })
+        .unwrap();
+        assert_eq!(calls, 2);
+
+        db.scan_cf(cf, b"k6", b"k9", ReadOptions::new(), |_, _| {
+            panic!("no entry in range")
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn test_scan_cf_limit() {
+        let path_dir = tempdir_with_prefix("_rust_rocksdb_scan_cf_limit");
+        let db = DB::open_default(path_dir.path().to_str().unwrap()).unwrap();
+        let cf = db.cf_handle("default").unwrap();
+        for i in 1..=5 {
+            let k = format!("k{}", i);
+            db.put(k.as_bytes(), k.as_bytes()).unwrap();
+        }
+        let keys = |entries: Vec<(Vec<u8>, Vec<u8>)>| -> Vec<Vec<u8>> {
+            entries
+                .into_iter()
+                .map(|(k, v)| {
+                    assert_eq!(k, v);
+                    k
+                })
+                .collect()
+        };
+
+        // Stops at the limit.
+        let entries = db
+            .scan_cf_limit(cf, SeekKey::Key(b"k2"), None, 2, ReadOptions::new())
+            .unwrap();
+        assert_eq!(keys(entries), vec![b"k2".to_vec(), b"k3".to_vec()]);
+        // Stops at the end, which is exclusive.
+        let entries = db
+            .scan_cf_limit(cf, SeekKey::Start, Some(b"k3"), 10, ReadOptions::new())
+            .unwrap();
+        assert_eq!(keys(entries), vec![b"k1".to_vec(), b"k2".to_vec()]);
+        let entries = db
+            .scan_cf_limit(cf, SeekKey::Key(b"k4"), Some(b"k9"), 10, ReadOptions::new())
+            .unwrap();
+        assert_eq!(keys(entries), vec![b"k4".to_vec(), b"k5".to_vec()]);
+
+        assert!(db
+            .scan_cf_limit(cf, SeekKey::Key(b"k6"), None, 10, ReadOptions::new())
+            .unwrap()
+            .is_empty());
+        assert!(db
+            .scan_cf_limit(cf, SeekKey::Key(b"k3"), Some(b"k3"), 10, ReadOptions::new())
+            .unwrap()
+            .is_empty());
+        assert!(db
+            .scan_cf_limit(cf, SeekKey::Start, None, 0, ReadOptions::new())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_put_cf_returning_seq() {
+        let path_dir = tempdir_with_prefix("_rust_rocksdb_put_cf_returning_seq");
+        let db = DB::open_default(path_dir.path().to_str().unwrap()).unwrap();
+        let cf = db.cf_handle("default").unwrap();
+        let wopts = WriteOptions::new();
+        let seq1 = db.put_cf_returning_seq(cf, b"k1", b"v1", &wopts).unwrap();
+        let seq2 = db.put_cf_returning_seq(cf, b"k2", b"v2", &wopts).unwrap();
+        assert!(seq1 > 0);
+        assert!(seq2 > seq1);
+        assert_eq!(seq2, db.get_latest_sequence_number());
+        assert_eq!(db.get_cf(cf, b"k1").unwrap().unwrap(), b"v1");
+        assert_eq!(db.get_cf(cf, b"k2").unwrap().unwrap(), b"v2");
+    }
+
+    // Looks up `keys` in `cf`, returning the result of each key in order.
+    fn multi_get_cf_opt(
+        db: &DB,
+        cf: &CFHandle,
+        keys: &[&[u8]],
+        readopts: &ReadOptions,
+    ) -> Vec<Result<Option<Vec<u8>>, String>> {
+        let n = keys.len();
+        let cfs: Vec<_> = keys.iter().map(|_| cf.inner as *const DBCFHandle).collect();
+        let keys_list: Vec<_> = keys.iter().map(|k| k.as_ptr()).collect();
+        let keys_list_sizes: Vec<_> = keys.iter().map(|k| k.len() as size_t).collect();
+        let mut values_list = vec![ptr::null_mut(); n];
+        let mut values_list_sizes = vec![0; n];
+        let mut errs = vec![ptr::null_mut(); n];
+        unsafe {
+            crocksdb_ffi::crocksdb_multi_get_cf(
+                db.inner,
+                readopts.get_inner(),
+                cfs.as_ptr(),
+                n as size_t,
+                keys_list.as_ptr(),
+                keys_list_sizes.as_ptr(),
+                values_list.as_mut_ptr(),
+                values_list_sizes.as_mut_ptr(),
+                errs.as_mut_ptr(),
+            );
+            (0..n)
+                .map(|i| {
+                    if !errs[i].is_null() {
+                        return Err(crocksdb_ffi::error_message(errs[i]));
+                    }
+                    let v = values_list[i];
+                    if v.is_null() {
+                        return Ok(None);
+                    }
+                    let value = slice::from_raw_parts(v, values_list_sizes[i]).to_vec();
+                    libc::free(v as *mut c_void);
+                    Ok(Some(value))
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_read_options_value_size_soft_limit() {
+        let path = tempdir_with_prefix("_rust_rocksdb_value_size_soft_limit");
+        let db = DB::open_default(path.path().to_str().unwrap()).unwrap();
+        let value = vec![b'v'; 1024];
+        let keys: Vec<&[u8]> = vec![b"k1", b"k2", b"k3", b"k4"];
+        for k in &keys {
+            db.put(k, &value).unwrap();
+        }
+        let cf = db.cf_handle("default").unwrap();
+
+        let res = multi_get_cf_opt(&db, cf, &keys, &ReadOptions::new());
+        assert!(res
+            .iter()
+            .all(|r| r.as_ref().unwrap().as_ref().unwrap() == &value));
+
+        let mut read_opts = ReadOptions::new();
+        read_opts.set_value_size_soft_limit(1500);
+        let res = multi_get_cf_opt(&db, cf, &keys, &read_opts);
+        // The limit is checked after each value is read, so the key that
+        // crosses it is still returned.
+        assert_eq!(res[0].as_ref().unwrap().as_ref().unwrap(), &value);
+        assert_eq!(res[1].as_ref().unwrap().as_ref().unwrap(), &value);
+        for r in &res[2..] {
+            let e = r.as_ref().unwrap_err();
+            assert!(e.to_lowercase().contains("aborted"), "{}", e);
+        }
+    }
 }
@@ -22,11 +22,13 @@ use crocksdb_ffi::{
     self, ChecksumType, DBBlockBasedTableOptions, DBBottommostLevelCompaction, DBCompactOptions,
     DBCompactionOptions, DBCompressionType, DBConcurrentTaskLimiter, DBFifoCompactionOptions,
     DBFlushOptions, DBInfoLogLevel, DBInstance, DBLRUCacheOptions, DBRateLimiter,
-    DBRateLimiterMode, DBReadOptions, DBRecoveryMode, DBRestoreOptions, DBSnapshot, DBStatistics,
-    DBStatisticsHistogramType, DBStatisticsTickerType, DBTitanDBOptions, DBTitanReadOptions,
-    DBWriteBufferManager, DBWriteOptions, IndexType, Options, PrepopulateBlockCache,
+    DBRateLimiterMode, DBReadOptions, DBRecoveryMode, DBRestoreOptions, DBSizeApproximationOptions,
+    DBSnapshot, DBStatistics, DBStatisticsHistogramType, DBStatisticsTickerType, DBTitanDBOptions,
+    DBTitanReadOptions, DBWriteBufferManager, DBWriteOptions, IndexType, Options,
+    PrepopulateBlockCache, ReadTier,
 };
 use event_listener::{new_event_listener, EventListener};
+use file_system::FileSystemInspector;
 use libc::{self, c_double, c_int, c_uchar, c_void, size_t};
 use logger::{new_logger, Logger};
 use merge_operator::MergeFn;
@@ -44,6 +46,7 @@ use table_properties_collector_factory::{
     new_table_properties_collector_factory, TablePropertiesCollectorFactory,
 };
 use titan::TitanDBOptions;
+use wal_filter::{new_wal_filter, WalFilter, WalFilterHandle};
 use TablePropertiesCollector;
 
 #[derive(Default, Debug)]
@@ -110,6 +113,17 @@ impl BlockBasedOptions {
         }
     }
 
+    /// Sets a cache for compressed blocks, used as a second tier behind the
+    /// uncompressed block cache.
+    pub fn set_block_cache_compressed(&mut self, cache: &Cache) {
+        unsafe {
+            crocksdb_ffi::crocksdb_block_based_options_set_block_cache_compressed(
+                self.inner,
+                cache.inner,
+            );
+        }
+    }
+
     pub fn set_no_block_cache(&mut self, v: bool) {
         unsafe {
             crocksdb_ffi::crocksdb_block_based_options_set_no_block_cache(self.inner, v);
@@ -464,6 +478,27 @@ impl WriteBufferManager {
         }
     }
 
+    /// Creates a write buffer manager whose memtable memory is also charged
+    /// to `cache`, so that memtables and cached blocks share one memory
+    /// budget.
+    pub fn new_with_cache(
+        flush_size: usize,
+        cache: &Cache,
+        stall_ratio: f32,
+        flush_oldest_first: bool,
+    ) -> Self {
+        unsafe {
+            Self {
+                inner: crocksdb_ffi::crocksdb_write_buffer_manager_create_with_cache(
+                    flush_size,
+                    cache.inner,
+                    stall_ratio,
+                    flush_oldest_first,
+                ),
+            }
+        }
+    }
+
     pub fn set_flush_size(&self, s: usize) {
         unsafe {
             crocksdb_ffi::crocksdb_write_buffer_manager_set_flush_size(self.inner, s);
@@ -474,6 +509,11 @@ impl WriteBufferManager {
         unsafe { crocksdb_ffi::crocksdb_write_buffer_manager_flush_size(self.inner) }
     }
 
+    /// Returns the total memtable memory limit, same as `flush_size`.
+    pub fn buffer_size(&self) -> usize {
+        self.flush_size()
+    }
+
     pub fn set_flush_oldest_first(&self, f: bool) {
         unsafe {
             crocksdb_ffi::crocksdb_write_buffer_manager_set_flush_oldest_first(self.inner, f);
@@ -555,6 +595,8 @@ pub struct ReadOptions {
     inner: *mut DBReadOptions,
     lower_bound: Vec<u8>,
     upper_bound: Vec<u8>,
+    timestamp: Vec<u8>,
+    iter_start_ts: Vec<u8>,
     titan_inner: *mut DBTitanReadOptions,
 }
 
@@ -578,6 +620,8 @@ impl Default for ReadOptions {
                 inner: opts,
                 lower_bound: vec![],
                 upper_bound: vec![],
+                timestamp: vec![],
+                iter_start_ts: vec![],
                 titan_inner: ptr::null_mut::<DBTitanReadOptions>(),
             }
         }
@@ -652,7 +696,61 @@ impl ReadOptions {
         &self.upper_bound
     }
 
-    pub fn set_read_tier(&mut self, tier: c_int) {
+    /// Reads the latest versions whose timestamps are not newer than `ts`,
+    /// only valid for column families using user timestamps.
+    pub fn set_timestamp(&mut self, ts: Vec<u8>) {
+        self.timestamp = ts;
+        unsafe {
+            crocksdb_ffi::crocksdb_readoptions_set_timestamp(
+                self.inner,
+                self.timestamp.as_ptr(),
+                self.timestamp.len(),
+            );
+        }
+    }
+
+    // Overwrites the timestamp set by `set_timestamp` in place, so iterators
+    // created with these options, which point to it, read at `ts` from now on.
+    pub(crate) fn update_timestamp(&mut self, ts: &[u8]) -> Result<(), String> {
+        if ts.len() != self.timestamp.len() {
+            return Err(format!(
+                "timestamp size mismatch: the iterator reads at {}-byte timestamps, got {}",
+                self.timestamp.len(),
+                ts.len()
+            ));
+        }
+        self.timestamp.copy_from_slice(ts);
+        Ok(())
+    }
+
+    /// Same as `set_timestamp`, but doesn't copy `ts`.
+    ///
+    /// # Safety
+    ///
+    /// `ts` must stay alive and unmodified until this ReadOptions is dropped
+    /// or its timestamp is replaced by another call to `set_timestamp` or
+    /// `set_timestamp_slice`.
+    pub unsafe fn set_timestamp_slice(&mut self, ts: &[u8]) {
+        self.timestamp.clear();
+        crocksdb_ffi::crocksdb_readoptions_set_timestamp(self.inner, ts.as_ptr(), ts.len());
+    }
+
+    /// Makes reads return all the versions of a key with a timestamp between
+    /// `ts` and the one set by `set_timestamp`, instead of only the latest.
+    /// Iterators created with it return internal keys, tombstones included,
+    /// use `DB::get_versions_cf_opt` to read the versions of a key.
+    pub fn set_iter_start_ts(&mut self, ts: Vec<u8>) {
+        self.iter_start_ts = ts;
+        unsafe {
+            crocksdb_ffi::crocksdb_readoptions_set_iter_start_ts(
+                self.inner,
+                self.iter_start_ts.as_ptr(),
+                self.iter_start_ts.len(),
+            );
+        }
+    }
+
+    pub fn set_read_tier(&mut self, tier: ReadTier) {
         unsafe {
             crocksdb_ffi::crocksdb_readoptions_set_read_tier(self.inner, tier);
         }
@@ -682,6 +780,14 @@ impl ReadOptions {
         }
     }
 
+    /// Once the accumulated size of values read by a multi-get exceeds `v`,
+    /// the remaining keys are not looked up and fail with an error instead.
+    pub fn set_value_size_soft_limit(&mut self, v: u64) {
+        unsafe {
+            crocksdb_ffi::crocksdb_readoptions_set_value_size_soft_limit(self.inner, v);
+        }
+    }
+
     pub fn set_total_order_seek(&mut self, v: bool) {
         unsafe {
             crocksdb_ffi::crocksdb_readoptions_set_total_order_seek(self.inner, v);
@@ -747,6 +853,7 @@ impl ReadOptions {
 
 pub struct WriteOptions {
     pub(crate) inner: *mut DBWriteOptions,
+    timestamp: Vec<u8>,
 }
 
 impl Drop for WriteOptions {
@@ -764,7 +871,10 @@ impl Default for WriteOptions {
             !write_opts.is_null(),
             "Could not create rocksdb write options"
         );
-        WriteOptions { inner: write_opts }
+        WriteOptions {
+            inner: write_opts,
+            timestamp: vec![],
+        }
     }
 }
 
@@ -812,6 +922,19 @@ impl WriteOptions {
             crocksdb_ffi::crocksdb_writeoptions_set_memtable_insert_hint_per_batch(self.inner, v);
         }
     }
+
+    /// Writes with the user timestamp `ts`, only valid for column families
+    /// using user timestamps.
+    pub fn set_timestamp(&mut self, ts: Vec<u8>) {
+        self.timestamp = ts;
+        unsafe {
+            crocksdb_ffi::crocksdb_writeoptions_set_timestamp(
+                self.inner,
+                self.timestamp.as_ptr(),
+                self.timestamp.len(),
+            );
+        }
+    }
 }
 
 pub struct CompactOptions {
@@ -915,6 +1038,7 @@ impl Drop for CompactionOptions {
 pub struct DBOptions {
     pub(crate) inner: *mut Options,
     env: Option<Arc<Env>>,
+    wal_filter: Option<Arc<WalFilterHandle>>,
     pub(crate) titan_inner: *mut DBTitanDBOptions,
 }
 
@@ -937,6 +1061,7 @@ impl Default for DBOptions {
             DBOptions {
                 inner: opts,
                 env: None,
+                wal_filter: None,
                 titan_inner: ptr::null_mut::<DBTitanDBOptions>(),
             }
         }
@@ -955,6 +1080,7 @@ impl Clone for DBOptions {
             DBOptions {
                 inner: opts,
                 env: self.env.clone(),
+                wal_filter: self.wal_filter.clone(),
                 titan_inner: titan_opts,
             }
         }
@@ -974,62 +1100,106 @@ impl DBOptions {
         DBOptions {
             inner,
             env: None,
+            wal_filter: None,
             titan_inner: ptr::null_mut::<DBTitanDBOptions>(),
         }
     }
 
-    pub fn set_titandb_options(&mut self, opts: &TitanDBOptions) {
+    pub fn set_titandb_options(&mut self, opts: &TitanDBOptions) -> &mut Self {
         unsafe {
             self.titan_inner = crocksdb_ffi::ctitandb_options_copy(opts.inner);
         }
+        self
     }
 
-    pub fn increase_parallelism(&mut self, parallelism: i32) {
+    pub fn increase_parallelism(&mut self, parallelism: i32) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_increase_parallelism(self.inner, parallelism);
         }
+        self
     }
 
-    pub fn add_event_listener<L: EventListener>(&mut self, l: L) {
+    pub fn add_event_listener<L: EventListener>(&mut self, l: L) -> &mut Self {
         let handle = new_event_listener(l);
         unsafe { crocksdb_ffi::crocksdb_options_add_eventlistener(self.inner, handle) }
+        self
     }
 
-    pub fn create_if_missing(&mut self, create_if_missing: bool) {
+    pub fn create_if_missing(&mut self, create_if_missing: bool) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_create_if_missing(self.inner, create_if_missing);
         }
+        self
     }
 
-    pub fn create_missing_column_families(&mut self, create_missing_column_families: bool) {
+    pub fn create_missing_column_families(
+        &mut self,
+        create_missing_column_families: bool,
+    ) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_create_missing_column_families(
                 self.inner,
                 create_missing_column_families,
             )
         }
+        self
+    }
+
+    /// Routes the file IO of the DB through `inspector`, on top of the env
+    /// set by `set_env` or the default env. Call `set_env` first if both are
+    /// needed. The inspector only sees the size of each request, it can't
+    /// tell which file or what kind of IO a request is for.
+    pub fn set_file_system_inspector<T: FileSystemInspector>(
+        &mut self,
+        inspector: T,
+    ) -> Result<&mut Self, String> {
+        let base_env = self.env.clone().unwrap_or_default();
+        let env = Env::new_file_system_inspected_env(base_env, inspector)?;
+        Ok(self.set_env(Arc::new(env)))
+    }
+
+    /// Sets a filter that decides which write batches are replayed from the
+    /// WAL files when the DB is opened.
+    pub fn set_wal_filter<S, F>(&mut self, name: S, filter: F) -> Result<&mut Self, String>
+    where
+        S: Into<Vec<u8>>,
+        F: WalFilter,
+    {
+        let c_name = match CString::new(name) {
+            Ok(s) => s,
+            Err(e) => return Err(format!("failed to convert to cstring: {:?}", e)),
+        };
+        let filter = new_wal_filter(c_name, filter);
+        unsafe {
+            crocksdb_ffi::crocksdb_options_set_wal_filter(self.inner, filter.inner);
+        }
+        self.wal_filter = Some(Arc::new(filter));
+        Ok(self)
     }
 
-    pub fn set_env(&mut self, env: Arc<Env>) {
+    pub fn set_env(&mut self, env: Arc<Env>) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_env(self.inner, env.inner);
             self.env = Some(env);
         }
+        self
     }
 
-    pub fn set_max_open_files(&mut self, nfiles: c_int) {
+    pub fn set_max_open_files(&mut self, nfiles: c_int) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_max_open_files(self.inner, nfiles);
         }
+        self
     }
 
-    pub fn set_max_total_wal_size(&mut self, size: u64) {
+    pub fn set_max_total_wal_size(&mut self, size: u64) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_max_total_wal_size(self.inner, size);
         }
+        self
     }
 
-    pub fn set_use_fsync(&mut self, useit: bool) {
+    pub fn set_use_fsync(&mut self, useit: bool) -> &mut Self {
         unsafe {
             if useit {
                 crocksdb_ffi::crocksdb_options_set_use_fsync(self.inner, 1)
@@ -1037,203 +1207,268 @@ impl DBOptions {
                 crocksdb_ffi::crocksdb_options_set_use_fsync(self.inner, 0)
             }
         }
+        self
     }
 
-    pub fn set_bytes_per_sync(&mut self, nbytes: u64) {
+    pub fn set_bytes_per_sync(&mut self, nbytes: u64) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_bytes_per_sync(self.inner, nbytes);
         }
+        self
     }
 
-    pub fn set_table_cache_num_shard_bits(&mut self, nbits: c_int) {
+    pub fn set_table_cache_num_shard_bits(&mut self, nbits: c_int) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_table_cache_numshardbits(self.inner, nbits);
         }
+        self
     }
 
-    pub fn set_writable_file_max_buffer_size(&mut self, nbytes: c_int) {
+    pub fn set_writable_file_max_buffer_size(&mut self, nbytes: c_int) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_writable_file_max_buffer_size(self.inner, nbytes);
         }
+        self
     }
 
-    pub fn set_use_direct_reads(&mut self, v: bool) {
+    pub fn set_use_direct_reads(&mut self, v: bool) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_use_direct_reads(self.inner, v);
         }
+        self
     }
 
-    pub fn set_use_direct_io_for_flush_and_compaction(&mut self, v: bool) {
+    pub fn set_use_direct_io_for_flush_and_compaction(&mut self, v: bool) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_use_direct_io_for_flush_and_compaction(
                 self.inner, v,
             );
         }
+        self
     }
 
-    pub fn set_max_manifest_file_size(&mut self, size: u64) {
+    pub fn set_max_manifest_file_size(&mut self, size: u64) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_max_manifest_file_size(self.inner, size);
         }
+        self
     }
 
-    pub fn set_max_background_jobs(&mut self, n: c_int) {
+    pub fn set_max_background_jobs(&mut self, n: c_int) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_max_background_jobs(self.inner, n);
         }
+        self
     }
 
     pub fn get_max_background_jobs(&self) -> i32 {
         unsafe { crocksdb_ffi::crocksdb_options_get_max_background_jobs(self.inner) }
     }
 
-    pub fn set_max_background_compactions(&mut self, n: c_int) {
+    pub fn set_max_background_compactions(&mut self, n: c_int) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_max_background_compactions(self.inner, n);
         }
+        self
     }
 
     pub fn get_max_background_compactions(&self) -> i32 {
         unsafe { crocksdb_ffi::crocksdb_options_get_max_background_compactions(self.inner) }
     }
 
-    pub fn set_base_background_compactions(&mut self, n: c_int) {
+    pub fn set_base_background_compactions(&mut self, n: c_int) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_base_background_compactions(self.inner, n);
         }
+        self
     }
 
     pub fn get_base_background_compactions(&self) -> i32 {
         unsafe { crocksdb_ffi::crocksdb_options_get_base_background_compactions(self.inner) }
     }
 
-    pub fn set_max_background_flushes(&mut self, n: c_int) {
+    pub fn set_max_background_flushes(&mut self, n: c_int) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_max_background_flushes(self.inner, n);
         }
+        self
     }
 
     pub fn get_max_background_flushes(&self) -> i32 {
         unsafe { crocksdb_ffi::crocksdb_options_get_max_background_flushes(self.inner) }
     }
 
-    pub fn set_max_subcompactions(&mut self, n: u32) {
+    pub fn set_max_subcompactions(&mut self, n: u32) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_max_subcompactions(self.inner, n);
         }
+        self
     }
 
-    pub fn set_wal_bytes_per_sync(&mut self, n: u64) {
+    pub fn set_wal_bytes_per_sync(&mut self, n: u64) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_wal_bytes_per_sync(self.inner, n);
         }
+        self
     }
 
-    pub fn set_wal_recovery_mode(&mut self, mode: DBRecoveryMode) {
+    pub fn set_wal_recovery_mode(&mut self, mode: DBRecoveryMode) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_wal_recovery_mode(self.inner, mode);
         }
+        self
     }
 
-    pub fn set_delayed_write_rate(&mut self, rate: u64) {
+    pub fn set_delayed_write_rate(&mut self, rate: u64) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_delayed_write_rate(self.inner, rate);
         }
+        self
     }
 
-    pub fn set_write_buffer_manager(&mut self, wbm: &WriteBufferManager) {
+    pub fn set_write_buffer_manager(&mut self, wbm: &WriteBufferManager) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_write_buffer_manager(self.inner, wbm.inner);
         }
+        self
     }
 
-    pub fn set_statistics(&mut self, s: &Statistics) {
+    pub fn set_statistics(&mut self, s: &Statistics) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_statistics(self.inner, s.inner);
         }
+        self
     }
 
-    pub fn set_stats_dump_period_sec(&mut self, period: usize) {
+    /// Returns the statistics set by `set_statistics`, the returned handle
+    /// shares the same counters. Returns an empty one if none is set.
+    pub fn get_statistics(&self) -> Statistics {
+        unsafe {
+            Statistics {
+                inner: crocksdb_ffi::crocksdb_options_get_statistics(self.inner),
+            }
+        }
+    }
+
+    /// Returns 0 if statistics is not enabled.
+    pub fn get_statistics_ticker_count(&self, ticker_type: DBStatisticsTickerType) -> u64 {
+        self.get_statistics().get_ticker_count(ticker_type)
+    }
+
+    pub fn get_and_reset_statistics_ticker_count(
+        &self,
+        ticker_type: DBStatisticsTickerType,
+    ) -> u64 {
+        self.get_statistics()
+            .get_and_reset_ticker_count(ticker_type)
+    }
+
+    /// Returns None if statistics is not enabled.
+    pub fn get_statistics_histogram(
+        &self,
+        hist_type: DBStatisticsHistogramType,
+    ) -> Option<HistogramData> {
+        self.get_statistics().get_histogram(hist_type)
+    }
+
+    pub fn reset_statistics(&self) {
+        self.get_statistics().reset();
+    }
+
+    pub fn set_stats_dump_period_sec(&mut self, period: usize) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_stats_dump_period_sec(self.inner, period);
         }
+        self
     }
 
-    pub fn set_stats_persist_period_sec(&mut self, n: u32) {
+    pub fn set_stats_persist_period_sec(&mut self, n: u32) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_stats_persist_period_sec(self.inner, n);
         }
+        self
     }
 
-    pub fn set_db_log_dir(&mut self, path: &str) {
+    pub fn set_db_log_dir(&mut self, path: &str) -> &mut Self {
         let path = CString::new(path.as_bytes()).unwrap();
         unsafe {
             crocksdb_ffi::crocksdb_options_set_db_log_dir(self.inner, path.as_ptr());
         }
+        self
     }
 
-    pub fn set_wal_dir(&mut self, path: &str) {
+    pub fn set_wal_dir(&mut self, path: &str) -> &mut Self {
         let path = CString::new(path.as_bytes()).unwrap();
         unsafe {
             crocksdb_ffi::crocksdb_options_set_wal_dir(self.inner, path.as_ptr());
         }
+        self
     }
 
-    pub fn set_wal_ttl_seconds(&mut self, ttl: u64) {
+    pub fn set_wal_ttl_seconds(&mut self, ttl: u64) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_wal_ttl_seconds(self.inner, ttl);
         }
+        self
     }
 
-    pub fn set_wal_size_limit_mb(&mut self, limit: u64) {
+    pub fn set_wal_size_limit_mb(&mut self, limit: u64) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_wal_size_limit_mb(self.inner, limit);
         }
+        self
     }
 
-    pub fn set_max_log_file_size(&mut self, size: u64) {
+    pub fn set_max_log_file_size(&mut self, size: u64) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_max_log_file_size(self.inner, size as size_t);
         }
+        self
     }
 
-    pub fn set_log_file_time_to_roll(&mut self, ttl: u64) {
+    pub fn set_log_file_time_to_roll(&mut self, ttl: u64) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_log_file_time_to_roll(self.inner, ttl as size_t);
         }
+        self
     }
 
-    pub fn set_info_log_level(&mut self, level: DBInfoLogLevel) {
+    pub fn set_info_log_level(&mut self, level: DBInfoLogLevel) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_info_log_level(self.inner, level);
         }
+        self
     }
 
-    pub fn set_keep_log_file_num(&mut self, num: u64) {
+    pub fn set_keep_log_file_num(&mut self, num: u64) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_keep_log_file_num(self.inner, num as size_t);
         }
+        self
     }
 
-    pub fn set_recycle_log_file_num(&mut self, num: u64) {
+    pub fn set_recycle_log_file_num(&mut self, num: u64) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_recycle_log_file_num(self.inner, num as size_t);
         }
+        self
     }
 
-    pub fn set_compaction_readahead_size(&mut self, size: u64) {
+    pub fn set_compaction_readahead_size(&mut self, size: u64) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_compaction_readahead_size(
                 self.inner,
                 size as size_t,
             );
         }
+        self
     }
 
-    pub fn set_rate_limiter(&mut self, rate_limiter: &RateLimiter) {
+    pub fn set_rate_limiter(&mut self, rate_limiter: &RateLimiter) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_ratelimiter(self.inner, rate_limiter.inner);
         }
+        self
     }
 
     pub fn get_rate_limiter(&self) -> Option<RateLimiter> {
@@ -1278,49 +1513,66 @@ impl DBOptions {
     }
 
     // Set the logger to options.
-    pub fn set_info_log<L: Logger>(&self, l: L) {
+    pub fn set_info_log<L: Logger>(&mut self, l: L) -> &mut Self {
         let logger = new_logger(l);
         unsafe {
             crocksdb_ffi::crocksdb_options_set_info_log(self.inner, logger);
         }
+        self
     }
 
-    pub fn enable_pipelined_write(&self, v: bool) {
+    pub fn enable_pipelined_write(&mut self, v: bool) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_enable_pipelined_write(self.inner, v);
         }
+        self
     }
 
-    pub fn enable_multi_batch_write(&self, v: bool) {
+    pub fn enable_multi_batch_write(&mut self, v: bool) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_enable_multi_batch_write(self.inner, v);
         }
+        self
     }
 
     pub fn is_enable_multi_batch_write(&self) -> bool {
         unsafe { crocksdb_ffi::crocksdb_options_is_enable_multi_batch_write(self.inner) }
     }
 
-    pub fn enable_unordered_write(&self, v: bool) {
+    pub fn enable_unordered_write(&mut self, v: bool) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_unordered_write(self.inner, v);
         }
+        self
     }
 
-    pub fn allow_concurrent_memtable_write(&self, v: bool) {
+    /// Writes the WAL and the memtable through two separate write queues, so
+    /// WAL-only writes don't wait for memtable inserts. Commonly paired with
+    /// `enable_unordered_write`, which relies on it to keep snapshots
+    /// consistent. Can't be combined with `enable_pipelined_write`.
+    pub fn set_two_write_queues(&mut self, v: bool) -> &mut Self {
+        unsafe {
+            crocksdb_ffi::crocksdb_options_set_two_write_queues(self.inner, v);
+        }
+        self
+    }
+
+    pub fn allow_concurrent_memtable_write(&mut self, v: bool) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_allow_concurrent_memtable_write(self.inner, v);
         }
+        self
     }
 
-    pub fn manual_wal_flush(&self, v: bool) {
+    pub fn manual_wal_flush(&mut self, v: bool) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_manual_wal_flush(self.inner, v);
         }
+        self
     }
 
     /// the second parameter is a slice which contains tuples (path, target_size).
-    pub fn set_db_paths<T: AsRef<Path>>(&self, val: &[(T, u64)]) {
+    pub fn set_db_paths<T: AsRef<Path>>(&mut self, val: &[(T, u64)]) -> &mut Self {
         let num_paths = val.len();
         let mut cpaths = Vec::with_capacity(num_paths);
         let mut cpath_lens = Vec::with_capacity(num_paths);
@@ -1341,24 +1593,28 @@ impl DBOptions {
                 num_paths as c_int,
             );
         }
+        self
     }
 
-    pub fn set_atomic_flush(&self, enable: bool) {
+    pub fn set_atomic_flush(&mut self, enable: bool) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_atomic_flush(self.inner, enable);
         }
+        self
     }
 
-    pub fn avoid_flush_during_recovery(&self, avoid: bool) {
+    pub fn avoid_flush_during_recovery(&mut self, avoid: bool) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_avoid_flush_during_recovery(self.inner, avoid);
         }
+        self
     }
 
-    pub fn avoid_flush_during_shutdown(&self, avoid: bool) {
+    pub fn avoid_flush_during_shutdown(&mut self, avoid: bool) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_avoid_flush_during_shutdown(self.inner, avoid);
         }
+        self
     }
 
     pub fn get_db_paths_num(&self) -> usize {
@@ -1382,16 +1638,18 @@ impl DBOptions {
 
     /// Set paranoid checks. The default value is `true`. We can set it to `false`
     /// to skip manifest checks.
-    pub fn set_paranoid_checks(&self, enable: bool) {
+    pub fn set_paranoid_checks(&mut self, enable: bool) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_paranoid_checks(self.inner, enable as u8);
         }
+        self
     }
 
-    pub fn set_doubly_skiplist(&self) {
+    pub fn set_doubly_skiplist(&mut self) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_doubly_skip_list_rep(self.inner);
         }
+        self
     }
 
     pub fn get_memtable_name(&self) -> Option<&str> {
@@ -1405,12 +1663,13 @@ impl DBOptions {
         }
     }
 
-    pub fn set_track_and_verify_wals_in_manifest(&self, enable: bool) {
+    pub fn set_track_and_verify_wals_in_manifest(&mut self, enable: bool) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_track_and_verify_wals_in_manifest(
                 self.inner, enable,
             );
         }
+        self
     }
 }
 
@@ -1491,26 +1750,29 @@ impl ColumnFamilyOptions {
         }
     }
 
-    pub fn set_titandb_options(&mut self, opts: &TitanDBOptions) {
+    pub fn set_titandb_options(&mut self, opts: &TitanDBOptions) -> &mut Self {
         unsafe {
             self.titan_inner = crocksdb_ffi::ctitandb_options_copy(opts.inner);
         }
+        self
     }
 
-    pub fn optimize_level_style_compaction(&mut self, memtable_memory_budget: i32) {
+    pub fn optimize_level_style_compaction(&mut self, memtable_memory_budget: i32) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_optimize_level_style_compaction(
                 self.inner,
                 memtable_memory_budget,
             );
         }
+        self
     }
 
-    pub fn set_env(&mut self, env: Arc<Env>) {
+    pub fn set_env(&mut self, env: Arc<Env>) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_env(self.inner, env.inner);
             self.env = Some(env);
         }
+        self
     }
 
     /// Set compaction filter.
@@ -1522,7 +1784,7 @@ impl ColumnFamilyOptions {
     /// recent call to GetSnapshot() to filter.
     ///
     /// See also `CompactionFilter`.
-    pub fn set_compaction_filter<S, C>(&mut self, name: S, filter: C) -> Result<(), String>
+    pub fn set_compaction_filter<S, C>(&mut self, name: S, filter: C) -> Result<&mut Self, String>
     where
         S: Into<Vec<u8>>,
         C: CompactionFilter,
@@ -1535,14 +1797,18 @@ impl ColumnFamilyOptions {
             let filter = new_compaction_filter::<C>(c_name, filter);
             crocksdb_ffi::crocksdb_options_set_compaction_filter(self.inner, filter.inner);
             self.filter = Some(filter);
-            Ok(())
+            Ok(self)
         }
     }
 
     /// Set compaction filter factory.
     ///
     /// See also `CompactionFilterFactory`.
-    pub fn set_compaction_filter_factory<S, C>(&mut self, name: S, factory: C) -> Result<(), String>
+    pub fn set_compaction_filter_factory<S, C>(
+        &mut self,
+        name: S,
+        factory: C,
+    ) -> Result<&mut Self, String>
     where
         S: Into<Vec<u8>>,
         C: CompactionFilterFactory,
@@ -1555,14 +1821,15 @@ impl ColumnFamilyOptions {
             let factory = new_compaction_filter_factory::<C>(c_name, factory)?;
             crocksdb_ffi::crocksdb_options_set_compaction_filter_factory(self.inner, factory.inner);
             std::mem::forget(factory); // Deconstructor will be called after `self` is dropped.
-            Ok(())
+            Ok(self)
         }
     }
 
-    pub fn set_compaction_thread_limiter(&mut self, limiter: &ConcurrentTaskLimiter) {
+    pub fn set_compaction_thread_limiter(&mut self, limiter: &ConcurrentTaskLimiter) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_compaction_thread_limiter(self.inner, limiter.inner);
         }
+        self
     }
 
     pub fn get_compaction_thread_limiter(&self) -> Option<ConcurrentTaskLimiter> {
@@ -1582,17 +1849,19 @@ impl ColumnFamilyOptions {
         &mut self,
         fname: &str,
         factory: T,
-    ) {
+    ) -> &mut Self {
         unsafe {
             let f = new_table_properties_collector_factory(fname, factory);
             crocksdb_ffi::crocksdb_options_add_table_properties_collector_factory(self.inner, f);
         }
+        self
     }
 
-    pub fn compression(&mut self, t: DBCompressionType) {
+    pub fn compression(&mut self, t: DBCompressionType) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_compression(self.inner, t);
         }
+        self
     }
 
     pub fn get_compression(&self) -> DBCompressionType {
@@ -1607,7 +1876,7 @@ impl ColumnFamilyOptions {
         max_dict_bytes: i32,
         zstd_max_train_bytes: i32,
         parallel_threads: i32,
-    ) {
+    ) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_compression_options(
                 self.inner,
@@ -1619,6 +1888,7 @@ impl ColumnFamilyOptions {
                 parallel_threads,
             )
         }
+        self
     }
 
     pub fn set_bottommost_level_compression_options(
@@ -1629,7 +1899,7 @@ impl ColumnFamilyOptions {
         max_dict_bytes: i32,
         zstd_max_train_bytes: i32,
         parallel_threads: i32,
-    ) {
+    ) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_bottommost_compression_options(
                 self.inner,
@@ -1641,9 +1911,10 @@ impl ColumnFamilyOptions {
                 parallel_threads,
             )
         }
+        self
     }
 
-    pub fn compression_per_level(&mut self, level_types: &[DBCompressionType]) {
+    pub fn compression_per_level(&mut self, level_types: &[DBCompressionType]) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_compression_per_level(
                 self.inner,
@@ -1651,6 +1922,7 @@ impl ColumnFamilyOptions {
                 level_types.len() as size_t,
             )
         }
+        self
     }
 
     pub fn get_compression_per_level(&self) -> Vec<DBCompressionType> {
@@ -1664,11 +1936,12 @@ impl ColumnFamilyOptions {
         }
     }
 
-    pub fn bottommost_compression(&self, c: DBCompressionType) {
+    pub fn bottommost_compression(&mut self, c: DBCompressionType) -> &mut Self {
         unsafe { crocksdb_ffi::crocksdb_set_bottommost_compression(self.inner, c) }
+        self
     }
 
-    pub fn add_merge_operator(&mut self, name: &str, merge_fn: MergeFn) {
+    pub fn add_merge_operator(&mut self, name: &str, merge_fn: MergeFn) -> &mut Self {
         let cb = Box::new(MergeOperatorCallback {
             name: CString::new(name.as_bytes()).unwrap(),
             merge_fn,
@@ -1686,9 +1959,10 @@ impl ColumnFamilyOptions {
             );
             crocksdb_ffi::crocksdb_options_set_merge_operator(self.inner, mo);
         }
+        self
     }
 
-    pub fn add_comparator(&mut self, name: &str, compare_fn: fn(&[u8], &[u8]) -> i32) {
+    pub fn add_comparator(&mut self, name: &str, compare_fn: fn(&[u8], &[u8]) -> i32) -> &mut Self {
         let cb = Box::new(ComparatorCallback {
             name: CString::new(name.as_bytes()).unwrap(),
             f: compare_fn,
@@ -1704,18 +1978,32 @@ impl ColumnFamilyOptions {
             );
             crocksdb_ffi::crocksdb_options_set_comparator(self.inner, cmp);
         }
+        self
+    }
+
+    /// Uses the bytewise comparator with 8 bytes user timestamps, keys of the
+    /// column family must be suffixed with a little-endian encoded u64
+    /// timestamp. Versions of the same user key are ordered by descending
+    /// timestamp.
+    pub fn set_bytewise_comparator_with_u64_ts(&mut self) -> &mut Self {
+        unsafe {
+            crocksdb_ffi::crocksdb_options_set_bytewise_comparator_with_u64_ts(self.inner);
+        }
+        self
     }
 
-    pub fn set_block_cache_size_mb(&mut self, cache_size: u64) {
+    pub fn set_block_cache_size_mb(&mut self, cache_size: u64) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_optimize_for_point_lookup(self.inner, cache_size);
         }
+        self
     }
 
-    pub fn set_min_write_buffer_number(&mut self, nbuf: c_int) {
+    pub fn set_min_write_buffer_number(&mut self, nbuf: c_int) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_min_write_buffer_number_to_merge(self.inner, nbuf);
         }
+        self
     }
 
     pub fn get_min_write_buffer_number(&self) -> u32 {
@@ -1724,43 +2012,75 @@ impl ColumnFamilyOptions {
         }
     }
 
-    pub fn set_max_write_buffer_number(&mut self, nbuf: c_int) {
+    pub fn set_max_write_buffer_number(&mut self, nbuf: c_int) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_max_write_buffer_number(self.inner, nbuf);
         }
+        self
     }
 
     pub fn get_max_write_buffer_number(&self) -> u32 {
         unsafe { crocksdb_ffi::crocksdb_options_get_max_write_buffer_number(self.inner) as u32 }
     }
 
-    pub fn set_write_buffer_size(&mut self, size: u64) {
+    /// Keeps flushed memtables in memory up to `size` bytes in total, so that
+    /// conflict checking of transactions can still see recent writes. 0 drops
+    /// them right after the flush, -1 uses `max_write_buffer_number` times the
+    /// write buffer size.
+    pub fn set_max_write_buffer_size_to_maintain(&mut self, size: i64) -> &mut Self {
+        unsafe {
+            crocksdb_ffi::crocksdb_options_set_max_write_buffer_size_to_maintain(self.inner, size);
+        }
+        self
+    }
+
+    pub fn get_max_write_buffer_size_to_maintain(&self) -> i64 {
+        unsafe { crocksdb_ffi::crocksdb_options_get_max_write_buffer_size_to_maintain(self.inner) }
+    }
+
+    pub fn set_write_buffer_size(&mut self, size: u64) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_write_buffer_size(self.inner, size);
         }
+        self
     }
 
     pub fn get_write_buffer_size(&self) -> u64 {
         unsafe { crocksdb_ffi::crocksdb_options_get_write_buffer_size(self.inner) }
     }
 
-    pub fn set_max_bytes_for_level_base(&mut self, size: u64) {
+    /// Allocates memtable memory from huge pages of the given size if it's
+    /// greater than 0, falls back to malloc if huge pages are not available.
+    pub fn set_memtable_huge_page_size(&mut self, size: usize) -> &mut Self {
+        unsafe {
+            crocksdb_ffi::crocksdb_options_set_memtable_huge_page_size(self.inner, size);
+        }
+        self
+    }
+
+    pub fn get_memtable_huge_page_size(&self) -> usize {
+        unsafe { crocksdb_ffi::crocksdb_options_get_memtable_huge_page_size(self.inner) }
+    }
+
+    pub fn set_max_bytes_for_level_base(&mut self, size: u64) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_max_bytes_for_level_base(self.inner, size);
         }
+        self
     }
 
     pub fn get_max_bytes_for_level_base(&self) -> u64 {
         unsafe { crocksdb_ffi::crocksdb_options_get_max_bytes_for_level_base(self.inner) }
     }
 
-    pub fn set_max_bytes_for_level_multiplier(&mut self, mul: i32) {
+    pub fn set_max_bytes_for_level_multiplier(&mut self, mul: i32) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_max_bytes_for_level_multiplier(
                 self.inner,
                 f64::from(mul),
             );
         }
+        self
     }
 
     pub fn get_max_bytes_for_level_multiplier(&self) -> i32 {
@@ -1769,20 +2089,22 @@ impl ColumnFamilyOptions {
         }
     }
 
-    pub fn set_max_compaction_bytes(&mut self, bytes: u64) {
+    pub fn set_max_compaction_bytes(&mut self, bytes: u64) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_max_compaction_bytes(self.inner, bytes);
         }
+        self
     }
 
     pub fn get_max_compaction_bytes(&self) -> u64 {
         unsafe { crocksdb_ffi::crocksdb_options_get_max_compaction_bytes(self.inner) }
     }
 
-    pub fn set_level_compaction_dynamic_level_bytes(&mut self, v: bool) {
+    pub fn set_level_compaction_dynamic_level_bytes(&mut self, v: bool) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_level_compaction_dynamic_level_bytes(self.inner, v);
         }
+        self
     }
 
     pub fn get_level_compaction_dynamic_level_bytes(&self) -> bool {
@@ -1791,12 +2113,13 @@ impl ColumnFamilyOptions {
         }
     }
 
-    pub fn set_soft_pending_compaction_bytes_limit(&mut self, size: u64) {
+    pub fn set_soft_pending_compaction_bytes_limit(&mut self, size: u64) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_soft_pending_compaction_bytes_limit(
                 self.inner, size,
             );
         }
+        self
     }
 
     pub fn get_soft_pending_compaction_bytes_limit(&self) -> u64 {
@@ -1805,12 +2128,13 @@ impl ColumnFamilyOptions {
         }
     }
 
-    pub fn set_hard_pending_compaction_bytes_limit(&mut self, size: u64) {
+    pub fn set_hard_pending_compaction_bytes_limit(&mut self, size: u64) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_hard_pending_compaction_bytes_limit(
                 self.inner, size,
             );
         }
+        self
     }
 
     pub fn get_hard_pending_compaction_bytes_limit(&self) -> u64 {
@@ -1819,28 +2143,31 @@ impl ColumnFamilyOptions {
         }
     }
 
-    pub fn set_target_file_size_base(&mut self, size: u64) {
+    pub fn set_target_file_size_base(&mut self, size: u64) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_target_file_size_base(self.inner, size);
         }
+        self
     }
 
-    pub fn set_target_file_size_multiplier(&mut self, multiplier: i32) {
+    pub fn set_target_file_size_multiplier(&mut self, multiplier: i32) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_target_file_size_multiplier(self.inner, multiplier)
         }
+        self
     }
 
     pub fn get_target_file_size_base(&self) -> u64 {
         unsafe { crocksdb_ffi::crocksdb_options_get_target_file_size_base(self.inner) }
     }
 
-    pub fn set_min_write_buffer_number_to_merge(&mut self, to_merge: c_int) {
+    pub fn set_min_write_buffer_number_to_merge(&mut self, to_merge: c_int) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_min_write_buffer_number_to_merge(
                 self.inner, to_merge,
             );
         }
+        self
     }
 
     pub fn get_min_write_buffer_number_to_merge(&self) -> u32 {
@@ -1849,10 +2176,11 @@ impl ColumnFamilyOptions {
         }
     }
 
-    pub fn set_level_zero_file_num_compaction_trigger(&mut self, n: c_int) {
+    pub fn set_level_zero_file_num_compaction_trigger(&mut self, n: c_int) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_level0_file_num_compaction_trigger(self.inner, n);
         }
+        self
     }
 
     pub fn get_level_zero_file_num_compaction_trigger(&self) -> u32 {
@@ -1861,10 +2189,11 @@ impl ColumnFamilyOptions {
         }
     }
 
-    pub fn set_level_zero_slowdown_writes_trigger(&mut self, n: c_int) {
+    pub fn set_level_zero_slowdown_writes_trigger(&mut self, n: c_int) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_level0_slowdown_writes_trigger(self.inner, n);
         }
+        self
     }
 
     pub fn get_level_zero_slowdown_writes_trigger(&self) -> u32 {
@@ -1873,29 +2202,32 @@ impl ColumnFamilyOptions {
         }
     }
 
-    pub fn set_level_zero_stop_writes_trigger(&mut self, n: c_int) {
+    pub fn set_level_zero_stop_writes_trigger(&mut self, n: c_int) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_level0_stop_writes_trigger(self.inner, n);
         }
+        self
     }
 
     pub fn get_level_zero_stop_writes_trigger(&self) -> u32 {
         unsafe { crocksdb_ffi::crocksdb_options_get_level0_stop_writes_trigger(self.inner) as u32 }
     }
 
-    pub fn set_compaction_style(&mut self, style: crocksdb_ffi::DBCompactionStyle) {
+    pub fn set_compaction_style(&mut self, style: crocksdb_ffi::DBCompactionStyle) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_compaction_style(self.inner, style);
         }
+        self
     }
 
-    pub fn compaction_priority(&mut self, priority: crocksdb_ffi::CompactionPriority) {
+    pub fn compaction_priority(&mut self, priority: crocksdb_ffi::CompactionPriority) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_compaction_priority(self.inner, priority);
         }
+        self
     }
 
-    pub fn set_disable_auto_compactions(&mut self, disable: bool) {
+    pub fn set_disable_auto_compactions(&mut self, disable: bool) -> &mut Self {
         unsafe {
             if disable {
                 crocksdb_ffi::crocksdb_options_set_disable_auto_compactions(self.inner, 1)
@@ -1903,27 +2235,51 @@ impl ColumnFamilyOptions {
                 crocksdb_ffi::crocksdb_options_set_disable_auto_compactions(self.inner, 0)
             }
         }
+        self
     }
 
     pub fn get_disable_auto_compactions(&self) -> bool {
         unsafe { crocksdb_ffi::crocksdb_options_get_disable_auto_compactions(self.inner) == 1 }
     }
 
-    pub fn set_disable_write_stall(&mut self, disable: bool) {
+    /// Enables the integrated BlobDB, which stores values of at least
+    /// `min_blob_size` bytes in separate blob files. It can't be combined
+    /// with a merge operator; opening such a column family fails.
+    pub fn set_enable_blob_files(&mut self, enable: bool) -> &mut Self {
+        unsafe { crocksdb_ffi::crocksdb_options_set_enable_blob_files(self.inner, enable) }
+        self
+    }
+
+    pub fn get_enable_blob_files(&self) -> bool {
+        unsafe { crocksdb_ffi::crocksdb_options_get_enable_blob_files(self.inner) }
+    }
+
+    pub fn set_min_blob_size(&mut self, size: u64) -> &mut Self {
+        unsafe { crocksdb_ffi::crocksdb_options_set_min_blob_size(self.inner, size) }
+        self
+    }
+
+    pub(crate) fn has_merge_operator(&self) -> bool {
+        unsafe { crocksdb_ffi::crocksdb_options_has_merge_operator(self.inner) }
+    }
+
+    pub fn set_disable_write_stall(&mut self, disable: bool) -> &mut Self {
         unsafe { crocksdb_ffi::crocksdb_options_set_disable_write_stall(self.inner, disable) }
+        self
     }
 
     pub fn get_disable_write_stall(&self) -> bool {
         unsafe { crocksdb_ffi::crocksdb_options_get_disable_write_stall(self.inner) }
     }
 
-    pub fn set_block_based_table_factory(&mut self, factory: &BlockBasedOptions) {
+    pub fn set_block_based_table_factory(&mut self, factory: &BlockBasedOptions) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_block_based_table_factory(self.inner, factory.inner);
         }
+        self
     }
 
-    pub fn set_report_bg_io_stats(&mut self, enable: bool) {
+    pub fn set_report_bg_io_stats(&mut self, enable: bool) -> &mut Self {
         unsafe {
             if enable {
                 crocksdb_ffi::crocksdb_options_set_report_bg_io_stats(self.inner, 1);
@@ -1931,19 +2287,25 @@ impl ColumnFamilyOptions {
                 crocksdb_ffi::crocksdb_options_set_report_bg_io_stats(self.inner, 0);
             }
         }
+        self
     }
 
-    pub fn set_num_levels(&mut self, n: c_int) {
+    pub fn set_num_levels(&mut self, n: c_int) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_num_levels(self.inner, n);
         }
+        self
     }
 
     pub fn get_num_levels(&self) -> usize {
         unsafe { crocksdb_ffi::crocksdb_options_get_num_levels(self.inner) as usize }
     }
 
-    pub fn set_prefix_extractor<S, ST>(&mut self, name: S, transform: ST) -> Result<(), String>
+    pub fn set_prefix_extractor<S, ST>(
+        &mut self,
+        name: S,
+        transform: ST,
+    ) -> Result<&mut Self, String>
     where
         S: Into<Vec<u8>>,
         ST: SliceTransform,
@@ -1955,21 +2317,22 @@ impl ColumnFamilyOptions {
             };
             let transform = new_slice_transform::<ST>(c_name, transform)?;
             crocksdb_ffi::crocksdb_options_set_prefix_extractor(self.inner, transform);
-            Ok(())
+            Ok(self)
         }
     }
 
-    pub fn set_optimize_filters_for_hits(&mut self, v: bool) {
+    pub fn set_optimize_filters_for_hits(&mut self, v: bool) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_optimize_filters_for_hits(self.inner, v);
         }
+        self
     }
 
     pub fn set_memtable_insert_hint_prefix_extractor<S, ST>(
         &mut self,
         name: S,
         transform: ST,
-    ) -> Result<(), String>
+    ) -> Result<&mut Self, String>
     where
         S: Into<Vec<u8>>,
         ST: SliceTransform,
@@ -1983,20 +2346,32 @@ impl ColumnFamilyOptions {
             crocksdb_ffi::crocksdb_options_set_memtable_insert_with_hint_prefix_extractor(
                 self.inner, transform,
             );
-            Ok(())
+            Ok(self)
         }
     }
 
-    pub fn set_memtable_prefix_bloom_size_ratio(&mut self, ratio: f64) {
+    pub fn set_memtable_prefix_bloom_size_ratio(&mut self, ratio: f64) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_memtable_prefix_bloom_size_ratio(self.inner, ratio);
         }
+        self
     }
 
-    pub fn set_force_consistency_checks(&mut self, v: bool) {
+    /// Sets the number of cache lines each probe of the memtable and plain
+    /// table bloom filters is confined to. 0 (the default) spreads probes over
+    /// the whole filter.
+    pub fn set_bloom_locality(&mut self, locality: u32) -> &mut Self {
+        unsafe {
+            crocksdb_ffi::crocksdb_options_set_bloom_locality(self.inner, locality);
+        }
+        self
+    }
+
+    pub fn set_force_consistency_checks(&mut self, v: bool) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_force_consistency_checks(self.inner, v);
         }
+        self
     }
 
     pub fn get_force_consistency_checks(&self) -> bool {
@@ -2039,22 +2414,25 @@ impl ColumnFamilyOptions {
         unsafe { crocksdb_ffi::ctitandb_options_get_blob_cache_capacity(self.titan_inner) as u64 }
     }
 
-    pub fn set_fifo_compaction_options(&mut self, fifo_opts: FifoCompactionOptions) {
+    pub fn set_fifo_compaction_options(&mut self, fifo_opts: FifoCompactionOptions) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_fifo_compaction_options(self.inner, fifo_opts.inner);
         }
+        self
     }
 
-    pub fn set_vector_memtable_factory(&mut self, reserved_bytes: u64) {
+    pub fn set_vector_memtable_factory(&mut self, reserved_bytes: u64) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_vector_memtable_factory(self.inner, reserved_bytes);
         }
+        self
     }
 
-    pub fn set_doubly_skiplist(&self) {
+    pub fn set_doubly_skiplist(&mut self) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_doubly_skip_list_rep(self.inner);
         }
+        self
     }
 
     pub fn get_memtable_factory_name(&self) -> Option<&str> {
@@ -2068,14 +2446,22 @@ impl ColumnFamilyOptions {
         }
     }
 
-    pub fn set_sst_partitioner_factory<F: SstPartitionerFactory>(&mut self, factory: F) {
+    pub fn set_sst_partitioner_factory<F: SstPartitionerFactory>(
+        &mut self,
+        factory: F,
+    ) -> &mut Self {
         let f = new_sst_partitioner_factory(factory);
         unsafe {
             crocksdb_ffi::crocksdb_options_set_sst_partitioner_factory(self.inner, f);
         }
+        self
     }
 
-    pub fn set_compact_on_deletion(&self, sliding_window_size: usize, deletion_trigger: usize) {
+    pub fn set_compact_on_deletion(
+        &mut self,
+        sliding_window_size: usize,
+        deletion_trigger: usize,
+    ) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_compact_on_deletion(
                 self.inner,
@@ -2083,32 +2469,36 @@ impl ColumnFamilyOptions {
                 deletion_trigger,
             );
         }
+        self
     }
 
-    pub fn set_ttl(&mut self, ttl_secs: u64) {
+    pub fn set_ttl(&mut self, ttl_secs: u64) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_ttl(self.inner, ttl_secs);
         }
+        self
     }
 
     pub fn get_ttl(&self) -> u64 {
         unsafe { crocksdb_ffi::crocksdb_options_get_ttl(self.inner) }
     }
 
-    pub fn set_periodic_compaction_seconds(&mut self, secs: u64) {
+    pub fn set_periodic_compaction_seconds(&mut self, secs: u64) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_periodic_compaction_seconds(self.inner, secs);
         }
+        self
     }
 
     pub fn get_periodic_compaction_seconds(&self) -> u64 {
         unsafe { crocksdb_ffi::crocksdb_options_get_periodic_compaction_seconds(self.inner) }
     }
 
-    pub fn set_write_buffer_manager(&mut self, wbm: &WriteBufferManager) {
+    pub fn set_write_buffer_manager(&mut self, wbm: &WriteBufferManager) -> &mut Self {
         unsafe {
             crocksdb_ffi::crocksdb_options_set_cf_write_buffer_manager(self.inner, wbm.inner);
         }
+        self
     }
 
     pub fn get_write_buffer_manager(&self) -> Option<WriteBufferManager> {
@@ -2250,6 +2640,60 @@ impl Drop for FlushOptions {
     }
 }
 
+/// SizeApproximationOptions is used by DB::get_approximate_sizes_opt.
+pub struct SizeApproximationOptions {
+    pub(crate) inner: *mut DBSizeApproximationOptions,
+}
+
+impl Default for SizeApproximationOptions {
+    fn default() -> Self {
+        unsafe {
+            Self {
+                inner: crocksdb_ffi::crocksdb_size_approximation_options_create(),
+            }
+        }
+    }
+}
+
+impl SizeApproximationOptions {
+    /// Whether data in memtables should be taken into account, false by default.
+    pub fn set_include_memtables(&mut self, include: bool) {
+        unsafe {
+            crocksdb_ffi::crocksdb_size_approximation_options_set_include_memtables(
+                self.inner, include,
+            );
+        }
+    }
+
+    /// Whether data in SST files should be taken into account, true by default.
+    pub fn set_include_files(&mut self, include: bool) {
+        unsafe {
+            crocksdb_ffi::crocksdb_size_approximation_options_set_include_files(
+                self.inner, include,
+            );
+        }
+    }
+
+    /// If positive, allows the size of SST files to be approximated with an
+    /// error margin of `margin` times the total size, which is much faster
+    /// for large ranges.
+    pub fn set_files_size_error_margin(&mut self, margin: f64) {
+        unsafe {
+            crocksdb_ffi::crocksdb_size_approximation_options_set_files_size_error_margin(
+                self.inner, margin,
+            );
+        }
+    }
+}
+
+impl Drop for SizeApproximationOptions {
+    fn drop(&mut self) {
+        unsafe {
+            crocksdb_ffi::crocksdb_size_approximation_options_destroy(self.inner);
+        }
+    }
+}
+
 /// IngestExternalFileOptions is used by DB::ingest_external_file
 pub struct IngestExternalFileOptions {
     pub(crate) inner: *mut crocksdb_ffi::IngestExternalFileOptions,
@@ -2332,6 +2776,19 @@ impl IngestExternalFileOptions {
             );
         }
     }
+
+    /// If set to true, DB::ingest_external_file() will fail with TryAgain if
+    /// any of the files can't be placed in the bottommost level, e.g. because it
+    /// overlaps with existing keys. The files are ingested atomically, so
+    /// either all of them are ingested or none is.
+    pub fn set_fail_if_not_bottommost_level(&mut self, whether_fail: bool) {
+        unsafe {
+            crocksdb_ffi::crocksdb_ingestexternalfileoptions_set_fail_if_not_bottommost_level(
+                self.inner,
+                whether_fail,
+            );
+        }
+    }
 }
 
 impl Drop for IngestExternalFileOptions {
@@ -2507,6 +2964,43 @@ impl Drop for LRUCacheOptions {
     }
 }
 
+/// Options to create a clock cache, see `Cache::new_clock_cache`.
+pub struct ClockCacheOptions {
+    pub(crate) capacity: usize,
+    pub(crate) num_shard_bits: c_int,
+    pub(crate) strict_capacity_limit: bool,
+}
+
+impl ClockCacheOptions {
+    pub fn new() -> ClockCacheOptions {
+        ClockCacheOptions {
+            capacity: 0,
+            num_shard_bits: -1,
+            strict_capacity_limit: false,
+        }
+    }
+
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+    }
+
+    /// The cache is sharded into 2^num_shard_bits shards, -1 lets RocksDB
+    /// choose a value based on the capacity.
+    pub fn set_num_shard_bits(&mut self, num_shard_bits: c_int) {
+        self.num_shard_bits = num_shard_bits;
+    }
+
+    pub fn set_strict_capacity_limit(&mut self, strict_capacity_limit: bool) {
+        self.strict_capacity_limit = strict_capacity_limit;
+    }
+}
+
+impl Default for ClockCacheOptions {
+    fn default() -> ClockCacheOptions {
+        ClockCacheOptions::new()
+    }
+}
+
 pub struct MergeInstanceOptions {
     pub merge_memtable: bool,
     pub allow_source_write: bool,
@@ -198,6 +198,64 @@ pub fn new_sst_partitioner_factory<F: SstPartitionerFactory>(
     }
 }
 
+/// An `SstPartitioner` that cuts output files whenever the first
+/// `prefix_len` bytes of the user key change, so that no SST file spans more
+/// than one prefix. Keys shorter than `prefix_len` are treated as their own
+/// prefix.
+pub struct FixedPrefixSstPartitioner {
+    prefix_len: usize,
+}
+
+impl FixedPrefixSstPartitioner {
+    pub fn new(prefix_len: usize) -> FixedPrefixSstPartitioner {
+        FixedPrefixSstPartitioner { prefix_len }
+    }
+
+    fn prefix<'a>(&self, key: &'a [u8]) -> &'a [u8] {
+        &key[..key.len().min(self.prefix_len)]
+    }
+}
+
+impl SstPartitioner for FixedPrefixSstPartitioner {
+    fn should_partition(&mut self, req: &SstPartitionerRequest) -> SstPartitionerResult {
+        if self.prefix(req.prev_user_key) != self.prefix(req.current_user_key) {
+            SstPartitionerResult::Required
+        } else {
+            SstPartitionerResult::NotRequired
+        }
+    }
+
+    fn can_do_trivial_move(&mut self, smallest_user_key: &[u8], largest_user_key: &[u8]) -> bool {
+        self.prefix(smallest_user_key) == self.prefix(largest_user_key)
+    }
+}
+
+pub struct FixedPrefixSstPartitionerFactory {
+    prefix_len: usize,
+    name: CString,
+}
+
+impl FixedPrefixSstPartitionerFactory {
+    pub fn new(prefix_len: usize) -> FixedPrefixSstPartitionerFactory {
+        FixedPrefixSstPartitionerFactory {
+            prefix_len,
+            name: CString::new(format!("FixedPrefixSstPartitionerFactory.{}", prefix_len)).unwrap(),
+        }
+    }
+}
+
+impl SstPartitionerFactory for FixedPrefixSstPartitionerFactory {
+    type Partitioner = FixedPrefixSstPartitioner;
+
+    fn name(&self) -> &CString {
+        &self.name
+    }
+
+    fn create_partitioner(&self, _: &SstPartitionerContext) -> Option<Self::Partitioner> {
+        Some(FixedPrefixSstPartitioner::new(self.prefix_len))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::{
@@ -576,4 +634,49 @@ mod test {
             assert_eq!(1, sl.drop_factory);
         }
     }
+
+    #[test]
+    fn fixed_prefix_partitioner() {
+        let factory = FixedPrefixSstPartitionerFactory::new(2);
+        assert_eq!(
+            factory.name().to_str().unwrap(),
+            "FixedPrefixSstPartitionerFactory.2"
+        );
+        let context = SstPartitionerContext {
+            is_full_compaction: false,
+            is_manual_compaction: false,
+            output_level: 1,
+            smallest_key: b"",
+            largest_key: b"",
+            next_level_boundaries: vec![],
+            next_level_sizes: vec![],
+        };
+        let mut partitioner = factory.create_partitioner(&context).unwrap();
+        let mut should_partition = |prev: &[u8], current: &[u8]| {
+            partitioner.should_partition(&SstPartitionerRequest {
+                prev_user_key: prev,
+                current_user_key: current,
+                current_output_file_size: 0,
+            })
+        };
+        assert_eq!(
+            should_partition(b"aa1", b"aa2"),
+            SstPartitionerResult::NotRequired
+        );
+        assert_eq!(
+            should_partition(b"aa9", b"ab0"),
+            SstPartitionerResult::Required
+        );
+        assert_eq!(
+            should_partition(b"a", b"aa"),
+            SstPartitionerResult::Required
+        );
+        assert_eq!(
+            should_partition(b"a", b"a"),
+            SstPartitionerResult::NotRequired
+        );
+        let mut partitioner = factory.create_partitioner(&context).unwrap();
+        assert!(partitioner.can_do_trivial_move(b"aa0", b"aa9"));
+        assert!(!partitioner.can_do_trivial_move(b"aa0", b"ab0"));
+    }
 }
@@ -257,6 +257,38 @@ impl UserCollectedProperties {
         }
     }
 
+    /// Decodes the value of `index` as a little-endian fixed64, as written by
+    /// rocksdb's `PutFixed64`. Returns `None` if there is no such entry or the
+    /// value isn't 8 bytes long.
+    pub fn get_u64<Q: AsRef<[u8]>>(&self, index: Q) -> Option<u64> {
+        let v = self.get(index)?;
+        let mut buf = [0u8; 8];
+        if v.len() != buf.len() {
+            return None;
+        }
+        buf.copy_from_slice(v);
+        Some(u64::from_le_bytes(buf))
+    }
+
+    /// Decodes the value of `index` as a varint64, as written by rocksdb's
+    /// `PutVarint64`. Returns `None` if there is no such entry or the value is
+    /// not exactly one varint.
+    pub fn get_varint_u64<Q: AsRef<[u8]>>(&self, index: Q) -> Option<u64> {
+        let v = self.get(index)?;
+        let mut n = 0u64;
+        for (i, b) in v.iter().enumerate().take(10) {
+            n |= u64::from(b & 0x7f) << (7 * i);
+            if b & 0x80 == 0 {
+                return if i + 1 == v.len() { Some(n) } else { None };
+            }
+        }
+        None
+    }
+
+    pub fn iter(&self) -> UserCollectedPropertiesIter {
+        UserCollectedPropertiesIter::new(self)
+    }
+
     pub fn len(&self) -> usize {
         unsafe { crocksdb_ffi::crocksdb_user_collected_properties_len(&self.inner) }
     }
@@ -18,6 +18,7 @@ use crate::table_properties_rc_handles::{
     TablePropertiesHandle, UserCollectedPropertiesHandle,
 };
 
+#[derive(Clone)]
 pub struct TablePropertiesCollection {
     handle: TablePropertiesCollectionHandle,
 }
@@ -131,6 +132,7 @@ impl Deref for TablePropertiesKey {
     }
 }
 
+#[derive(Clone)]
 pub struct TableProperties {
     handle: TablePropertiesHandle,
 }
@@ -153,6 +155,7 @@ impl TableProperties {
     }
 }
 
+#[derive(Clone)]
 pub struct UserCollectedProperties {
     handle: UserCollectedPropertiesHandle,
 }
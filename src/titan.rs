@@ -201,3 +201,23 @@ impl DerefMut for TitanBlobIndex {
         &mut self.inner
     }
 }
+
+/// Blob file statistics of a Titan column family.
+///
+/// The discardable ratio of a blob file is the fraction of its data that is
+/// no longer referenced, `num_discardable_ratio_le*_file` count live blob files
+/// by that ratio. Blob files whose ratio exceeds the `discardable_ratio` option
+/// are picked by GC, which is scheduled after compactions.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TitanBlobStats {
+    pub num_live_blob_file: u64,
+    pub num_obsolete_blob_file: u64,
+    pub live_blob_size: u64,
+    pub live_blob_file_size: u64,
+    pub obsolete_blob_file_size: u64,
+    pub num_discardable_ratio_le0_file: u64,
+    pub num_discardable_ratio_le20_file: u64,
+    pub num_discardable_ratio_le50_file: u64,
+    pub num_discardable_ratio_le80_file: u64,
+    pub num_discardable_ratio_le100_file: u64,
+}
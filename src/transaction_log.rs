@@ -0,0 +1,99 @@
+// Copyright 2023 TiKV Project Authors. Licensed under Apache-2.0.
+
+use crocksdb_ffi::{self, DBWalIterator};
+use std::marker::PhantomData;
+
+use crate::{WriteBatch, DB};
+
+/// An iterator over the write batches in the WAL files of a DB, created by
+/// `DB::get_updates_since`. Each item is the sequence number of the first
+/// operation in the batch together with the batch itself.
+///
+/// When created by `DB::get_updates_since_cf`, the batches only hold the
+/// operations on the requested column families and batches without any of
+/// them are skipped. The sequence number is still the one of the whole batch
+/// in the WAL.
+///
+/// `None` means the iterator has caught up with the latest write. An `Err`
+/// means the WAL can't be read any further, e.g. because the files holding
+/// the requested updates have been recycled.
+pub struct TransactionLogIterator<'a> {
+    inner: *mut DBWalIterator,
+    cf_ids: Option<Vec<u32>>,
+    done: bool,
+    _db: PhantomData<&'a DB>,
+}
+
+impl<'a> TransactionLogIterator<'a> {
+    pub(crate) unsafe fn from_raw(
+        inner: *mut DBWalIterator,
+        cf_ids: Option<Vec<u32>>,
+    ) -> TransactionLogIterator<'a> {
+        TransactionLogIterator {
+            inner,
+            cf_ids,
+            done: false,
+            _db: PhantomData,
+        }
+    }
+
+    fn status(&self) -> Result<(), String> {
+        unsafe {
+            ffi_try!(crocksdb_wal_iter_status(self.inner));
+        }
+        Ok(())
+    }
+}
+
+fn filter_cf(batch: &WriteBatch, cf_ids: &[u32]) -> Result<WriteBatch, String> {
+    unsafe {
+        let inner = ffi_try!(crocksdb_writebatch_filter_cf(
+            batch.inner,
+            cf_ids.as_ptr(),
+            cf_ids.len()
+        ));
+        Ok(WriteBatch { inner })
+    }
+}
+
+impl<'a> Iterator for TransactionLogIterator<'a> {
+    type Item = Result<(u64, WriteBatch), String>;
+
+    fn next(&mut self) -> Option<Result<(u64, WriteBatch), String>> {
+        if self.done {
+            return None;
+        }
+        loop {
+            unsafe {
+                if !crocksdb_ffi::crocksdb_wal_iter_valid(self.inner) {
+                    self.done = true;
+                    return self.status().err().map(Err);
+                }
+                let mut seq = 0;
+                let inner = crocksdb_ffi::crocksdb_wal_iter_get_batch(self.inner, &mut seq);
+                crocksdb_ffi::crocksdb_wal_iter_next(self.inner);
+                let batch = WriteBatch { inner };
+                let cf_ids = match self.cf_ids {
+                    Some(ref cf_ids) => cf_ids,
+                    None => return Some(Ok((seq, batch))),
+                };
+                match filter_cf(&batch, cf_ids) {
+                    Ok(filtered) if filtered.is_empty() => continue,
+                    Ok(filtered) => return Some(Ok((seq, filtered))),
+                    Err(e) => {
+                        self.done = true;
+                        return Some(Err(e));
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<'a> Drop for TransactionLogIterator<'a> {
+    fn drop(&mut self) {
+        unsafe {
+            crocksdb_ffi::crocksdb_wal_iter_destroy(self.inner);
+        }
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright 2023 TiKV Project Authors. Licensed under Apache-2.0.
+
+pub use crocksdb_ffi::WalFilterResult;
+use crocksdb_ffi::{self, DBWalFilter};
+use libc::{c_char, c_void, size_t};
+use std::ffi::CString;
+
+use crate::WriteBatch;
+
+/// `WalFilter` is called on every write batch replayed from the WAL files
+/// when a DB is opened, and decides whether the batch is applied.
+pub trait WalFilter: Send + Sync {
+    fn log_record(&self, batch: &WriteBatch) -> WalFilterResult;
+}
+
+struct WalFilterProxy<F: WalFilter> {
+    name: CString,
+    filter: F,
+}
+
+extern "C" fn name<F: WalFilter>(filter: *mut c_void) -> *const c_char {
+    unsafe { (*(filter as *mut WalFilterProxy<F>)).name.as_ptr() }
+}
+
+extern "C" fn destructor<F: WalFilter>(filter: *mut c_void) {
+    unsafe {
+        let _ = Box::from_raw(filter as *mut WalFilterProxy<F>);
+    }
+}
+
+extern "C" fn log_record<F: WalFilter>(
+    filter: *mut c_void,
+    rep: *const u8,
+    rep_len: size_t,
+) -> WalFilterResult {
+    unsafe {
+        let filter = &(*(filter as *mut WalFilterProxy<F>)).filter;
+        let batch = WriteBatch {
+            inner: crocksdb_ffi::crocksdb_writebatch_create_from(rep, rep_len),
+        };
+        filter.log_record(&batch)
+    }
+}
+
+pub struct WalFilterHandle {
+    pub(crate) inner: *mut DBWalFilter,
+}
+
+unsafe impl Send for WalFilterHandle {}
+unsafe impl Sync for WalFilterHandle {}
+
+impl Drop for WalFilterHandle {
+    fn drop(&mut self) {
+        unsafe {
+            crocksdb_ffi::crocksdb_walfilter_destroy(self.inner);
+        }
+    }
+}
+
+pub fn new_wal_filter<F: WalFilter>(c_name: CString, f: F) -> WalFilterHandle {
+    let proxy = Box::into_raw(Box::new(WalFilterProxy {
+        name: c_name,
+        filter: f,
+    }));
+    let inner = unsafe {
+        crocksdb_ffi::crocksdb_walfilter_create(
+            proxy as *mut c_void,
+            destructor::<F>,
+            log_record::<F>,
+            name::<F>,
+        )
+    };
+    WalFilterHandle { inner }
+}
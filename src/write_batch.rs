@@ -1,5 +1,7 @@
 use crocksdb_ffi::{self, DBValueType, DBWriteBatch, DBWriteBatchIterator};
 use libc::{c_void, size_t};
+use rocksdb::{CFHandle, Writable, DB};
+use rocksdb_options::WriteOptions;
 use std::marker::PhantomData;
 use std::slice;
 
@@ -104,6 +106,12 @@ impl WriteBatch {
         }
     }
 
+    /// Appends all the operations of `other` to the end of this batch,
+    /// preserving their order.
+    pub fn append_batch(&mut self, other: &WriteBatch) {
+        self.append(other.data());
+    }
+
     pub fn iterate<F>(&self, cfs: &[&str], mut iterator_fn: F)
     where
         F: FnMut(&str, DBValueType, &[u8], Option<&[u8]>),
@@ -132,6 +140,180 @@ impl WriteBatch {
     pub fn iter(&self) -> WriteBatchIter {
         WriteBatchIter::new(self)
     }
+
+    /// Calls the method of `handler` matching each operation in the batch,
+    /// in order.
+    pub fn iterate_with_handler(&self, handler: &mut dyn WriteBatchHandler) -> Result<(), String> {
+        let mut handler = handler;
+        unsafe {
+            ffi_try!(crocksdb_writebatch_iterate_handler(
+                self.inner,
+                &mut handler as *mut &mut dyn WriteBatchHandler as *mut c_void,
+                handler_put_cf,
+                handler_delete_cf,
+                handler_single_delete_cf,
+                handler_merge_cf,
+                handler_delete_range_cf
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Visits the operations of a `WriteBatch`, see
+/// `WriteBatch::iterate_with_handler`. `cf` is the column family id.
+pub trait WriteBatchHandler {
+    fn put(&mut self, cf: u32, key: &[u8], value: &[u8]);
+    fn delete(&mut self, cf: u32, key: &[u8]);
+    fn single_delete(&mut self, cf: u32, key: &[u8]) {
+        self.delete(cf, key)
+    }
+    fn merge(&mut self, cf: u32, key: &[u8], value: &[u8]);
+    fn delete_range(&mut self, cf: u32, begin_key: &[u8], end_key: &[u8]);
+}
+
+unsafe fn handler<'a>(state: *mut c_void) -> &'a mut dyn WriteBatchHandler {
+    &mut **(state as *mut &mut dyn WriteBatchHandler)
+}
+
+unsafe extern "C" fn handler_put_cf(
+    state: *mut c_void,
+    cf: u32,
+    k: *const u8,
+    klen: size_t,
+    v: *const u8,
+    vlen: size_t,
+) {
+    let (key, value) = (
+        slice::from_raw_parts(k, klen),
+        slice::from_raw_parts(v, vlen),
+    );
+    handler(state).put(cf, key, value);
+}
+
+unsafe extern "C" fn handler_delete_cf(state: *mut c_void, cf: u32, k: *const u8, klen: size_t) {
+    handler(state).delete(cf, slice::from_raw_parts(k, klen));
+}
+
+unsafe extern "C" fn handler_single_delete_cf(
+    state: *mut c_void,
+    cf: u32,
+    k: *const u8,
+    klen: size_t,
+) {
+    handler(state).single_delete(cf, slice::from_raw_parts(k, klen));
+}
+
+unsafe extern "C" fn handler_merge_cf(
+    state: *mut c_void,
+    cf: u32,
+    k: *const u8,
+    klen: size_t,
+    v: *const u8,
+    vlen: size_t,
+) {
+    let (key, value) = (
+        slice::from_raw_parts(k, klen),
+        slice::from_raw_parts(v, vlen),
+    );
+    handler(state).merge(cf, key, value);
+}
+
+unsafe extern "C" fn handler_delete_range_cf(
+    state: *mut c_void,
+    cf: u32,
+    b: *const u8,
+    blen: size_t,
+    e: *const u8,
+    elen: size_t,
+) {
+    let (begin_key, end_key) = (
+        slice::from_raw_parts(b, blen),
+        slice::from_raw_parts(e, elen),
+    );
+    handler(state).delete_range(cf, begin_key, end_key);
+}
+
+/// Collects operations on one or more column families of `db` and writes
+/// them atomically on `commit`. Created by `DB::batch`.
+///
+/// The first failed operation is kept and returned by `commit`, nothing is
+/// written in that case.
+pub struct BatchBuilder<'a> {
+    db: &'a DB,
+    batch: WriteBatch,
+    err: Option<String>,
+}
+
+impl<'a> BatchBuilder<'a> {
+    pub(crate) fn new(db: &'a DB) -> BatchBuilder<'a> {
+        BatchBuilder {
+            db,
+            batch: WriteBatch::new(),
+            err: None,
+        }
+    }
+
+    fn record(&mut self, res: Result<(), String>) -> &mut Self {
+        if let Err(e) = res {
+            self.err.get_or_insert(e);
+        }
+        self
+    }
+
+    pub fn put_cf(&mut self, cf: &CFHandle, key: &[u8], value: &[u8]) -> &mut Self {
+        let res = self.batch.put_cf(cf, key, value);
+        self.record(res)
+    }
+
+    /// Puts `key` at the user timestamp `ts`. `ts` must be as long as the
+    /// timestamps of `cf`.
+    pub fn put_cf_with_ts(
+        &mut self,
+        cf: &CFHandle,
+        key: &[u8],
+        ts: &[u8],
+        value: &[u8],
+    ) -> &mut Self {
+        let ts_sz = cf.timestamp_size();
+        let res = if ts.len() != ts_sz {
+            Err(format!(
+                "timestamp size mismatch: the column family expects {} bytes, got {}",
+                ts_sz,
+                ts.len()
+            ))
+        } else {
+            let mut key_with_ts = Vec::with_capacity(key.len() + ts.len());
+            key_with_ts.extend_from_slice(key);
+            key_with_ts.extend_from_slice(ts);
+            self.batch.put_cf(cf, &key_with_ts, value)
+        };
+        self.record(res)
+    }
+
+    pub fn merge_cf(&mut self, cf: &CFHandle, key: &[u8], value: &[u8]) -> &mut Self {
+        let res = self.batch.merge_cf(cf, key, value);
+        self.record(res)
+    }
+
+    pub fn delete_cf(&mut self, cf: &CFHandle, key: &[u8]) -> &mut Self {
+        let res = self.batch.delete_cf(cf, key);
+        self.record(res)
+    }
+
+    pub fn count(&self) -> usize {
+        self.batch.count()
+    }
+
+    pub fn commit(&mut self, writeopts: &WriteOptions) -> Result<(), String> {
+        if let Some(e) = self.err.take() {
+            self.batch.clear();
+            return Err(e);
+        }
+        let res = self.db.write_opt(&self.batch, writeopts);
+        self.batch.clear();
+        res
+    }
 }
 
 pub struct WriteBatchIter<'a> {
@@ -17,11 +17,16 @@ mod test_rate_limiter;
 mod test_read_only;
 mod test_rocksdb_options;
 mod test_slice_transform;
+mod test_sst_partitioner;
 mod test_statistics;
 mod test_table_properties;
 mod test_table_properties_rc;
 mod test_titan;
+mod test_transaction_log;
 mod test_ttl;
+mod test_user_timestamp;
+mod test_wal_filter;
+mod test_write_batch_with_index;
 
 fn tempdir_with_prefix(prefix: &str) -> tempfile::TempDir {
     tempfile::Builder::new().prefix(prefix).tempdir().expect("")
@@ -13,6 +13,9 @@
 // limitations under the License.
 //
 
+use std::fs;
+use std::sync::Mutex;
+
 use rocksdb::{ColumnFamilyOptions, DBOptions, Env, MergeOperands, Writable, DB};
 
 use super::tempdir_with_prefix;
@@ -148,7 +151,7 @@ fn test_provided_merge(
 
 #[test]
 pub fn test_column_family_option_use_doubly_skiplist() {
-    let cf_opts = ColumnFamilyOptions::new();
+    let mut cf_opts = ColumnFamilyOptions::new();
     let memtable_name = cf_opts.get_memtable_factory_name();
     assert!(memtable_name.is_some());
     assert_eq!("SkipListFactory", memtable_name.unwrap());
@@ -176,3 +179,87 @@ fn test_db_lock() {
     let _r1 = DB::open_for_read_only(opts.clone(), path, false).unwrap();
     assert_eq!(env.is_db_locked(path), Ok(false));
 }
+
+#[test]
+fn test_open_cf_auto() {
+    let path = tempdir_with_prefix("_rust_rocksdb_open_cf_auto");
+    let path_str = path.path().to_str().unwrap();
+
+    {
+        let mut opts = DBOptions::new();
+        opts.create_if_missing(true);
+        let mut db = DB::open_cf_auto(opts, path_str, |_| ColumnFamilyOptions::new()).unwrap();
+        assert_eq!(db.cf_names(), vec!["default"]);
+        for name in &["cf1", "cf2", "cf3"] {
+            db.create_cf(*name).unwrap();
+            let cf = db.cf_handle(name).unwrap();
+            db.put_cf(cf, b"k", name.as_bytes()).unwrap();
+        }
+    }
+
+    let opened = Mutex::new(vec![]);
+    let db = DB::open_cf_auto(DBOptions::new(), path_str, |name| {
+        opened.lock().unwrap().push(name.to_owned());
+        let mut cf_opts = ColumnFamilyOptions::new();
+        cf_opts.add_merge_operator("test operator", test_provided_merge);
+        cf_opts
+    })
+    .unwrap();
+    let mut opened = opened.into_inner().unwrap();
+    opened.sort_unstable();
+    assert_eq!(opened, vec!["cf1", "cf2", "cf3", "default"]);
+    let mut names = db.cf_names();
+    names.sort_unstable();
+    assert_eq!(names, vec!["cf1", "cf2", "cf3", "default"]);
+    for name in &["cf1", "cf2", "cf3"] {
+        let cf = db.cf_handle(name).unwrap();
+        assert_eq!(db.get_cf(cf, b"k").unwrap().unwrap(), name.as_bytes());
+        db.merge_cf(cf, b"k", b"+").unwrap();
+        let v = db.get_cf(cf, b"k").unwrap().unwrap();
+        assert_eq!(v.to_utf8().unwrap(), format!("{}+", name));
+    }
+}
+
+#[test]
+fn test_open_cf_auto_missing_db() {
+    let path = tempdir_with_prefix("_rust_rocksdb_open_cf_auto_missing");
+    let path_str = path.path().join("db");
+    let path_str = path_str.to_str().unwrap();
+    assert!(DB::open_cf_auto(DBOptions::new(), path_str, |_| ColumnFamilyOptions::new()).is_err());
+}
+
+#[test]
+fn test_open_cf_auto_corrupted_db() {
+    let path = tempdir_with_prefix("_rust_rocksdb_open_cf_auto_corrupted");
+    let path_str = path.path().to_str().unwrap();
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    let mut db = DB::open(opts, path_str).unwrap();
+    db.create_cf("cf1").unwrap();
+    drop(db);
+
+    // Failing to list the column families of an existing db is an error
+    // rather than a reason to only open the default one.
+    fs::write(path.path().join("CURRENT"), b"MANIFEST-999999\n").unwrap();
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    assert!(DB::open_cf_auto(opts, path_str, |_| ColumnFamilyOptions::new()).is_err());
+}
+
+#[test]
+fn test_cf_timestamp_size() {
+    let path = tempdir_with_prefix("_rust_rocksdb_cf_timestamp_size");
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let mut ts_opts = ColumnFamilyOptions::new();
+    ts_opts.set_bytewise_comparator_with_u64_ts();
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![("default", ColumnFamilyOptions::new()), ("ts", ts_opts)],
+    )
+    .unwrap();
+    assert_eq!(db.cf_timestamp_size(db.cf_handle("default").unwrap()), 0);
+    assert_eq!(db.cf_timestamp_size(db.cf_handle("ts").unwrap()), 8);
+}
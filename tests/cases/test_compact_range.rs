@@ -130,3 +130,81 @@ fn test_compact_range_bottommost_level_compaction() {
     assert_eq!(bottommost_files.len(), 1);
     assert_ne!(bottommost_filename, bottommost_files[0].get_name());
 }
+
+#[test]
+fn test_compact_range_auto_target_level() {
+    let path = tempdir_with_prefix("_rust_rocksdb_test_compact_range_auto_target_level");
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    let mut cf_opts = ColumnFamilyOptions::new();
+    cf_opts.set_disable_auto_compactions(true);
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![("default", cf_opts)],
+    )
+    .unwrap();
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    for i in 0..5u8 {
+        db.put(&[i], b"value").unwrap();
+        db.flush(&fopts).unwrap();
+    }
+    let cf_handle = db.cf_handle("default").unwrap();
+    let metadata = db.get_column_family_meta_data(cf_handle);
+    assert_eq!(metadata.get_levels()[0].get_files().len(), 5);
+
+    // A negative target level lets rocksdb pick the level to move to.
+    let mut compact_opts = CompactOptions::new();
+    compact_opts.set_change_level(true);
+    compact_opts.set_target_level(-1);
+    compact_opts.set_bottommost_level_compaction(DBBottommostLevelCompaction::Force);
+    db.compact_range_cf_opt(cf_handle, &compact_opts, None, None);
+
+    let metadata = db.get_column_family_meta_data(cf_handle);
+    let levels = metadata.get_levels();
+    assert!(levels[0].get_files().is_empty());
+    let num_files: usize = levels.iter().map(|l| l.get_files().len()).sum();
+    assert_eq!(num_files, 1);
+    for i in 0..5u8 {
+        assert_eq!(db.get(&[i]).unwrap().unwrap(), b"value");
+    }
+}
+
+#[test]
+fn test_compact_range_cf_full_range() {
+    let path = tempdir_with_prefix("_rust_rocksdb_test_compact_range_cf_full_range");
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let mut cf_opts = ColumnFamilyOptions::new();
+    cf_opts.set_level_zero_file_num_compaction_trigger(10);
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![("default", ColumnFamilyOptions::new()), ("cf1", cf_opts)],
+    )
+    .unwrap();
+    let cf = db.cf_handle("cf1").unwrap();
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    for i in 0..4 {
+        let key = format!("k{}", i);
+        db.put_cf(cf, key.as_bytes(), b"v").unwrap();
+        db.flush_cf(cf, &fopts).unwrap();
+    }
+    let files_at_level = |level: usize| {
+        db.get_property_int_cf(cf, &format!("rocksdb.num-files-at-level{}", level))
+            .unwrap()
+    };
+    assert_eq!(files_at_level(0), 4);
+
+    // No bounds means the whole column family.
+    db.compact_range_cf(cf, None, None);
+    assert_eq!(files_at_level(0), 0);
+    assert_eq!(files_at_level(1), 1);
+    for i in 0..4 {
+        let key = format!("k{}", i);
+        assert_eq!(db.get_cf(cf, key.as_bytes()).unwrap().unwrap(), b"v");
+    }
+}
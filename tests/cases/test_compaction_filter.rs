@@ -11,13 +11,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, RwLock};
+use std::ffi::CString;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 
 use rocksdb::CompactionFilterDecision;
 use rocksdb::CompactionFilterValueType;
 use rocksdb::TitanDBOptions;
-use rocksdb::{ColumnFamilyOptions, CompactionFilter, DBOptions, Writable, DB};
+use rocksdb::{
+    ColumnFamilyOptions, CompactionFilter, CompactionFilterContext, CompactionFilterFactory,
+    DBOptions, DBTableFileCreationReason, FlushOptions, Writable, DB,
+};
 
 use super::tempdir_with_prefix;
 
@@ -111,3 +115,310 @@ fn test_compaction_filter_impl(titan: bool) {
     }
     assert!(drop_called.load(Ordering::Relaxed));
 }
+
+struct NoopFilter;
+impl CompactionFilter for NoopFilter {}
+
+#[derive(Clone, Default)]
+struct RangeRecorder {
+    ranges: Arc<Mutex<Vec<(Vec<u8>, Vec<u8>)>>>,
+}
+
+impl CompactionFilterFactory for RangeRecorder {
+    type Filter = NoopFilter;
+
+    fn create_compaction_filter(
+        &self,
+        context: &CompactionFilterContext,
+    ) -> Option<(CString, Self::Filter)> {
+        self.ranges
+            .lock()
+            .unwrap()
+            .push((context.start_key().to_vec(), context.end_key().to_vec()));
+        Some((CString::new("noop").unwrap(), NoopFilter))
+    }
+}
+
+#[test]
+fn test_compaction_filter_context_key_range() {
+    let path = tempdir_with_prefix("_rust_rocksdb_compaction_filter_context_key_range");
+    let recorder = RangeRecorder::default();
+    let mut cf_opts = ColumnFamilyOptions::new();
+    cf_opts.set_disable_auto_compactions(true);
+    cf_opts
+        .set_compaction_filter_factory("range_recorder", recorder.clone())
+        .unwrap();
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![("default", cf_opts)],
+    )
+    .unwrap();
+
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    for prefix in &["a", "b"] {
+        for i in 0..10 {
+            db.put(format!("{}{:02}", prefix, i).as_bytes(), b"v")
+                .unwrap();
+        }
+        db.flush(&fopts).unwrap();
+    }
+
+    // Only the file holding the "b" keys overlaps the compacted range.
+    db.compact_range(Some(b"b"), Some(b"c"));
+    let ranges = recorder.ranges.lock().unwrap();
+    assert_eq!(ranges.len(), 1);
+    let (start, end) = &ranges[0];
+    assert!(start.as_slice() <= end.as_slice());
+    assert!(start.as_slice() >= &b"b00"[..]);
+    assert!(end.as_slice() <= &b"b09"[..]);
+}
+
+#[derive(Clone, Default)]
+struct FlagRecorder {
+    // (is_full_compaction, is_manual_compaction, is_bottommost_level)
+    flags: Arc<Mutex<Vec<(bool, bool, bool)>>>,
+}
+
+impl CompactionFilterFactory for FlagRecorder {
+    type Filter = NoopFilter;
+
+    fn create_compaction_filter(
+        &self,
+        context: &CompactionFilterContext,
+    ) -> Option<(CString, Self::Filter)> {
+        self.flags.lock().unwrap().push((
+            context.is_full_compaction(),
+            context.is_manual_compaction(),
+            context.is_bottommost_level(),
+        ));
+        Some((CString::new("noop").unwrap(), NoopFilter))
+    }
+}
+
+#[test]
+fn test_compaction_filter_context_flags() {
+    let path = tempdir_with_prefix("_rust_rocksdb_compaction_filter_context_flags");
+    let recorder = FlagRecorder::default();
+    let mut cf_opts = ColumnFamilyOptions::new();
+    cf_opts.set_disable_auto_compactions(true);
+    cf_opts
+        .set_compaction_filter_factory("flag_recorder", recorder.clone())
+        .unwrap();
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![("default", cf_opts)],
+    )
+    .unwrap();
+
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    for i in 0..3 {
+        db.put(format!("k{}", i).as_bytes(), b"v").unwrap();
+        db.flush(&fopts).unwrap();
+    }
+    db.compact_range(None, None);
+
+    let flags = recorder.flags.lock().unwrap();
+    assert_eq!(*flags, vec![(true, true, true)]);
+}
+
+struct DropZFilter;
+
+impl CompactionFilter for DropZFilter {
+    fn filter(&mut self, _: usize, key: &[u8], _: &[u8], _: &mut Vec<u8>, _: &mut bool) -> bool {
+        key.starts_with(b"z")
+    }
+}
+
+#[test]
+fn test_stateless_compaction_filter() {
+    let path = tempdir_with_prefix("_rust_rocksdb_stateless_compaction_filter");
+    let mut cf_opts = ColumnFamilyOptions::new();
+    cf_opts
+        .set_compaction_filter("drop_z", DropZFilter)
+        .unwrap();
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![("default", cf_opts)],
+    )
+    .unwrap();
+
+    for k in &[b"a1", b"z1", b"b1", b"z2"] {
+        db.put(*k, b"v").unwrap();
+    }
+    db.compact_range(None, None);
+    assert!(db.get(b"z1").unwrap().is_none());
+    assert!(db.get(b"z2").unwrap().is_none());
+    assert_eq!(db.get(b"a1").unwrap().unwrap(), b"v");
+    assert_eq!(db.get(b"b1").unwrap().unwrap(), b"v");
+}
+
+struct DropPrefixFilter {
+    prefix: Vec<u8>,
+}
+
+impl CompactionFilter for DropPrefixFilter {
+    fn filter(&mut self, _: usize, key: &[u8], _: &[u8], _: &mut Vec<u8>, _: &mut bool) -> bool {
+        key.starts_with(&self.prefix)
+    }
+}
+
+struct DropPrefixConfig {
+    prefix: Vec<u8>,
+}
+
+impl DropPrefixConfig {
+    fn factory(&self) -> DropPrefixFactory {
+        DropPrefixFactory {
+            prefix: self.prefix.clone(),
+            created: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+}
+
+struct DropPrefixFactory {
+    prefix: Vec<u8>,
+    created: Arc<AtomicUsize>,
+}
+
+impl CompactionFilterFactory for DropPrefixFactory {
+    type Filter = DropPrefixFilter;
+
+    fn create_compaction_filter(
+        &self,
+        _: &CompactionFilterContext,
+    ) -> Option<(CString, Self::Filter)> {
+        self.created.fetch_add(1, Ordering::SeqCst);
+        let filter = DropPrefixFilter {
+            prefix: self.prefix.clone(),
+        };
+        Some((CString::new("drop_prefix").unwrap(), filter))
+    }
+}
+
+#[test]
+fn test_compaction_filter_factory_per_cf() {
+    let path = tempdir_with_prefix("_rust_rocksdb_compaction_filter_factory_per_cf");
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    let mut db = DB::open(opts, path.path().to_str().unwrap()).unwrap();
+
+    let config = DropPrefixConfig {
+        prefix: b"z".to_vec(),
+    };
+    let mut counters = vec![];
+    for name in &["cf1", "cf2"] {
+        let factory = config.factory();
+        counters.push(factory.created.clone());
+        let mut cf_opts = ColumnFamilyOptions::new();
+        cf_opts
+            .set_compaction_filter_factory("drop_prefix", factory)
+            .unwrap();
+        db.create_cf((*name, cf_opts)).unwrap();
+    }
+
+    for name in &["cf1", "cf2"] {
+        let cf = db.cf_handle(name).unwrap();
+        db.put_cf(cf, b"a", b"v").unwrap();
+        db.put_cf(cf, b"z", b"v").unwrap();
+        db.compact_range_cf(cf, None, None);
+    }
+    for (name, created) in ["cf1", "cf2"].iter().zip(&counters) {
+        let cf = db.cf_handle(name).unwrap();
+        assert_eq!(db.get_cf(cf, b"a").unwrap().unwrap(), b"v");
+        assert!(db.get_cf(cf, b"z").unwrap().is_none());
+        assert!(created.load(Ordering::SeqCst) > 0);
+    }
+}
+
+struct FlushOnlyFilter {
+    enabled: Arc<AtomicBool>,
+}
+
+impl CompactionFilter for FlushOnlyFilter {
+    fn filter(&mut self, _: usize, key: &[u8], _: &[u8], _: &mut Vec<u8>, _: &mut bool) -> bool {
+        self.enabled.load(Ordering::SeqCst) && key.starts_with(b"z")
+    }
+}
+
+struct FlushOnlyFactory {
+    enabled: Arc<AtomicBool>,
+    reasons: Arc<Mutex<Vec<DBTableFileCreationReason>>>,
+}
+
+impl CompactionFilterFactory for FlushOnlyFactory {
+    type Filter = FlushOnlyFilter;
+
+    fn should_filter_table_file_creation(&self, reason: DBTableFileCreationReason) -> bool {
+        matches!(reason, DBTableFileCreationReason::Flush)
+    }
+
+    fn create_compaction_filter(
+        &self,
+        context: &CompactionFilterContext,
+    ) -> Option<(CString, Self::Filter)> {
+        self.reasons.lock().unwrap().push(context.reason());
+        let filter = FlushOnlyFilter {
+            enabled: self.enabled.clone(),
+        };
+        Some((CString::new("flush_only").unwrap(), filter))
+    }
+}
+
+#[test]
+fn test_compaction_filter_flush_only() {
+    let path = tempdir_with_prefix("_rust_rocksdb_compaction_filter_flush_only");
+    let enabled = Arc::new(AtomicBool::new(true));
+    let reasons = Arc::new(Mutex::new(vec![]));
+    let mut cf_opts = ColumnFamilyOptions::new();
+    cf_opts
+        .set_compaction_filter_factory(
+            "flush_only",
+            FlushOnlyFactory {
+                enabled: enabled.clone(),
+                reasons: reasons.clone(),
+            },
+        )
+        .unwrap();
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![("default", cf_opts)],
+    )
+    .unwrap();
+
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    db.put(b"a1", b"v").unwrap();
+    db.put(b"z1", b"v").unwrap();
+    db.flush(&fopts).unwrap();
+    // Flush-created tables go through the filter.
+    assert_eq!(db.get(b"a1").unwrap().unwrap(), b"v");
+    assert!(db.get(b"z1").unwrap().is_none());
+
+    // Let `z2` reach an SST file, then make sure compaction doesn't drop it.
+    enabled.store(false, Ordering::SeqCst);
+    db.put(b"z2", b"v").unwrap();
+    db.flush(&fopts).unwrap();
+    enabled.store(true, Ordering::SeqCst);
+    db.compact_range(None, None);
+    assert_eq!(db.get(b"z2").unwrap().unwrap(), b"v");
+
+    let reasons = reasons.lock().unwrap();
+    assert_eq!(reasons.len(), 2);
+    assert!(reasons
+        .iter()
+        .all(|r| matches!(r, DBTableFileCreationReason::Flush)));
+}
@@ -11,8 +11,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "encryption")]
+use std::collections::HashMap;
+#[cfg(feature = "encryption")]
+use std::io;
 use std::sync::Arc;
+#[cfg(feature = "encryption")]
+use std::sync::Mutex;
+#[cfg(feature = "encryption")]
+use std::thread;
+#[cfg(feature = "encryption")]
+use std::time::{Duration, Instant};
 
+#[cfg(feature = "encryption")]
+use rocksdb::{DBEncryptionMethod, EncryptionKeyManager, FileEncryptionInfo};
 use rocksdb::{DBOptions, Env, FlushOptions, Writable, DB};
 
 use super::tempdir_with_prefix;
@@ -36,6 +48,21 @@ fn test_ctr_encrypted_env() {
     }
 }
 
+#[test]
+fn test_ctr_encrypted_env_nested_path() {
+    let path = tempdir_with_prefix("_rust_rocksdb_cryption_env_nested_path");
+    let db_path = path.path().join("a").join("b").join("db");
+    let env = Env::new_default_ctr_encrypted_env(&[8, 7, 6, 5, 4, 3, 2, 1]).unwrap();
+
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    opts.set_env(Arc::new(env));
+    let db = DB::open(opts, db_path.to_str().unwrap()).unwrap();
+    db.put(b"key1", b"value1").unwrap();
+    assert_eq!(db.get(b"key1").unwrap().unwrap(), b"value1");
+    assert!(db_path.join("CURRENT").exists());
+}
+
 fn test_ctr_encrypted_env_impl(encrypted_env: Arc<Env>) {
     let path = tempdir_with_prefix("_rust_rocksdb_cryption_env");
     let path_str = path.path().to_str().unwrap();
@@ -80,3 +107,109 @@ fn test_ctr_encrypted_env_impl(encrypted_env: Arc<Env>) {
         assert_eq!(v.as_slice(), &*db.get(k).unwrap().unwrap());
     }
 }
+
+#[cfg(feature = "encryption")]
+#[derive(Clone, Default)]
+struct RecordingKeyManager {
+    files: Arc<Mutex<HashMap<String, FileEncryptionInfo>>>,
+    deleted: Arc<Mutex<Vec<String>>>,
+}
+
+#[cfg(feature = "encryption")]
+impl EncryptionKeyManager for RecordingKeyManager {
+    fn get_file(&self, fname: &str) -> io::Result<FileEncryptionInfo> {
+        let files = self.files.lock().unwrap();
+        Ok(files.get(fname).cloned().unwrap_or(FileEncryptionInfo {
+            method: DBEncryptionMethod::Plaintext,
+            key: vec![],
+            iv: vec![],
+        }))
+    }
+
+    fn new_file(&self, fname: &str) -> io::Result<FileEncryptionInfo> {
+        let info = FileEncryptionInfo {
+            method: DBEncryptionMethod::Aes128Ctr,
+            key: vec![7; 16],
+            iv: vec![9; 16],
+        };
+        let mut files = self.files.lock().unwrap();
+        files.insert(fname.to_owned(), info.clone());
+        Ok(info)
+    }
+
+    fn delete_file(&self, fname: &str, _physical_fname: Option<&str>) -> io::Result<()> {
+        self.files.lock().unwrap().remove(fname);
+        self.deleted.lock().unwrap().push(fname.to_owned());
+        Ok(())
+    }
+
+    fn link_file(&self, src_fname: &str, dst_fname: &str) -> io::Result<()> {
+        let mut files = self.files.lock().unwrap();
+        if let Some(info) = files.get(src_fname).cloned() {
+            files.insert(dst_fname.to_owned(), info);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "encryption")]
+#[test]
+fn test_key_manager_delete_file_on_compaction() {
+    let path = tempdir_with_prefix("_rust_rocksdb_key_manager_delete_file");
+    let path_str = path.path().to_str().unwrap();
+    let key_manager = RecordingKeyManager::default();
+    let env =
+        Env::new_key_managed_encrypted_env(Arc::new(Env::default()), key_manager.clone()).unwrap();
+
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    opts.set_env(Arc::new(env));
+    let db = DB::open(opts, path_str).unwrap();
+
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    for i in 0..3 {
+        db.put(format!("k{}", i).as_bytes(), b"v").unwrap();
+        db.flush(&fopts).unwrap();
+    }
+    let is_sst = |name: &String| name.ends_with(".sst");
+    let ssts_before: Vec<String> = key_manager
+        .files
+        .lock()
+        .unwrap()
+        .keys()
+        .filter(|n| is_sst(n))
+        .cloned()
+        .collect();
+    assert_eq!(ssts_before.len(), 3);
+    assert!(!key_manager.deleted.lock().unwrap().iter().any(is_sst));
+
+    // Compaction replaces the flushed files, whose keys must be dropped.
+    db.compact_range(None, None);
+    // Obsolete files are purged by the background job after the manual
+    // compaction returns.
+    let all_deleted = || {
+        let deleted = key_manager.deleted.lock().unwrap();
+        ssts_before.iter().all(|sst| deleted.contains(sst))
+    };
+    let start = Instant::now();
+    while !all_deleted() {
+        assert!(
+            start.elapsed() < Duration::from_secs(10),
+            "{:?} are not all deleted",
+            ssts_before
+        );
+        thread::sleep(Duration::from_millis(10));
+    }
+    let live_ssts = key_manager
+        .files
+        .lock()
+        .unwrap()
+        .keys()
+        .filter(|n| is_sst(n))
+        .count();
+    assert_eq!(live_ssts, 1);
+    for i in 0..3 {
+        assert_eq!(db.get(format!("k{}", i).as_bytes()).unwrap().unwrap(), b"v");
+    }
+}
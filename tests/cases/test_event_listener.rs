@@ -12,9 +12,11 @@
 // limitations under the License.
 
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::*;
-use std::sync::Arc;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 
 use rocksdb::*;
 
@@ -343,6 +345,152 @@ fn test_event_listener_status_reset() {
     assert_eq!(counter.load(Ordering::SeqCst), 1);
 }
 
+#[derive(Default, Clone)]
+struct FlushedFileRecorder {
+    // (file path, smallest seqno, largest seqno, triggered writes slowdown)
+    files: Arc<Mutex<Vec<(PathBuf, u64, u64, bool)>>>,
+}
+
+impl EventListener for FlushedFileRecorder {
+    fn on_flush_completed(&self, info: &FlushJobInfo) {
+        self.files.lock().unwrap().push((
+            info.file_path().to_path_buf(),
+            info.smallest_seqno(),
+            info.largest_seqno(),
+            info.triggered_writes_slowdown(),
+        ));
+    }
+}
+
+#[test]
+fn test_event_listener_flushed_file_path() {
+    let path = tempdir_with_prefix("_rust_rocksdb_event_listener_flushed_file_path");
+    let path_str = path.path().to_str().unwrap();
+
+    let mut opts = DBOptions::new();
+    let recorder = FlushedFileRecorder::default();
+    opts.add_event_listener(recorder.clone());
+    opts.create_if_missing(true);
+    let db = DB::open(opts, path_str).unwrap();
+
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    db.put(b"k1", b"v1").unwrap();
+    db.put(b"k2", b"v2").unwrap();
+    db.flush(&fopts).unwrap();
+    db.put(b"k3", b"v3").unwrap();
+    db.flush(&fopts).unwrap();
+
+    let files = recorder.files.lock().unwrap().clone();
+    assert_eq!(files.len(), 2);
+    assert_eq!((files[0].1, files[0].2), (1, 2));
+    assert_eq!((files[1].1, files[1].2), (3, 3));
+    let live_files = db.get_live_files();
+    for (file_path, _, _, triggered_writes_slowdown) in &files {
+        assert!(file_path.exists());
+        assert!(!triggered_writes_slowdown);
+        let file_name = file_path.file_name().unwrap().to_str().unwrap();
+        assert!((0..live_files.get_files_count() as i32)
+            .any(|i| live_files.get_name(i).ends_with(file_name)));
+    }
+}
+
+#[derive(Clone, Default)]
+struct FaultyInspector(Arc<AtomicBool>);
+
+impl FileSystemInspector for FaultyInspector {
+    fn read(&self, len: usize) -> Result<usize, String> {
+        Ok(len)
+    }
+
+    fn write(&self, len: usize) -> Result<usize, String> {
+        if self.0.load(Ordering::SeqCst) {
+            Err("injected write error".to_owned())
+        } else {
+            Ok(len)
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+struct BackgroundErrorRecoverer {
+    suppress: bool,
+    background_error: Arc<AtomicUsize>,
+    recovery_completed: Arc<AtomicUsize>,
+}
+
+impl EventListener for BackgroundErrorRecoverer {
+    fn on_background_error(&self, _: DBBackgroundErrorReason, s: MutableStatus) {
+        assert!(s.result().is_err());
+        assert_eq!(s.severity(), DBStatusSeverity::HardError);
+        if self.suppress {
+            s.try_reset().unwrap();
+        }
+        self.background_error.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn on_error_recovery_completed(&self, old_bg_error: Result<(), String>) {
+        assert!(old_bg_error.is_err());
+        self.recovery_completed.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn open_with_faulty_env(path: &str, listener: BackgroundErrorRecoverer) -> (DB, Arc<AtomicBool>) {
+    let inspector = FaultyInspector::default();
+    let fail = inspector.0.clone();
+    let env = Env::new_file_system_inspected_env(Arc::new(Env::default()), inspector).unwrap();
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    opts.set_env(Arc::new(env));
+    opts.add_event_listener(listener);
+    (DB::open(opts, path).unwrap(), fail)
+}
+
+#[test]
+fn test_event_listener_suppress_background_error() {
+    let path = tempdir_with_prefix("_rust_rocksdb_event_listener_suppress_bg_error");
+    let listener = BackgroundErrorRecoverer {
+        suppress: true,
+        ..Default::default()
+    };
+    let (db, fail) = open_with_faulty_env(path.path().to_str().unwrap(), listener.clone());
+
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    db.put(b"k1", b"v1").unwrap();
+    fail.store(true, Ordering::SeqCst);
+    assert!(db.flush(&fopts).is_err());
+    fail.store(false, Ordering::SeqCst);
+    assert_eq!(listener.background_error.load(Ordering::SeqCst), 1);
+
+    // The error is cleared, so the db keeps accepting writes.
+    db.put(b"k2", b"v2").unwrap();
+    db.flush(&fopts).unwrap();
+    assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+    assert_eq!(listener.recovery_completed.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn test_event_listener_error_recovery_completed() {
+    let path = tempdir_with_prefix("_rust_rocksdb_event_listener_error_recovery");
+    let listener = BackgroundErrorRecoverer::default();
+    let (db, fail) = open_with_faulty_env(path.path().to_str().unwrap(), listener.clone());
+
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    db.put(b"k1", b"v1").unwrap();
+    fail.store(true, Ordering::SeqCst);
+    assert!(db.flush(&fopts).is_err());
+    fail.store(false, Ordering::SeqCst);
+    assert_eq!(listener.background_error.load(Ordering::SeqCst), 1);
+    assert!(db.put(b"k2", b"v2").is_err());
+
+    db.resume().unwrap();
+    assert_eq!(listener.recovery_completed.load(Ordering::SeqCst), 1);
+    db.put(b"k2", b"v2").unwrap();
+    assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+}
+
 fn disturb_sst_file(db: &DB, path: &Path) {
     let files = db.get_live_files();
     let mut file_name = files.get_name(0);
@@ -361,3 +509,129 @@ fn compact_files_to_bottom(db: &DB) {
     // output_level should be from 0 to 6.
     db.compact_range(None, None);
 }
+
+#[derive(Default, Clone)]
+struct SubcompactionCounter {
+    begin: Arc<AtomicUsize>,
+    completed: Arc<AtomicUsize>,
+}
+
+impl EventListener for SubcompactionCounter {
+    fn on_subcompaction_begin(&self, info: &SubcompactionJobInfo) {
+        assert_eq!(info.cf_name(), "default");
+        self.begin.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn on_subcompaction_completed(&self, info: &SubcompactionJobInfo) {
+        info.status().unwrap();
+        self.completed.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn test_event_listener_subcompaction() {
+    let path = tempdir_with_prefix("_rust_rocksdb_event_listener_subcompaction");
+    let counter = SubcompactionCounter::default();
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    opts.set_max_subcompactions(4);
+    opts.add_event_listener(counter.clone());
+    let mut cf_opts = ColumnFamilyOptions::new();
+    cf_opts.set_disable_auto_compactions(true);
+    // Subcompactions are bounded by the number of output files, keep them small.
+    cf_opts.set_target_file_size_base(32 * 1024);
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![("default", cf_opts)],
+    )
+    .unwrap();
+
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    let value = vec![b'v'; 256];
+    let fill = || {
+        for round in 0..4 {
+            for i in (round..4000).step_by(4) {
+                db.put(format!("k{:06}", i).as_bytes(), &value).unwrap();
+            }
+            db.flush(&fopts).unwrap();
+        }
+    };
+    // The first compaction moves everything to L1, only compactions into a
+    // non-empty level are split.
+    fill();
+    db.compact_range(None, None);
+    fill();
+    db.compact_range(None, None);
+
+    assert!(counter.begin.load(Ordering::SeqCst) > 1);
+    assert_eq!(
+        counter.begin.load(Ordering::SeqCst),
+        counter.completed.load(Ordering::SeqCst)
+    );
+}
+
+/// Blocks flushes in `on_flush_begin` until it's opened.
+#[derive(Default, Clone)]
+pub struct FlushGate {
+    open: Arc<(Mutex<bool>, Condvar)>,
+}
+
+impl FlushGate {
+    pub fn open(&self) {
+        let (lock, cvar) = &*self.open;
+        *lock.lock().unwrap() = true;
+        cvar.notify_all();
+    }
+}
+
+impl EventListener for FlushGate {
+    fn on_flush_begin(&self, _: &FlushJobInfo) {
+        let (lock, cvar) = &*self.open;
+        let mut open = lock.lock().unwrap();
+        while !*open {
+            open = cvar.wait(open).unwrap();
+        }
+    }
+}
+
+#[test]
+fn test_background_activity() {
+    let path = tempdir_with_prefix("_rust_rocksdb_test_background_activity");
+    let gate = FlushGate::default();
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    opts.add_event_listener(gate.clone());
+    let db = DB::open(opts, path.path().to_str().unwrap()).unwrap();
+    let cf = db.cf_handle("default").unwrap();
+    assert_eq!(
+        db.background_activity_cf(cf).unwrap(),
+        BackgroundActivity::default()
+    );
+
+    db.put(b"k1", b"v1").unwrap();
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(false);
+    db.flush(&fopts).unwrap();
+    let start = Instant::now();
+    loop {
+        let activity = db.background_activity_cf(cf).unwrap();
+        if activity.num_running_flushes > 0 {
+            break;
+        }
+        if start.elapsed() > Duration::from_secs(10) {
+            // Let the flush finish so that the DB can be closed.
+            gate.open();
+            panic!("no running flush: {:?}", activity);
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    gate.open();
+    fopts.set_wait(true);
+    db.flush(&fopts).unwrap();
+    let activity = db.background_activity_cf(cf).unwrap();
+    assert_eq!(activity.num_running_flushes, 0);
+    assert!(!activity.mem_table_flush_pending);
+}
@@ -442,6 +442,83 @@ fn test_mem_sst_file_writer() {
     assert!(env.file_exists(mem_sst_str).is_err());
 }
 
+#[test]
+fn test_ingest_external_file_from_buffer() {
+    let path = tempdir_with_prefix("_rust_rocksdb_ingest_external_file_from_buffer");
+    let db = create_default_database(&path);
+    db.put(b"k2", b"old").unwrap();
+    db.put(b"k4", b"v4").unwrap();
+    let seq = db.get_latest_sequence_number();
+
+    let env = Arc::new(Env::new_mem());
+    let mut opts = db.get_options().clone();
+    opts.set_env(env.clone());
+    let mem_sst_path = path.path().join("mem_sst");
+    let mem_sst_str = mem_sst_path.to_str().unwrap();
+    gen_sst(
+        opts,
+        None,
+        mem_sst_str,
+        &[(b"k1", b"v1"), (b"k2", b"v2"), (b"k3", b"v3")],
+    );
+    let mut buf = Vec::new();
+    env.new_sequential_file(mem_sst_str, EnvOptions::new())
+        .unwrap()
+        .read_to_end(&mut buf)
+        .unwrap();
+
+    let cf = db.cf_handle("default").unwrap();
+    let ingest_opts = IngestExternalFileOptions::new();
+    db.ingest_external_file_from_buffer_cf(cf, &ingest_opts, &buf)
+        .unwrap();
+    check_kv(
+        &db,
+        None,
+        &[
+            (b"k1", Some(b"v1")),
+            (b"k2", Some(b"v2")),
+            (b"k3", Some(b"v3")),
+            (b"k4", Some(b"v4")),
+        ],
+    );
+    // The file overlaps with the memtable, so it gets a new sequence number.
+    assert_eq!(db.get_latest_sequence_number(), seq + 1);
+    for entry in fs::read_dir(path.path()).unwrap() {
+        let name = entry.unwrap().file_name();
+        assert!(!name.to_str().unwrap().ends_with(".tmp"), "{:?}", name);
+    }
+
+    assert!(db
+        .ingest_external_file_from_buffer_cf(cf, &ingest_opts, b"not an sst")
+        .is_err());
+    for entry in fs::read_dir(path.path()).unwrap() {
+        let name = entry.unwrap().file_name();
+        assert!(!name.to_str().unwrap().ends_with(".tmp"), "{:?}", name);
+    }
+    drop(db);
+
+    // The buffer is written through the env of the DB.
+    let mem_path = tempdir_with_prefix("_rust_rocksdb_ingest_external_file_from_buffer_mem");
+    let mem_db_path = mem_path.path().join("db");
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    opts.set_env(env);
+    let db = DB::open(opts, mem_db_path.to_str().unwrap()).unwrap();
+    let cf = db.cf_handle("default").unwrap();
+    db.ingest_external_file_from_buffer_cf(cf, &ingest_opts, &buf)
+        .unwrap();
+    check_kv(
+        &db,
+        None,
+        &[
+            (b"k1", Some(b"v1")),
+            (b"k2", Some(b"v2")),
+            (b"k3", Some(b"v3")),
+        ],
+    );
+    assert!(!mem_db_path.exists());
+}
+
 #[test]
 fn test_set_external_sst_file_global_seq_no() {
     let db_path = tempdir_with_prefix("_rust_rocksdb_set_external_sst_file_global_seq_no_db");
@@ -535,6 +612,58 @@ fn test_read_sst() {
     );
 }
 
+/// Collects the keys from the current position to the end of `it`.
+fn keys_from<D>(it: &mut DBIterator<D>) -> Vec<Vec<u8>> {
+    let mut keys = vec![];
+    while it.valid().unwrap() {
+        keys.push(it.key().to_vec());
+        it.next().unwrap();
+    }
+    keys
+}
+
+#[test]
+fn test_read_sst_seek() {
+    let dir = tempdir_with_prefix("_rust_rocksdb_test_read_sst_seek");
+    let db = create_default_database(&dir);
+    let sst_path = dir.path().join("sst");
+    let sst_path_str = sst_path.to_str().unwrap();
+    let data: Vec<_> = (0..10u8).map(|i| (vec![b'k', i * 2], vec![i])).collect();
+    let kvs: Vec<_> = data
+        .iter()
+        .map(|(k, v)| (k.as_slice(), v.as_slice()))
+        .collect();
+    gen_sst(ColumnFamilyOptions::new(), None, sst_path_str, &kvs);
+    for (k, v) in &kvs {
+        db.put(k, v).unwrap();
+    }
+
+    let mut reader = SstFileReader::new(ColumnFamilyOptions::default());
+    reader.open(sst_path_str).unwrap();
+    let mut it = reader.iter();
+    // Seeks to the first key not less than the target.
+    assert!(it.seek(SeekKey::Key(&[b'k', 9])).unwrap());
+    assert_eq!(it.key(), &[b'k', 10]);
+    assert_eq!(it.value(), &[5]);
+    let keys = keys_from(&mut it);
+    assert_eq!(keys.len(), 5);
+    assert!(keys.windows(2).all(|w| w[0] < w[1]));
+
+    // The same code walks an iterator of the DB holding the same data.
+    let mut db_it = db.iter();
+    db_it.seek(SeekKey::Key(&[b'k', 9])).unwrap();
+    assert_eq!(keys_from(&mut db_it), keys);
+
+    assert!(it.seek_for_prev(SeekKey::Key(&[b'k', 9])).unwrap());
+    assert_eq!(it.key(), &[b'k', 8]);
+    assert!(it.prev().unwrap());
+    assert_eq!(it.key(), &[b'k', 6]);
+    assert!(it.seek(SeekKey::End).unwrap());
+    assert_eq!(it.key(), &[b'k', 18]);
+    assert!(!it.next().unwrap());
+    assert!(!it.seek(SeekKey::Key(&[b'k', 19])).unwrap());
+}
+
 #[test]
 fn test_read_invalid_sst() {
     let dir = tempdir_with_prefix("_rust_rocksdb_test_read_invalid_sst");
@@ -565,3 +694,144 @@ fn test_ingest_external_file_options() {
     ingest_opt.set_write_global_seqno(true);
     assert_eq!(true, ingest_opt.get_write_global_seqno());
 }
+
+#[test]
+fn test_external_sst_file_info() {
+    let gen_path = tempdir_with_prefix("_rust_rocksdb_external_sst_file_info");
+    let file = gen_path.path().join("info");
+    let file = file.to_str().unwrap();
+    let mut writer = SstFileWriter::new(EnvOptions::new(), ColumnFamilyOptions::new());
+    writer.open(file).unwrap();
+    writer.put(b"k1", b"v1").unwrap();
+    writer.put(b"k2", b"v2").unwrap();
+    writer.put(b"k5", b"v5").unwrap();
+    let info = writer.finish().unwrap();
+    assert_eq!(info.file_path().to_str().unwrap(), file);
+    assert_eq!(info.smallest_key(), b"k1");
+    assert_eq!(info.largest_key(), b"k5");
+    assert_eq!(info.sequence_number(), 0);
+    assert_eq!(info.num_entries(), 3);
+    assert_eq!(info.file_size(), fs::metadata(file).unwrap().len());
+}
+
+#[test]
+fn test_ingest_sst_with_delete_range() {
+    let path = tempdir_with_prefix("_rust_rocksdb_ingest_sst_with_delete_range");
+    let db = create_default_database(&path);
+    for i in 0..10 {
+        db.put(format!("k{}", i).as_bytes(), b"v").unwrap();
+    }
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    db.flush(&fopts).unwrap();
+
+    let gen_path = tempdir_with_prefix("_rust_rocksdb_ingest_sst_with_delete_range_gen");
+    let file = gen_path.path().join("delete_range");
+    let file = file.to_str().unwrap();
+    let mut writer = SstFileWriter::new(EnvOptions::new(), ColumnFamilyOptions::new());
+    writer.open(file).unwrap();
+    let value = vec![b'v'; 100];
+    for i in 0..1000 {
+        writer.put(format!("m{:04}", i).as_bytes(), &value).unwrap();
+    }
+    // The running size grows as data blocks are written out.
+    let running_size = writer.file_size();
+    assert!(running_size > 0);
+    writer.delete_range(b"k3", b"k6").unwrap();
+    let info = writer.finish().unwrap();
+    assert!(writer.file_size() >= running_size);
+    assert_eq!(info.file_size(), writer.file_size());
+
+    let mut ingest_opt = IngestExternalFileOptions::new();
+    ingest_opt.allow_global_seqno(true);
+    db.ingest_external_file(&ingest_opt, &[file]).unwrap();
+    for i in 0..10 {
+        let v = db.get(format!("k{}", i).as_bytes()).unwrap();
+        assert_eq!(v.is_none(), (3..6).contains(&i), "k{}", i);
+    }
+    assert_eq!(db.get(b"m0000").unwrap().unwrap(), &value[..]);
+    assert_eq!(db.get(b"m0999").unwrap().unwrap(), &value[..]);
+}
+
+#[test]
+fn test_ingest_snapshot_consistent() {
+    let path = tempdir_with_prefix("_rust_rocksdb_ingest_snapshot_consistent");
+    let db = create_default_database(&path);
+    db.put(b"k1", b"v1").unwrap();
+    let snap = db.snapshot();
+    let snap_seqno = snap.get_sequence_number();
+
+    let gen_path = tempdir_with_prefix("_rust_rocksdb_ingest_snapshot_consistent_gen");
+    let file = gen_path.path().join("overlapped");
+    let file = file.to_str().unwrap();
+    gen_sst(
+        ColumnFamilyOptions::new(),
+        None,
+        file,
+        &[(b"k1", b"v2"), (b"k2", b"v2")],
+    );
+    let mut ingest_opt = IngestExternalFileOptions::new();
+    ingest_opt.allow_global_seqno(true);
+    ingest_opt.snapshot_consistent(true);
+    db.ingest_external_file(&ingest_opt, &[file]).unwrap();
+
+    // The file gets a global seqno above the snapshot.
+    assert!(db.get_latest_sequence_number() > snap_seqno);
+    assert_eq!(snap.get(b"k1").unwrap().unwrap(), b"v1");
+    assert!(snap.get(b"k2").unwrap().is_none());
+    check_kv(&db, None, &[(b"k1", Some(b"v2")), (b"k2", Some(b"v2"))]);
+}
+
+#[test]
+fn test_ingest_fail_if_not_bottommost_level() {
+    let path = tempdir_with_prefix("_rust_rocksdb_ingest_fail_if_not_bottommost_level");
+    let db = create_default_database(&path);
+    db.put(b"k1", b"v1").unwrap();
+    db.put(b"k3", b"v3").unwrap();
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    db.flush(&fopts).unwrap();
+
+    let gen_path = tempdir_with_prefix("_rust_rocksdb_ingest_fail_if_not_bottommost_level_gen");
+    let disjoint = gen_path.path().join("disjoint");
+    let disjoint = disjoint.to_str().unwrap();
+    gen_sst(
+        ColumnFamilyOptions::new(),
+        None,
+        disjoint,
+        &[(b"a1", b"a1"), (b"a2", b"a2")],
+    );
+    let overlapped = gen_path.path().join("overlapped");
+    let overlapped = overlapped.to_str().unwrap();
+    gen_sst(
+        ColumnFamilyOptions::new(),
+        None,
+        overlapped,
+        &[(b"k2", b"k2"), (b"k4", b"k4")],
+    );
+
+    let mut ingest_opt = IngestExternalFileOptions::new();
+    ingest_opt.allow_global_seqno(true);
+    ingest_opt.snapshot_consistent(true);
+    ingest_opt.set_fail_if_not_bottommost_level(true);
+    let cf = db.cf_handle("default").unwrap();
+    assert!(db
+        .ingest_external_file_cf(cf, &ingest_opt, &[disjoint, overlapped])
+        .is_err());
+    // Nothing is ingested if any of the files fails.
+    assert!(db.get(b"a1").unwrap().is_none());
+    assert!(db.get(b"k2").unwrap().is_none());
+
+    db.ingest_external_file_cf(cf, &ingest_opt, &[disjoint])
+        .unwrap();
+    check_kv(
+        &db,
+        None,
+        &[
+            (b"a1", Some(b"a1")),
+            (b"a2", Some(b"a2")),
+            (b"k1", Some(b"v1")),
+            (b"k2", None),
+        ],
+    );
+}
@@ -286,6 +286,42 @@ fn read_with_upper_bound() {
     }
 }
 
+#[test]
+fn read_with_lower_and_upper_bound() {
+    let path = tempdir_with_prefix("_rust_rocksdb_read_with_lower_and_upper_bound");
+    let db = DB::open_default(path.path().to_str().unwrap()).unwrap();
+    for k in &[b"a", b"b", b"c", b"d", b"e"] {
+        db.put(*k, *k).unwrap();
+    }
+
+    let bounded_opts = || {
+        let mut readopts = ReadOptions::new();
+        // The bounds are owned by the read options, so temporaries are fine.
+        readopts.set_iterate_lower_bound(b"b".to_vec());
+        readopts.set_iterate_upper_bound(b"d".to_vec());
+        readopts
+    };
+    let readopts = bounded_opts();
+    assert_eq!(readopts.iterate_lower_bound(), b"b");
+    assert_eq!(readopts.iterate_upper_bound(), b"d");
+
+    let mut iter = db.iter_opt(readopts);
+    iter.seek(SeekKey::Start).unwrap();
+    let keys: Vec<_> = next_collect(&mut iter)
+        .into_iter()
+        .map(|(k, _)| k)
+        .collect();
+    assert_eq!(keys, vec![b"b".to_vec(), b"c".to_vec()]);
+
+    let mut iter = db.iter_opt(bounded_opts());
+    iter.seek(SeekKey::End).unwrap();
+    let keys: Vec<_> = prev_collect(&mut iter)
+        .into_iter()
+        .map(|(k, _)| k)
+        .collect();
+    assert_eq!(keys, vec![b"c".to_vec(), b"b".to_vec()]);
+}
+
 #[test]
 fn test_total_order_seek() {
     let path = tempdir_with_prefix("_rust_rocksdb_total_order_seek");
@@ -376,6 +412,73 @@ fn test_total_order_seek() {
     assert!(key_count == 9);
 }
 
+#[test]
+fn test_prefix_seek_stops_at_prefix_boundary() {
+    let path = tempdir_with_prefix("_rust_rocksdb_prefix_seek_boundary");
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    let mut cf_opts = ColumnFamilyOptions::new();
+    cf_opts
+        .set_prefix_extractor::<&str, FixedPrefixTransform>(
+            "FixedPrefixTransform",
+            FixedPrefixTransform { prefix_len: 4 },
+        )
+        .unwrap();
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![("default", cf_opts)],
+    )
+    .unwrap();
+
+    // Keys are `metric_id + timestamp`.
+    let key = |metric: u32, ts: u64| {
+        let mut k = metric.to_be_bytes().to_vec();
+        k.extend_from_slice(&ts.to_be_bytes());
+        k
+    };
+    for metric in 1..4 {
+        for ts in 0..5 {
+            db.put(&key(metric, ts), b"v").unwrap();
+        }
+        if metric == 2 {
+            let mut fopts = FlushOptions::default();
+            fopts.set_wait(true);
+            db.flush(&fopts).unwrap();
+        }
+    }
+
+    let scan_from = |ropts: ReadOptions, metric: u32| {
+        let mut iter = db.iter_opt(ropts);
+        iter.seek(SeekKey::Key(&metric.to_be_bytes())).unwrap();
+        let mut keys = vec![];
+        while iter.valid().unwrap() {
+            keys.push(iter.key().to_vec());
+            iter.next().unwrap();
+        }
+        keys
+    };
+
+    let mut ropts = ReadOptions::new();
+    ropts.set_prefix_same_as_start(true);
+    let keys = scan_from(ropts, 2);
+    assert_eq!(keys, (0..5).map(|ts| key(2, ts)).collect::<Vec<_>>());
+
+    // With auto prefix mode the result is the same as total order seek,
+    // the upper bound is what limits the iteration to one metric.
+    let mut ropts = ReadOptions::new();
+    ropts.set_auto_prefix_mode(true);
+    ropts.set_iterate_upper_bound(3u32.to_be_bytes().to_vec());
+    let keys = scan_from(ropts, 2);
+    assert_eq!(keys, (0..5).map(|ts| key(2, ts)).collect::<Vec<_>>());
+
+    let mut ropts = ReadOptions::new();
+    ropts.set_total_order_seek(true);
+    let keys = scan_from(ropts, 2);
+    assert_eq!(keys.len(), 10);
+    assert_eq!(keys[5], key(3, 0));
+}
+
 #[test]
 fn test_fixed_suffix_seek() {
     let path = tempdir_with_prefix("_rust_rocksdb_fixed_suffix_seek");
@@ -489,3 +592,157 @@ fn test_iter_sequence_number() {
     assert_eq!(v, b"value22");
     assert_eq!(seqno, 4);
 }
+
+#[test]
+fn test_iterator_into_iter() {
+    let path = tempdir_with_prefix("_rust_rocksdb_iterator_into_iter");
+    let db = DB::open_default(path.path().to_str().unwrap()).unwrap();
+    for i in 0..10u8 {
+        db.put(&[b'k', i], &[b'v', i]).unwrap();
+    }
+
+    let mut iter = db.iter();
+    assert!(iter.seek(SeekKey::Start).unwrap());
+    let expected = next_collect(&mut iter);
+    assert_eq!(expected.len(), 10);
+
+    let mut iter = db.iter();
+    assert!(iter.seek(SeekKey::Start).unwrap());
+    let collected: Vec<_> = iter
+        .into_iter()
+        .map(|kv| {
+            let (k, v) = kv.unwrap();
+            (k.into_vec(), v.into_vec())
+        })
+        .collect();
+    assert_eq!(collected, expected);
+
+    // Starts from the current position.
+    let mut iter = db.iter();
+    assert!(iter.seek(SeekKey::Key(&[b'k', 5])).unwrap());
+    let collected: Result<Vec<_>, _> = iter.into_iter().collect();
+    let collected = collected.unwrap();
+    assert_eq!(collected.len(), 5);
+    assert_eq!(&*collected[0].0, &[b'k', 5]);
+
+    // An unpositioned iterator yields nothing.
+    let iter = db.iter();
+    assert_eq!(iter.into_iter().count(), 0);
+}
+
+#[test]
+fn test_iterator_rev_iter() {
+    let path = tempdir_with_prefix("_rust_rocksdb_iterator_rev_iter");
+    let db = DB::open_default(path.path().to_str().unwrap()).unwrap();
+    for i in 0..10u8 {
+        db.put(&[b'k', i], &[b'v', i]).unwrap();
+    }
+
+    let mut iter = db.iter();
+    assert!(iter.seek_for_prev(SeekKey::End).unwrap());
+    let expected = prev_collect(&mut iter);
+    assert_eq!(expected.len(), 10);
+
+    let mut iter = db.iter();
+    assert!(iter.seek_for_prev(SeekKey::End).unwrap());
+    let collected: Vec<_> = iter
+        .rev_iter()
+        .map(|kv| {
+            let (k, v) = kv.unwrap();
+            (k.into_vec(), v.into_vec())
+        })
+        .collect();
+    assert_eq!(collected, expected);
+    for (i, (k, v)) in collected.iter().enumerate() {
+        let n = 9 - i as u8;
+        assert_eq!(k.as_slice(), &[b'k', n]);
+        assert_eq!(v.as_slice(), &[b'v', n]);
+    }
+    assert!(collected.windows(2).all(|w| w[0].0 > w[1].0));
+
+    // Starts from the current position.
+    let mut iter = db.iter();
+    assert!(iter.seek_for_prev(SeekKey::Key(&[b'k', 4])).unwrap());
+    let keys: Vec<_> = iter.rev_iter().map(|kv| kv.unwrap().0).collect();
+    assert_eq!(keys.len(), 5);
+    assert_eq!(&*keys[0], &[b'k', 4]);
+    assert_eq!(&*keys[4], &[b'k', 0]);
+}
+
+#[test]
+fn test_iter_cfs_opt() {
+    let path = tempdir_with_prefix("_rust_rocksdb_iter_cfs_opt");
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![
+            ("default", ColumnFamilyOptions::new()),
+            ("cf1", ColumnFamilyOptions::new()),
+        ],
+    )
+    .unwrap();
+    let default = db.cf_handle("default").unwrap();
+    let cf1 = db.cf_handle("cf1").unwrap();
+    db.put_cf(default, b"k1", b"v1").unwrap();
+    db.put_cf(cf1, b"k2", b"v2").unwrap();
+
+    let mut iters = db
+        .iter_cfs_opt(&[default, cf1], ReadOptions::new())
+        .unwrap();
+    assert_eq!(iters.len(), 2);
+    db.put_cf(default, b"k1", b"v1_new").unwrap();
+    db.put_cf(default, b"k3", b"v3").unwrap();
+    db.delete_cf(cf1, b"k2").unwrap();
+    db.put_cf(cf1, b"k4", b"v4").unwrap();
+
+    iters[0].seek(SeekKey::Start).unwrap();
+    assert_eq!(
+        next_collect(&mut iters[0]),
+        vec![(b"k1".to_vec(), b"v1".to_vec())]
+    );
+    iters[1].seek(SeekKey::Start).unwrap();
+    assert_eq!(
+        next_collect(&mut iters[1]),
+        vec![(b"k2".to_vec(), b"v2".to_vec())]
+    );
+    assert!(iters[0]
+        .seek_with_ts(SeekKey::Start, &[0; 8])
+        .unwrap_err()
+        .contains("sharing read options"));
+    drop(iters);
+
+    let mut iter = db.iter_cf(cf1);
+    iter.seek(SeekKey::Start).unwrap();
+    assert_eq!(
+        next_collect(&mut iter),
+        vec![(b"k4".to_vec(), b"v4".to_vec())]
+    );
+}
+
+#[test]
+fn test_iterator_valid_for_prefix() {
+    let path = tempdir_with_prefix("_rust_rocksdb_iterator_valid_for_prefix");
+    let db = DB::open_default(path.path().to_str().unwrap()).unwrap();
+    for k in &[b"a1", b"b1", b"b2", b"b3", b"c1"] {
+        db.put(*k, b"v").unwrap();
+    }
+
+    let mut iter = db.iter();
+    iter.seek(SeekKey::Key(b"b")).unwrap();
+    let mut keys = vec![];
+    while iter.valid_for_prefix(b"b").unwrap() {
+        keys.push(iter.key().to_vec());
+        iter.next().unwrap();
+    }
+    assert_eq!(keys, vec![b"b1".to_vec(), b"b2".to_vec(), b"b3".to_vec()]);
+    // The iterator itself has moved on to the next prefix.
+    assert!(iter.valid().unwrap());
+    assert_eq!(iter.key(), b"c1");
+
+    iter.seek(SeekKey::Key(b"c2")).unwrap();
+    assert!(!iter.valid().unwrap());
+    assert!(!iter.valid_for_prefix(b"c").unwrap());
+}
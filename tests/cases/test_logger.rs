@@ -1,10 +1,10 @@
-use std::sync::{atomic::*, Arc};
+use std::sync::{atomic::*, Arc, Mutex};
 use std::time::Duration;
 use std::{str, thread};
 
 use super::tempdir_with_prefix;
 
-use rocksdb::{DBInfoLogLevel as InfoLogLevel, DBOptions, Logger, DB};
+use rocksdb::{DBInfoLogLevel as InfoLogLevel, DBOptions, Logger, Writable, DB};
 
 #[derive(Default, Clone)]
 struct TestDrop {
@@ -53,3 +53,60 @@ fn test_logger() {
     drop(logger);
     assert_eq!(1, drop_called.load(Ordering::SeqCst));
 }
+
+#[test]
+fn test_closure_logger_level_filter() {
+    let open_with_level = |name: &str, level: InfoLogLevel| {
+        let lines = Arc::new(Mutex::new(vec![]));
+        let path = tempdir_with_prefix(name);
+        let mut opts = DBOptions::new();
+        let lines_ = lines.clone();
+        opts.set_info_log(move |level: InfoLogLevel, log: &str| {
+            lines_.lock().unwrap().push((level, log.to_owned()));
+        });
+        opts.set_info_log_level(level);
+        opts.create_if_missing(true);
+        let db = DB::open(opts, path.path().to_str().unwrap()).unwrap();
+        drop(db);
+        let lines = lines.lock().unwrap().clone();
+        lines
+    };
+
+    let lines = open_with_level("_rust_rocksdb_test_closure_logger_info", InfoLogLevel::Info);
+    assert!(
+        lines.iter().any(|(l, _)| *l == InfoLogLevel::Info),
+        "{:?}",
+        lines
+    );
+    assert!(lines
+        .iter()
+        .all(|(l, _)| *l as u32 >= InfoLogLevel::Info as u32));
+
+    let lines = open_with_level(
+        "_rust_rocksdb_test_closure_logger_error",
+        InfoLogLevel::Error,
+    );
+    assert!(
+        lines
+            .iter()
+            .all(|(l, _)| *l as u32 >= InfoLogLevel::Error as u32),
+        "{:?}",
+        lines
+    );
+}
+
+#[test]
+fn test_panicking_logger() {
+    let path = tempdir_with_prefix("_rust_rocksdb_test_panicking_logger");
+    let mut opts = DBOptions::new();
+    let panicked = AtomicBool::new(false);
+    opts.set_info_log(move |_: InfoLogLevel, _: &str| {
+        if !panicked.swap(true, Ordering::SeqCst) {
+            panic!("logger panics");
+        }
+    });
+    opts.create_if_missing(true);
+    let db = DB::open(opts, path.path().to_str().unwrap()).unwrap();
+    db.put(b"k", b"v").unwrap();
+    assert_eq!(db.get(b"k").unwrap().unwrap(), b"v");
+}
@@ -69,6 +69,54 @@ fn test_metadata() {
     }
 }
 
+#[test]
+fn test_sst_file_metadata_boundaries() {
+    let path = tempdir_with_prefix("_rust_rocksdb_test_sst_file_metadata_boundaries");
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    let mut cf_opts = ColumnFamilyOptions::new();
+    cf_opts.set_disable_auto_compactions(true);
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![("default", cf_opts)],
+    )
+    .unwrap();
+    let cf_handle = db.cf_handle("default").unwrap();
+
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    for k in &[b"a1", b"a2", b"a3"] {
+        db.put(*k, b"v").unwrap();
+    }
+    db.flush(&fopts).unwrap();
+    for k in &[b"b1", b"b2"] {
+        db.put(*k, b"v").unwrap();
+    }
+    db.delete(b"b3").unwrap();
+    db.flush(&fopts).unwrap();
+
+    let cf_meta = db.get_column_family_meta_data(cf_handle);
+    let mut files = cf_meta.get_level(0).get_files();
+    assert_eq!(files.len(), 2);
+    files.sort_by_key(|f| f.smallest_key());
+
+    assert_eq!(files[0].smallest_key(), b"a1".to_vec());
+    assert_eq!(files[0].largest_key(), b"a3".to_vec());
+    assert_eq!(files[0].get_num_entries(), 3);
+    assert_eq!(files[0].get_num_deletions(), 0);
+
+    assert_eq!(files[1].smallest_key(), b"b1".to_vec());
+    assert_eq!(files[1].largest_key(), b"b3".to_vec());
+    assert_eq!(files[1].get_num_entries(), 3);
+    assert_eq!(files[1].get_num_deletions(), 1);
+
+    assert!(files[0].largest_key() < files[1].smallest_key());
+    for f in &files {
+        assert!(!f.is_being_compacted());
+    }
+}
+
 fn get_files_cf(db: &DB, cf: &CFHandle, max_level: usize) -> Vec<String> {
     let mut files = Vec::new();
     let cf_meta = db.get_column_family_meta_data(cf);
@@ -119,3 +167,132 @@ fn test_compact_files() {
         .unwrap();
     assert_eq!(get_files_cf(&db, cf_handle, 0).len(), 1);
 }
+
+#[test]
+fn test_compact_files_to_level_with_zstd() {
+    let path = tempdir_with_prefix("_rust_rocksdb_test_compact_files_to_level_with_zstd");
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    let mut cf_opts = ColumnFamilyOptions::new();
+    cf_opts.set_disable_auto_compactions(true);
+    cf_opts.compression(DBCompressionType::No);
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![("default", cf_opts)],
+    )
+    .unwrap();
+    let cf_handle = db.cf_handle("default").unwrap();
+
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    for i in 0..4u8 {
+        db.put(&[i], &[i; 128]).unwrap();
+        db.flush(&fopts).unwrap();
+    }
+    let mut opts = CompactionOptions::new();
+    opts.set_compression(DBCompressionType::Zstd);
+    opts.set_output_file_size_limit(64 << 20);
+    opts.set_max_subcompactions(2);
+    // Only the first two files are rewritten.
+    let mut input_files = get_files_cf(&db, cf_handle, 0);
+    input_files.sort();
+    input_files.truncate(2);
+    db.compact_files_cf(cf_handle, &opts, &input_files, 2)
+        .unwrap();
+
+    let cf_meta = db.get_column_family_meta_data(cf_handle);
+    let levels = cf_meta.get_levels();
+    assert_eq!(levels[0].get_files().len(), 2);
+    assert!(levels[1].get_files().is_empty());
+    let files = levels[2].get_files();
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].get_num_entries(), 2);
+    assert!(!input_files.contains(&files[0].get_name()));
+
+    let props = db.get_properties_of_all_tables_cf(cf_handle).unwrap();
+    let compressions: Vec<_> = props
+        .iter()
+        .map(|(name, p)| {
+            (
+                name.ends_with(&files[0].get_name()),
+                p.compression_name().to_owned(),
+            )
+        })
+        .collect();
+    assert_eq!(compressions.len(), 3);
+    for (compacted, compression) in compressions {
+        if compacted {
+            assert_eq!(compression, "ZSTD");
+        } else {
+            assert_eq!(compression, "NoCompression");
+        }
+    }
+}
+
+#[test]
+fn test_get_live_file_names() {
+    let path = tempdir_with_prefix("_rust_rocksdb_test_get_live_file_names");
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    let db = DB::open(opts, path.path().to_str().unwrap()).unwrap();
+    db.put(b"k1", b"v1").unwrap();
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    db.flush(&fopts).unwrap();
+    db.put(b"k2", b"v2").unwrap();
+
+    let (files, manifest_size) = db.get_live_file_names(false).unwrap();
+    assert!(files.iter().any(|f| f == "/CURRENT"), "{:?}", files);
+    let manifest = files.iter().find(|f| f.starts_with("/MANIFEST-")).unwrap();
+    assert!(manifest_size > 0);
+    assert!(manifest_size <= path.path().join(&manifest[1..]).metadata().unwrap().len());
+    let ssts = |files: &[String]| files.iter().filter(|f| f.ends_with(".sst")).count();
+    assert_eq!(ssts(&files), 1);
+
+    // Flushing the memtable first adds the sst holding k2.
+    let (files, _) = db.get_live_file_names(true).unwrap();
+    assert_eq!(ssts(&files), 2);
+}
+
+#[test]
+fn test_flush_and_get_sst_cf() {
+    let path = tempdir_with_prefix("_rust_rocksdb_test_flush_and_get_sst_cf");
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    let mut cf_opts = ColumnFamilyOptions::new();
+    cf_opts.set_disable_auto_compactions(true);
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![("default", cf_opts)],
+    )
+    .unwrap();
+    let cf_handle = db.cf_handle("default").unwrap();
+
+    // Nothing to flush.
+    assert!(db.flush_and_get_sst_cf(cf_handle).unwrap().is_empty());
+
+    for i in 0..10u8 {
+        db.put(&[i], &[i]).unwrap();
+    }
+    let flushed = db.flush_and_get_sst_cf(cf_handle).unwrap();
+    assert_eq!(flushed.len(), 1);
+    let files = flushed.get_files();
+    assert_eq!(files[0].get_num_entries(), 10);
+    assert_eq!(files[0].get_smallestkey(), [0]);
+    assert_eq!(files[0].get_largestkey(), [9]);
+    let first = files[0].get_name();
+
+    // Only the files of the latest flush are returned.
+    for i in 0..3u8 {
+        db.put(&[i], &[i]).unwrap();
+    }
+    db.delete(&[5]).unwrap();
+    let flushed = db.flush_and_get_sst_cf(cf_handle).unwrap();
+    let files = flushed.get_files();
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].get_num_entries(), 4);
+    assert_eq!(files[0].get_num_deletions(), 1);
+    assert_ne!(files[0].get_name(), first);
+}
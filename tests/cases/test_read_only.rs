@@ -1,4 +1,4 @@
-use rocksdb::{DBOptions, Writable, DB};
+use rocksdb::{DBOptions, FlushOptions, Writable, DB};
 
 use super::tempdir_with_prefix;
 
@@ -90,3 +90,77 @@ fn test_open_cf_for_read_only() {
         check_kv!(r2, cf2, b"cf2_k3", b"cf2_v3");
     }
 }
+
+#[test]
+fn test_read_only_rejects_writes() {
+    let temp = tempdir_with_prefix("_rust_rocksdb_test_read_only_rejects_writes");
+    let path = temp.path().to_str().unwrap();
+
+    {
+        let rw = DB::open_default(path).unwrap();
+        rw.put(b"k1", b"v1").unwrap();
+    }
+
+    let r1 = DB::open_for_read_only(DBOptions::new(), path, false).unwrap();
+    check_kv!(r1, b"k1", b"v1");
+    assert!(r1.put(b"k2", b"v2").is_err());
+    assert!(r1.delete(b"k1").is_err());
+    check_kv!(r1, b"k1", b"v1");
+    assert!(r1.get(b"k2").unwrap().is_none());
+
+    // The WAL is left in place after a clean close, `error_if_log_file_exist`
+    // refuses to open such a db.
+    assert!(DB::open_for_read_only(DBOptions::new(), path, true).is_err());
+}
+
+#[test]
+fn test_open_as_secondary() {
+    let temp = tempdir_with_prefix("_rust_rocksdb_test_open_as_secondary");
+    let path = temp.path().to_str().unwrap();
+    let secondary = tempdir_with_prefix("_rust_rocksdb_test_open_as_secondary_secondary");
+    let secondary_path = secondary.path().to_str().unwrap();
+
+    let primary = DB::open_default(path).unwrap();
+    primary.put(b"k1", b"v1").unwrap();
+
+    let mut opts = DBOptions::new();
+    opts.set_max_open_files(-1);
+    let s1 = DB::open_as_secondary(opts, path, secondary_path).unwrap();
+    check_kv!(s1, b"k1", b"v1");
+    assert!(s1.put(b"k2", b"v2").is_err());
+
+    primary.put(b"k2", b"v2").unwrap();
+    assert!(s1.get(b"k2").unwrap().is_none());
+    s1.try_catch_up_with_primary().unwrap();
+    check_kv!(s1, b"k2", b"v2");
+}
+
+#[test]
+fn test_open_cf_as_secondary() {
+    let temp = tempdir_with_prefix("_rust_rocksdb_test_open_cf_as_secondary");
+    let path = temp.path().to_str().unwrap();
+    let secondary = tempdir_with_prefix("_rust_rocksdb_test_open_cf_as_secondary_secondary");
+    let secondary_path = secondary.path().to_str().unwrap();
+
+    {
+        let mut rw = DB::open_default(path).unwrap();
+        let _ = rw.create_cf("cf1").unwrap();
+    }
+    let primary = DB::open_cf(DBOptions::new(), path, vec!["cf1"]).unwrap();
+    let cf1 = primary.cf_handle("cf1").unwrap();
+    primary.put_cf(cf1, b"cf1_k1", b"cf1_v1").unwrap();
+
+    let mut opts = DBOptions::new();
+    opts.set_max_open_files(-1);
+    let s1 = DB::open_cf_as_secondary(opts, path, secondary_path, vec!["cf1"]).unwrap();
+    let s_cf1 = s1.cf_handle("cf1").unwrap();
+    check_kv!(s1, s_cf1, b"cf1_k1", b"cf1_v1");
+
+    primary.put_cf(cf1, b"cf1_k2", b"cf1_v2").unwrap();
+    let mut flush_opts = FlushOptions::default();
+    flush_opts.set_wait(true);
+    primary.flush_cf(cf1, &flush_opts).unwrap();
+    s1.try_catch_up_with_primary().unwrap();
+    check_kv!(s1, s_cf1, b"cf1_k1", b"cf1_v1");
+    check_kv!(s1, s_cf1, b"cf1_k2", b"cf1_v2");
+}
@@ -12,6 +12,7 @@
 // limitations under the License.
 
 use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
@@ -21,12 +22,14 @@ use rocksdb::crocksdb_ffi::{
     DBStatisticsHistogramType as HistogramType, DBStatisticsTickerType as TickerType,
 };
 use rocksdb::{
-    BlockBasedOptions, Cache, ColumnFamilyOptions, CompactOptions, DBOptions, Env,
-    FifoCompactionOptions, FlushOptions, IndexType, LRUCacheOptions, RateLimiter, ReadOptions,
-    SeekKey, SliceTransform, Statistics, Writable, WriteOptions, DB,
+    BlockBasedOptions, Cache, ColumnFamilyOptions, CompactOptions, DBEnvPriority, DBOptions, Env,
+    FifoCompactionOptions, FileSystemInspector, FlushOptions, IndexType, LRUCacheOptions,
+    MergeOperands, RateLimiter, ReadOptions, ReadTier, SeekKey, SliceTransform, Statistics,
+    Writable, WriteBufferManager, WriteOptions, DB,
 };
 
 use super::tempdir_with_prefix;
+use super::test_event_listener::FlushGate;
 
 #[test]
 fn test_set_num_levels() {
@@ -45,6 +48,41 @@ fn test_set_num_levels() {
     drop(db);
 }
 
+#[test]
+fn test_num_levels_bounds_lsm_depth() {
+    let path = tempdir_with_prefix("_rust_rocksdb_test_num_levels_bounds_lsm_depth");
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    let mut cf_opts = ColumnFamilyOptions::new();
+    cf_opts.set_num_levels(5);
+    cf_opts.set_write_buffer_size(64 * 1024);
+    cf_opts.set_target_file_size_base(64 * 1024);
+    cf_opts.set_max_bytes_for_level_base(128 * 1024);
+    cf_opts.set_max_bytes_for_level_multiplier(2);
+    cf_opts.set_level_zero_file_num_compaction_trigger(2);
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![("default", cf_opts)],
+    )
+    .unwrap();
+
+    let value = vec![b'v'; 1024];
+    for i in 0..4096 {
+        db.put(format!("k{:06}", i).as_bytes(), &value).unwrap();
+    }
+    db.wait_for_compact(true, Some(Duration::from_secs(60)))
+        .unwrap();
+
+    let cf = db.cf_handle("default").unwrap();
+    let cf_meta = db.get_column_family_meta_data(cf);
+    let levels = cf_meta.get_levels();
+    assert_eq!(levels.len(), 5);
+    let populated = levels.iter().filter(|l| !l.get_files().is_empty()).count();
+    assert!(populated > 1, "only {} level is populated", populated);
+    assert!(!levels[4].get_files().is_empty());
+}
+
 #[test]
 fn test_log_file_opt() {
     let path = tempdir_with_prefix("_rust_rocksdb_log_file_opt");
@@ -71,7 +109,7 @@ fn test_compaction_readahead_size() {
 fn test_set_max_manifest_file_size() {
     let mut opts = DBOptions::new();
     let size = 20 * 1024 * 1024;
-    opts.set_max_manifest_file_size(size)
+    opts.set_max_manifest_file_size(size);
 }
 
 #[test]
@@ -135,6 +173,51 @@ fn test_memtable_insert_hint_prefix_extractor() {
     assert_eq!(db.get(b"k0-3").unwrap().unwrap(), b"c");
 }
 
+#[test]
+fn test_set_bloom_locality() {
+    let path = tempdir_with_prefix("_rust_rocksdb_set_bloom_locality");
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    let mut cf_opts = ColumnFamilyOptions::new();
+    cf_opts
+        .set_prefix_extractor::<&str, FixedPrefixTransform>(
+            "FixedPrefixTransform",
+            FixedPrefixTransform { prefix_len: 3 },
+        )
+        .unwrap();
+    cf_opts.set_memtable_prefix_bloom_size_ratio(0.1);
+    cf_opts.set_bloom_locality(1);
+    let mut block_opts = BlockBasedOptions::new();
+    block_opts.set_bloom_filter(10.0, false);
+    cf_opts.set_block_based_table_factory(&block_opts);
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![("default", cf_opts)],
+    )
+    .unwrap();
+
+    for i in 0..100 {
+        db.put(format!("k{:02}-a", i).as_bytes(), b"v").unwrap();
+    }
+    let check = |db: &DB| {
+        for i in 0..100 {
+            let key = format!("k{:02}-a", i);
+            assert_eq!(db.get(key.as_bytes()).unwrap().unwrap(), b"v");
+            let absent = format!("k{:02}-b", i);
+            assert!(db.get(absent.as_bytes()).unwrap().is_none());
+        }
+        assert!(db.get(b"x00-a").unwrap().is_none());
+    };
+    // Lookups through the memtable bloom filter.
+    check(&db);
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    db.flush(&fopts).unwrap();
+    // Lookups through the SST bloom filter.
+    check(&db);
+}
+
 #[test]
 fn test_set_delayed_write_rate() {
     let path = tempdir_with_prefix("_rust_rocksdb_test_set_delayed_write_rate");
@@ -224,7 +307,21 @@ fn test_set_wal_opt() {
     opts.set_wal_size_limit_mb(10);
     let wal_dir = tempdir_with_prefix("_rust_rocksdb_test_set_wal_dir");
     opts.set_wal_dir(wal_dir.path().to_str().unwrap());
+    opts.set_recycle_log_file_num(2);
     let db = DB::open(opts, path.path().to_str().unwrap()).unwrap();
+    db.put(b"key", b"value").unwrap();
+
+    let count_logs = |dir: &Path| {
+        std::fs::read_dir(dir)
+            .unwrap()
+            .filter(|e| {
+                let name = e.as_ref().unwrap().file_name();
+                name.to_str().unwrap().ends_with(".log")
+            })
+            .count()
+    };
+    assert!(count_logs(wal_dir.path()) > 0);
+    assert_eq!(count_logs(path.path()), 0);
     drop(db);
 }
 
@@ -337,12 +434,34 @@ fn test_partitioned_index_filters() {
     block_opts.set_cache_index_and_filter_blocks_with_high_priority(true);
     block_opts.set_pin_l0_filter_and_index_blocks_in_cache(true);
     cf_opts.set_block_based_table_factory(&block_opts);
-    DB::open_cf(
+    let db = DB::open_cf(
         opts,
         path.path().to_str().unwrap(),
         vec![("default", cf_opts)],
     )
     .unwrap();
+
+    let value = vec![b'v'; 512];
+    for i in 0..5000 {
+        db.put(format!("k{:05}", i).as_bytes(), &value).unwrap();
+    }
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    db.flush(&fopts).unwrap();
+
+    // Both the index and the filter outgrow a single metadata block, so
+    // they are split into several partitions.
+    let collection = db.get_properties_of_all_tables().unwrap();
+    assert_eq!(collection.len(), 1);
+    for (_, props) in collection.iter() {
+        assert!(props.index_size() > 4096);
+        assert!(props.filter_size() > 4096);
+    }
+    for i in (0..5000).step_by(7) {
+        let key = format!("k{:05}", i);
+        assert_eq!(db.get(key.as_bytes()).unwrap().unwrap(), &value[..]);
+    }
+    assert!(db.get(b"k02500x").unwrap().is_none());
 }
 
 #[test]
@@ -492,6 +611,93 @@ fn test_get_block_cache_usage() {
     assert!(db.get_options().get_block_cache_usage() > 0);
 }
 
+#[test]
+fn test_cache_usage_and_capacity() {
+    let path = tempdir_with_prefix("_rust_rocksdb_cache_usage_and_capacity");
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    let mut cache_opts = LRUCacheOptions::new();
+    cache_opts.set_capacity(4 * 1024 * 1024);
+    let cache = Cache::new_lru_cache(cache_opts);
+    assert_eq!(cache.get_capacity(), 4 * 1024 * 1024);
+    assert_eq!(cache.get_usage(), 0);
+    let mut block_opts = BlockBasedOptions::new();
+    block_opts.set_block_cache(&cache);
+    let mut cf_opts = ColumnFamilyOptions::new();
+    cf_opts.set_block_based_table_factory(&block_opts);
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![("default", cf_opts)],
+    )
+    .unwrap();
+
+    let value = vec![b'v'; 1024];
+    for i in 0..1000 {
+        db.put(format!("k{:04}", i).as_bytes(), &value).unwrap();
+    }
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    db.flush(&fopts).unwrap();
+    for i in 0..1000 {
+        db.get(format!("k{:04}", i).as_bytes()).unwrap();
+    }
+    let usage = cache.get_usage();
+    assert!(usage > 0);
+
+    // The block an iterator is positioned at is pinned.
+    let mut iter = db.iter();
+    iter.seek(SeekKey::Start).unwrap();
+    assert!(cache.get_pinned_usage() > 0);
+    drop(iter);
+    assert_eq!(cache.get_pinned_usage(), 0);
+
+    cache.set_capacity(usage / 4);
+    assert_eq!(cache.get_capacity(), usage / 4);
+    assert!(cache.get_usage() <= usage / 4);
+}
+
+#[test]
+fn test_lru_cache_high_pri_pool() {
+    let path = tempdir_with_prefix("_rust_rocksdb_lru_cache_high_pri_pool");
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    let mut cache_opts = LRUCacheOptions::new();
+    cache_opts.set_capacity(8 * 1024 * 1024);
+    cache_opts.set_num_shard_bits(2);
+    cache_opts.set_strict_capacity_limit(true);
+    cache_opts.set_high_pri_pool_ratio(0.5);
+    let cache = Cache::new_lru_cache(cache_opts);
+    let mut block_opts = BlockBasedOptions::new();
+    block_opts.set_block_cache(&cache);
+    block_opts.set_bloom_filter(10.0, false);
+    block_opts.set_cache_index_and_filter_blocks(true);
+    block_opts.set_cache_index_and_filter_blocks_with_high_priority(true);
+    block_opts.set_pin_l0_filter_and_index_blocks_in_cache(true);
+    let mut cf_opts = ColumnFamilyOptions::new();
+    cf_opts.set_block_based_table_factory(&block_opts);
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![("default", cf_opts)],
+    )
+    .unwrap();
+
+    for i in 0..100 {
+        db.put(format!("k{:03}", i).as_bytes(), b"v").unwrap();
+    }
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    db.flush(&fopts).unwrap();
+    for i in 0..100 {
+        let v = db.get(format!("k{:03}", i).as_bytes()).unwrap().unwrap();
+        assert_eq!(&*v, b"v");
+    }
+    assert!(db.get(b"missing").unwrap().is_none());
+    // The pinned L0 index and filter blocks stay in the cache.
+    assert!(cache.get_pinned_usage() > 0);
+}
+
 #[test]
 fn test_block_cache_capacity() {
     let path = tempdir_with_prefix("_rust_rocksdb_set_and_get_block_cache_capacity");
@@ -675,6 +881,40 @@ fn test_enable_pipelined_write() {
     }
 }
 
+#[test]
+fn test_two_write_queues() {
+    let path = tempdir_with_prefix("_rust_rocksdb_two_write_queues");
+    let path_str = path.path().to_str().unwrap();
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    opts.set_two_write_queues(true);
+    let db = Arc::new(DB::open(opts, path_str).unwrap());
+    let handles: Vec<_> = (0..4)
+        .map(|t| {
+            let db = db.clone();
+            thread::spawn(move || {
+                for i in 0..100 {
+                    db.put(format!("k_{}_{}", t, i).as_bytes(), b"v").unwrap();
+                }
+            })
+        })
+        .collect();
+    for h in handles {
+        h.join().unwrap();
+    }
+    drop(db);
+
+    let mut opts = DBOptions::new();
+    opts.set_two_write_queues(true);
+    let db = DB::open(opts, path_str).unwrap();
+    for t in 0..4 {
+        for i in 0..100 {
+            let key = format!("k_{}_{}", t, i);
+            assert_eq!(db.get(key.as_bytes()).unwrap().unwrap(), b"v");
+        }
+    }
+}
+
 #[test]
 fn test_get_compression() {
     let mut opts = DBOptions::new();
@@ -684,6 +924,34 @@ fn test_get_compression() {
     assert_eq!(cf_opts.get_compression(), DBCompressionType::Snappy);
 }
 
+#[test]
+fn test_compression_type_str() {
+    let all = [
+        DBCompressionType::No,
+        DBCompressionType::Snappy,
+        DBCompressionType::Zlib,
+        DBCompressionType::Bz2,
+        DBCompressionType::Lz4,
+        DBCompressionType::Lz4hc,
+        DBCompressionType::Zstd,
+        DBCompressionType::ZstdNotFinal,
+        DBCompressionType::Disable,
+    ];
+    for t in &all {
+        assert_eq!(DBCompressionType::from_str(&t.to_string()), Some(*t));
+    }
+    assert_eq!(DBCompressionType::Zstd.to_string(), "zstd");
+    assert_eq!(
+        DBCompressionType::from_str("ZSTD"),
+        Some(DBCompressionType::Zstd)
+    );
+    assert_eq!(
+        DBCompressionType::from_str("none"),
+        Some(DBCompressionType::No)
+    );
+    assert_eq!(DBCompressionType::from_str("brotli"), None);
+}
+
 #[test]
 fn test_get_compression_per_level() {
     let mut cf_opts = ColumnFamilyOptions::new();
@@ -704,7 +972,7 @@ fn test_get_compression_per_level() {
 fn test_bottommost_compression() {
     let path = tempdir_with_prefix("_rust_rocksdb_bottommost_compression");
     let mut opts = DBOptions::new();
-    let cf_opts = ColumnFamilyOptions::new();
+    let mut cf_opts = ColumnFamilyOptions::new();
     opts.create_if_missing(true);
     cf_opts.bottommost_compression(DBCompressionType::No);
     DB::open_cf(
@@ -754,6 +1022,78 @@ fn test_write_options() {
     assert_eq!(db.get(b"k3").unwrap().unwrap(), b"c");
 }
 
+#[test]
+fn test_write_no_slowdown() {
+    let path = tempdir_with_prefix("_rust_rocksdb_test_write_no_slowdown");
+    let gate = FlushGate::default();
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    opts.add_event_listener(gate.clone());
+    let mut cf_opts = ColumnFamilyOptions::new();
+    cf_opts.set_max_write_buffer_number(2);
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![("default", cf_opts)],
+    )
+    .unwrap();
+
+    let mut wopts = WriteOptions::new();
+    wopts.set_low_pri(true);
+    wopts.set_memtable_insert_hint_per_batch(true);
+    db.put_opt(b"k0", b"v0", &wopts).unwrap();
+
+    // Pile up immutable memtables behind the blocked flush until writes stop.
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(false);
+    fopts.set_allow_write_stall(true);
+    for i in 1..3 {
+        db.put(format!("k{}", i).as_bytes(), b"v").unwrap();
+        db.flush(&fopts).unwrap();
+    }
+    let mut wopts = WriteOptions::new();
+    wopts.set_no_slowdown(true);
+    let res = db.put_opt(b"k3", b"v3", &wopts);
+    // Let the flushes finish so that the DB can be closed.
+    gate.open();
+    let err = res.unwrap_err();
+    assert!(err.contains("Write stall"), "{}", err);
+    assert!(db.get(b"k3").unwrap().is_none());
+
+    fopts.set_wait(true);
+    db.flush(&fopts).unwrap();
+    db.put_opt(b"k3", b"v3", &wopts).unwrap();
+    assert_eq!(db.get(b"k3").unwrap().unwrap(), b"v3");
+}
+
+#[test]
+fn test_read_tier() {
+    let path = tempdir_with_prefix("_rust_rocksdb_read_tier");
+    let db = DB::open_default(path.path().to_str().unwrap()).unwrap();
+    db.put(b"k1", b"v1").unwrap();
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    db.flush(&fopts).unwrap();
+    db.put(b"k2", b"v2").unwrap();
+
+    let mut cache_only = ReadOptions::new();
+    cache_only.set_read_tier(ReadTier::BlockCacheTier);
+    // Memtable hits don't need IO.
+    assert_eq!(db.get_opt(b"k2", &cache_only).unwrap().unwrap(), b"v2");
+    // The data block of `k1` isn't cached yet.
+    let err = db.get_opt(b"k1", &cache_only).unwrap_err();
+    assert!(err.contains("incomplete"), "{}", err);
+    assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+    assert_eq!(db.get_opt(b"k1", &cache_only).unwrap().unwrap(), b"v1");
+
+    let mut memtable_only = ReadOptions::new();
+    memtable_only.set_read_tier(ReadTier::MemtableTier);
+    let mut iter = db.iter_opt(memtable_only);
+    iter.seek(SeekKey::Start).unwrap();
+    assert_eq!(iter.key(), b"k2");
+    assert!(!iter.next().unwrap());
+}
+
 #[test]
 fn test_read_options() {
     let path = tempdir_with_prefix("_rust_rocksdb_write_options");
@@ -768,7 +1108,7 @@ fn test_read_options() {
     read_opts.set_ignore_range_deletions(true);
     read_opts.set_max_skippable_internal_keys(0);
     read_opts.set_readahead_size(0);
-    read_opts.set_read_tier(0);
+    read_opts.set_read_tier(ReadTier::ReadAllTier);
 
     db.put(b"k1", b"a").unwrap();
     db.put(b"k2", b"b").unwrap();
@@ -914,7 +1254,7 @@ fn test_compact_on_deletion() {
     let dels_trigger = 90;
 
     let mut opts = DBOptions::new();
-    let cf_opts = ColumnFamilyOptions::new();
+    let mut cf_opts = ColumnFamilyOptions::new();
     opts.create_if_missing(true);
     cf_opts.set_compact_on_deletion(window_size, dels_trigger);
 
@@ -975,3 +1315,376 @@ fn test_ttl_compaction_options() {
     assert_eq!(db.get_options().get_ttl(), 3600);
     assert_eq!(db.get_options().get_periodic_compaction_seconds(), 7200);
 }
+
+#[test]
+fn test_chained_options() {
+    let path = tempdir_with_prefix("_rust_rocksdb_chained_options");
+    let path_str = path.path().to_str().unwrap();
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true)
+        .set_max_open_files(-1)
+        .set_max_background_jobs(4)
+        .set_keep_log_file_num(10);
+    let mut cf_opts = ColumnFamilyOptions::new();
+    cf_opts
+        .set_write_buffer_size(4 * 1024 * 1024)
+        .set_max_write_buffer_number(4)
+        .set_num_levels(5)
+        .set_disable_auto_compactions(true);
+    assert_eq!(opts.get_max_background_jobs(), 4);
+    assert_eq!(cf_opts.get_num_levels(), 5);
+    let db = DB::open_cf(opts, path_str, vec![("default", cf_opts)]).unwrap();
+    db.put(b"k1", b"v1").unwrap();
+    assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+    assert_eq!(db.get_options().get_num_levels(), 5);
+    assert!(db.get_options().get_disable_auto_compactions());
+}
+
+#[test]
+fn test_small_write_buffer_size() {
+    let path = tempdir_with_prefix("_rust_rocksdb_small_write_buffer_size");
+    let path_str = path.path().to_str().unwrap();
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    let mut cf_opts = ColumnFamilyOptions::new();
+    cf_opts
+        .set_write_buffer_size(64 * 1024)
+        .set_memtable_huge_page_size(2 * 1024 * 1024)
+        .set_disable_auto_compactions(true);
+    assert_eq!(cf_opts.get_memtable_huge_page_size(), 2 * 1024 * 1024);
+    let db = DB::open_cf(opts, path_str, vec![("default", cf_opts)]).unwrap();
+    assert_eq!(db.get_options().get_write_buffer_size(), 64 * 1024);
+    assert_eq!(
+        db.get_options().get_memtable_huge_page_size(),
+        2 * 1024 * 1024
+    );
+
+    let value = vec![b'v'; 1024];
+    for i in 0..256 {
+        db.put(format!("k{:04}", i).as_bytes(), &value).unwrap();
+    }
+    let level0_prop = "rocksdb.num-files-at-level0";
+    for _ in 0..100 {
+        if db.get_property_int(level0_prop).unwrap() > 0 {
+            break;
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    assert!(db.get_property_int(level0_prop).unwrap() > 0);
+}
+
+#[test]
+fn test_write_buffer_manager_triggers_flush() {
+    let mut cache_opts = LRUCacheOptions::new();
+    cache_opts.set_capacity(8 * 1024 * 1024);
+    let cache = Cache::new_lru_cache(cache_opts);
+    let wbm = WriteBufferManager::new_with_cache(256 * 1024, &cache, 0.0, true);
+    assert_eq!(wbm.buffer_size(), 256 * 1024);
+
+    let paths: Vec<_> = (0..2)
+        .map(|i| tempdir_with_prefix(&format!("_rust_rocksdb_write_buffer_manager_{}", i)))
+        .collect();
+    let dbs: Vec<_> = paths
+        .iter()
+        .map(|path| {
+            let mut opts = DBOptions::new();
+            opts.create_if_missing(true).set_write_buffer_manager(&wbm);
+            let mut cf_opts = ColumnFamilyOptions::new();
+            // Large enough that only the write buffer manager can trigger flushes.
+            cf_opts
+                .set_write_buffer_size(64 * 1024 * 1024)
+                .set_disable_auto_compactions(true);
+            DB::open_cf(
+                opts,
+                path.path().to_str().unwrap(),
+                vec![("default", cf_opts)],
+            )
+            .unwrap()
+        })
+        .collect();
+
+    let value = vec![b'v'; 1024];
+    for i in 0..1024 {
+        let db = &dbs[i % dbs.len()];
+        db.put(format!("k{:04}", i).as_bytes(), &value).unwrap();
+    }
+    let level0_prop = "rocksdb.num-files-at-level0";
+    let flushed = || {
+        dbs.iter()
+            .map(|db| db.get_property_int(level0_prop).unwrap())
+            .sum::<u64>()
+    };
+    for _ in 0..100 {
+        if flushed() > 0 {
+            break;
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    assert!(flushed() > 0);
+    assert!(wbm.memory_usage() > 0);
+}
+
+#[test]
+fn test_shared_env_across_dbs() {
+    let path = tempdir_with_prefix("_rust_rocksdb_test_shared_env_across_dbs");
+    let env = Arc::new(Env::new_mem());
+    let open = |name: &str| {
+        let mut opts = DBOptions::new();
+        opts.create_if_missing(true);
+        opts.set_env(env.clone());
+        DB::open(opts, path.path().join(name).to_str().unwrap()).unwrap()
+    };
+    let db1 = open("db1");
+    let db2 = open("db2");
+    // Each DB keeps its own reference to the env.
+    assert_eq!(Arc::strong_count(&env), 3);
+    drop(env);
+
+    db1.put(b"k1", b"v1").unwrap();
+    db2.put(b"k2", b"v2").unwrap();
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    db1.flush(&fopts).unwrap();
+    db2.flush(&fopts).unwrap();
+    // Nothing is written to the real file system.
+    assert!(!path.path().join("db1").exists());
+    assert!(!path.path().join("db2").exists());
+
+    assert_eq!(db1.get(b"k1").unwrap().unwrap(), b"v1");
+    assert!(db1.get(b"k2").unwrap().is_none());
+    assert_eq!(db2.get(b"k2").unwrap().unwrap(), b"v2");
+    assert!(db2.get(b"k1").unwrap().is_none());
+
+    drop(db1);
+    let env = db2.env().unwrap();
+    assert_eq!(Arc::strong_count(&env), 2);
+}
+
+#[test]
+fn test_env_background_threads_with_priority() {
+    let path = tempdir_with_prefix("_rust_rocksdb_test_env_background_threads_with_priority");
+    let env = Arc::new(Env::new_mem());
+    env.set_background_threads_with_priority(2, DBEnvPriority::Low);
+    env.set_background_threads_with_priority(1, DBEnvPriority::High);
+    assert_eq!(env.get_background_threads(DBEnvPriority::Low), 2);
+    assert_eq!(env.get_background_threads(DBEnvPriority::High), 1);
+    assert_eq!(env.get_high_priority_background_threads(), 1);
+
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    opts.set_env(env.clone());
+    opts.set_max_background_jobs(3);
+    let mut cf_opts = ColumnFamilyOptions::new();
+    cf_opts.set_level_zero_file_num_compaction_trigger(2);
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![("default", cf_opts)],
+    )
+    .unwrap();
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    for i in 0..5 {
+        for j in 0..10 {
+            let key = format!("k{}_{}", i, j);
+            db.put(key.as_bytes(), b"v").unwrap();
+        }
+        db.flush(&fopts).unwrap();
+    }
+    db.wait_for_compact(false, Some(Duration::from_secs(10)))
+        .unwrap();
+    assert_eq!(db.get(b"k4_9").unwrap().unwrap(), b"v");
+}
+
+#[test]
+fn test_mem_env_lifecycle() {
+    let path = tempdir_with_prefix("_rust_rocksdb_test_mem_env_lifecycle");
+    let db_path = path.path().join("db");
+    let db_path = db_path.to_str().unwrap();
+    let env = Arc::new(Env::new_mem());
+    let open = || {
+        let mut opts = DBOptions::new();
+        opts.create_if_missing(true);
+        opts.set_env(env.clone());
+        DB::open(opts, db_path).unwrap()
+    };
+
+    let db = open();
+    db.put(b"k1", b"v1").unwrap();
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    db.flush(&fopts).unwrap();
+    db.put(b"k2", b"v2").unwrap();
+    drop(db);
+    assert!(!Path::new(db_path).exists());
+    assert!(env.file_exists(&format!("{}/CURRENT", db_path)).is_ok());
+
+    // Both the flushed and the unflushed key survive a reopen on the same env.
+    let db = open();
+    assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+    assert_eq!(db.get(b"k2").unwrap().unwrap(), b"v2");
+    assert!(!Path::new(db_path).exists());
+}
+
+fn concat_merge(_: &[u8], existing_val: Option<&[u8]>, operands: &mut MergeOperands) -> Vec<u8> {
+    let mut result = existing_val.map_or_else(Vec::new, |v| v.to_vec());
+    for op in operands {
+        result.extend_from_slice(op);
+    }
+    result
+}
+
+#[test]
+fn test_enable_blob_files_with_merge_operator() {
+    let path = tempdir_with_prefix("_rust_rocksdb_test_enable_blob_files_with_merge_operator");
+    let path_str = path.path().to_str().unwrap();
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+
+    let mut blob_opts = ColumnFamilyOptions::new();
+    blob_opts.set_enable_blob_files(true);
+    blob_opts.set_min_blob_size(16);
+    assert!(blob_opts.get_enable_blob_files());
+    let mut merge_opts = ColumnFamilyOptions::new();
+    merge_opts.add_merge_operator("concat", concat_merge);
+    let mut invalid_opts = ColumnFamilyOptions::new();
+    invalid_opts.set_enable_blob_files(true);
+    invalid_opts.add_merge_operator("concat", concat_merge);
+
+    let err = DB::open_cf(
+        opts.clone(),
+        path_str,
+        vec![
+            ("default", ColumnFamilyOptions::new()),
+            ("invalid", invalid_opts),
+        ],
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        "column family invalid: enable_blob_files can't be used with a merge operator"
+    );
+
+    let mut db = DB::open_cf(
+        opts,
+        path_str,
+        vec![
+            ("default", ColumnFamilyOptions::new()),
+            ("blob", blob_opts),
+            ("merge", merge_opts),
+        ],
+    )
+    .unwrap();
+    let value = vec![b'v'; 64];
+    let blob_cf = db.cf_handle("blob").unwrap();
+    db.put_cf(blob_cf, b"k", &value).unwrap();
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    db.flush_cf(blob_cf, &fopts).unwrap();
+    assert_eq!(db.get_cf(blob_cf, b"k").unwrap().unwrap(), &*value);
+    let merge_cf = db.cf_handle("merge").unwrap();
+    db.merge_cf(merge_cf, b"k", b"a").unwrap();
+    db.merge_cf(merge_cf, b"k", b"b").unwrap();
+    assert_eq!(db.get_cf(merge_cf, b"k").unwrap().unwrap(), b"ab");
+
+    let mut invalid_opts = ColumnFamilyOptions::new();
+    invalid_opts.set_enable_blob_files(true);
+    invalid_opts.add_merge_operator("concat", concat_merge);
+    assert!(db.create_cf(("invalid", invalid_opts)).is_err());
+    assert!(db.cf_handle("invalid").is_none());
+}
+
+#[test]
+fn test_max_write_buffer_size_to_maintain() {
+    let path = tempdir_with_prefix("_rust_rocksdb_test_max_write_buffer_size_to_maintain");
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    let mut cf_opts = ColumnFamilyOptions::new();
+    cf_opts.set_write_buffer_size(64 << 10);
+    cf_opts.set_max_write_buffer_size_to_maintain(1 << 20);
+    assert_eq!(cf_opts.get_max_write_buffer_size_to_maintain(), 1 << 20);
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![("default", cf_opts)],
+    )
+    .unwrap();
+    let cf = db.cf_handle("default").unwrap();
+    assert_eq!(
+        db.get_options_cf(cf)
+            .get_max_write_buffer_size_to_maintain(),
+        1 << 20
+    );
+
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    for i in 0..3 {
+        db.put(format!("k{}", i).as_bytes(), b"v").unwrap();
+        db.flush(&fopts).unwrap();
+    }
+    db.put(b"k3", b"v").unwrap();
+    for i in 0..4 {
+        assert_eq!(db.get(format!("k{}", i).as_bytes()).unwrap().unwrap(), b"v");
+    }
+    // The flushed memtables are still accounted for.
+    assert!(
+        db.get_property_int("rocksdb.num-immutable-mem-table-flushed")
+            .unwrap()
+            > 0
+    );
+}
+
+#[derive(Default, Clone)]
+struct CountingInspector {
+    read: Arc<AtomicUsize>,
+    written: Arc<AtomicUsize>,
+    // Caps each write request; the env retries with the rest.
+    max_write: usize,
+}
+
+impl FileSystemInspector for CountingInspector {
+    fn read(&self, len: usize) -> Result<usize, String> {
+        self.read.fetch_add(len, Ordering::SeqCst);
+        Ok(len)
+    }
+
+    fn write(&self, len: usize) -> Result<usize, String> {
+        let allowed = len.min(self.max_write);
+        self.written.fetch_add(allowed, Ordering::SeqCst);
+        Ok(allowed)
+    }
+}
+
+#[test]
+fn test_file_system_inspector() {
+    let path = tempdir_with_prefix("_rust_rocksdb_test_file_system_inspector");
+    let inspector = CountingInspector {
+        max_write: 1024,
+        ..Default::default()
+    };
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    opts.set_file_system_inspector(inspector.clone()).unwrap();
+    let db = DB::open(opts, path.path().to_str().unwrap()).unwrap();
+    let value = vec![b'v'; 4096];
+    for i in 0..100 {
+        db.put(format!("k{:03}", i).as_bytes(), &value).unwrap();
+    }
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    db.flush(&fopts).unwrap();
+    assert!(inspector.written.load(Ordering::SeqCst) >= 100 * value.len());
+
+    let before = inspector.read.load(Ordering::SeqCst);
+    let mut iter = db.iter();
+    iter.seek(SeekKey::Start).unwrap();
+    let mut count = 0;
+    while iter.valid().unwrap() {
+        assert_eq!(iter.value(), &*value);
+        count += 1;
+        iter.next().unwrap();
+    }
+    assert_eq!(count, 100);
+    assert!(inspector.read.load(Ordering::SeqCst) > before);
+}
@@ -0,0 +1,46 @@
+// Copyright 2023 TiKV Project Authors. Licensed under Apache-2.0.
+
+use rocksdb::{
+    ColumnFamilyOptions, DBOptions, FixedPrefixSstPartitionerFactory, FlushOptions, Writable, DB,
+};
+
+use super::tempdir_with_prefix;
+
+#[test]
+fn test_fixed_prefix_sst_partitioner() {
+    let path = tempdir_with_prefix("_rust_rocksdb_fixed_prefix_sst_partitioner");
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    let mut cf_opts = ColumnFamilyOptions::new();
+    cf_opts.set_sst_partitioner_factory(FixedPrefixSstPartitionerFactory::new(3));
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![("default", cf_opts)],
+    )
+    .unwrap();
+
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    // Every flush covers several prefixes.
+    for round in 0..3 {
+        for prefix in &["p00", "p01", "p02", "p10"] {
+            for i in 0..10 {
+                let key = format!("{}_{}_{}", prefix, round, i);
+                db.put(key.as_bytes(), b"v").unwrap();
+            }
+        }
+        db.flush(&fopts).unwrap();
+    }
+
+    let cf = db.cf_handle("default").unwrap();
+    db.compact_range_cf(cf, None, None);
+
+    let cf_meta = db.get_column_family_meta_data(cf);
+    assert_eq!(cf_meta.get_level(0).get_files().len(), 0);
+    let files = cf_meta.get_level(1).get_files();
+    assert_eq!(files.len(), 4);
+    for f in files {
+        assert_eq!(f.get_smallestkey()[..3], f.get_largestkey()[..3]);
+    }
+}
@@ -120,3 +120,135 @@ fn test_shared_db_statistics() {
     let get_micros = statistics.get_histogram(HistogramType::DbGet).unwrap();
     assert_eq!(get_micros.max, 0.0);
 }
+
+#[test]
+fn test_block_cache_compressed_statistics() {
+    let path = tempdir_with_prefix("_rust_rocksdb_block_cache_compressed_statistics");
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    let statistics = Statistics::new();
+    opts.set_statistics(&statistics);
+    let mut cf_opts = ColumnFamilyOptions::new();
+    cf_opts.compression(DBCompressionType::Snappy);
+    let mut block_opts = BlockBasedOptions::new();
+    // An uncompressed cache without capacity forces every read to fall
+    // through to the compressed tier.
+    let mut cache_opts = LRUCacheOptions::new();
+    cache_opts.set_capacity(0);
+    block_opts.set_block_cache(&Cache::new_lru_cache(cache_opts));
+    let mut compressed_cache_opts = LRUCacheOptions::new();
+    compressed_cache_opts.set_capacity(8 * 1024 * 1024);
+    block_opts.set_block_cache_compressed(&Cache::new_lru_cache(compressed_cache_opts));
+    cf_opts.set_block_based_table_factory(&block_opts);
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![("default", cf_opts)],
+    )
+    .unwrap();
+
+    let value = b"value".repeat(100);
+    for i in 0..100 {
+        db.put(format!("k{:03}", i).as_bytes(), &value).unwrap();
+    }
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    db.flush(&fopts).unwrap();
+
+    for _ in 0..3 {
+        for i in 0..100 {
+            assert_eq!(
+                db.get(format!("k{:03}", i).as_bytes()).unwrap().unwrap(),
+                &value[..]
+            );
+        }
+    }
+    assert!(statistics.get_ticker_count(TickerType::BlockCacheCompressedAdd) > 0);
+    assert!(statistics.get_ticker_count(TickerType::BlockCacheCompressedHit) > 0);
+}
+
+#[test]
+fn test_ribbon_filter_statistics() {
+    let path = tempdir_with_prefix("_rust_rocksdb_ribbon_filter_statistics");
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    let statistics = Statistics::new();
+    opts.set_statistics(&statistics);
+    let mut cf_opts = ColumnFamilyOptions::new();
+    let mut block_opts = BlockBasedOptions::new();
+    // Ribbon filters need format_version 5. A negative `bloom_before_level`
+    // uses ribbon on every level, including L0.
+    block_opts.set_format_version(5);
+    block_opts.set_ribbon_filter(10.0, -1);
+    cf_opts.set_block_based_table_factory(&block_opts);
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![("default", cf_opts)],
+    )
+    .unwrap();
+
+    for i in 0..100 {
+        db.put(format!("k{:03}", i).as_bytes(), b"v").unwrap();
+    }
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    db.flush(&fopts).unwrap();
+
+    for i in 0..100 {
+        assert_eq!(
+            db.get(format!("k{:03}", i).as_bytes()).unwrap().unwrap(),
+            b"v"
+        );
+    }
+    // Absent keys inside the file's key range are rejected by the filter.
+    for i in 0..99 {
+        assert!(db.get(format!("k{:03}x", i).as_bytes()).unwrap().is_none());
+    }
+    assert!(statistics.get_ticker_count(TickerType::BloomFilterUseful) > 0);
+}
+
+#[test]
+fn test_db_options_statistics() {
+    let path = tempdir_with_prefix("_rust_rocksdb_db_options_statistics");
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    assert!(opts.get_statistics().is_empty());
+    assert_eq!(
+        opts.get_statistics_ticker_count(TickerType::BlockCacheHit),
+        0
+    );
+    assert!(opts
+        .get_statistics_histogram(HistogramType::DbGet)
+        .is_none());
+
+    opts.set_statistics(&Statistics::new());
+    let db = DB::open(opts, path.path().to_str().unwrap()).unwrap();
+    db.put(b"k1", b"v1").unwrap();
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    db.flush(&fopts).unwrap();
+    for _ in 0..10 {
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+    }
+
+    let opts = db.get_db_options();
+    assert!(opts.get_statistics_ticker_count(TickerType::BlockCacheHit) > 0);
+    let hist = opts.get_statistics_histogram(HistogramType::DbGet).unwrap();
+    assert!(hist.max >= hist.percentile99);
+    assert!(hist.percentile99 >= hist.percentile95);
+    assert!(hist.percentile95 >= hist.median);
+
+    assert!(opts.get_and_reset_statistics_ticker_count(TickerType::BlockCacheHit) > 0);
+    assert_eq!(
+        opts.get_statistics_ticker_count(TickerType::BlockCacheHit),
+        0
+    );
+    assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+    assert!(opts.get_statistics_ticker_count(TickerType::BlockCacheHit) > 0);
+    opts.reset_statistics();
+    assert_eq!(
+        opts.get_statistics_ticker_count(TickerType::BlockCacheHit),
+        0
+    );
+}
@@ -296,3 +296,231 @@ fn test_table_properties_with_table_filter() {
     // First sst will be skipped
     assert_eq!(iter.key(), key5.as_ref());
 }
+
+const DELETE_COUNT_PROP: &[u8] = b"delete-count";
+const VALUE_BYTES_PROP: &[u8] = b"value-bytes";
+
+#[derive(Default)]
+struct DeleteCounter {
+    num_deletes: u32,
+}
+
+impl TablePropertiesCollector for DeleteCounter {
+    fn add(&mut self, _: &[u8], _: &[u8], entry_type: DBEntryType, _: u64, _: u64) {
+        if let DBEntryType::Delete | DBEntryType::SingleDelete = entry_type {
+            self.num_deletes += 1;
+        }
+    }
+
+    fn finish(&mut self) -> HashMap<Vec<u8>, Vec<u8>> {
+        let mut props = HashMap::new();
+        props.insert(DELETE_COUNT_PROP.to_vec(), encode_u32(self.num_deletes));
+        props
+    }
+}
+
+struct DeleteCounterFactory {}
+
+impl TablePropertiesCollectorFactory<DeleteCounter> for DeleteCounterFactory {
+    fn create_table_properties_collector(&mut self, _: u32) -> DeleteCounter {
+        DeleteCounter::default()
+    }
+}
+
+#[derive(Default)]
+struct ValueBytesCollector {
+    value_bytes: u32,
+}
+
+impl TablePropertiesCollector for ValueBytesCollector {
+    fn add(&mut self, _: &[u8], value: &[u8], entry_type: DBEntryType, _: u64, _: u64) {
+        if let DBEntryType::Put = entry_type {
+            self.value_bytes += value.len() as u32;
+        }
+    }
+
+    fn finish(&mut self) -> HashMap<Vec<u8>, Vec<u8>> {
+        let mut props = HashMap::new();
+        props.insert(VALUE_BYTES_PROP.to_vec(), encode_u32(self.value_bytes));
+        props
+    }
+}
+
+struct ValueBytesFactory {}
+
+impl TablePropertiesCollectorFactory<ValueBytesCollector> for ValueBytesFactory {
+    fn create_table_properties_collector(&mut self, _: u32) -> ValueBytesCollector {
+        ValueBytesCollector::default()
+    }
+}
+
+#[test]
+fn test_multiple_table_properties_collector_factories() {
+    let mut opts = DBOptions::new();
+    let mut cf_opts = ColumnFamilyOptions::new();
+    opts.create_if_missing(true);
+    cf_opts.add_table_properties_collector_factory::<DeleteCounter, DeleteCounterFactory>(
+        "delete-counter",
+        DeleteCounterFactory {},
+    );
+    cf_opts.add_table_properties_collector_factory::<ValueBytesCollector, ValueBytesFactory>(
+        "value-bytes-collector",
+        ValueBytesFactory {},
+    );
+    let path = tempdir_with_prefix("_rust_rocksdb_multiple_collectors");
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![("default", cf_opts)],
+    )
+    .unwrap();
+
+    db.put(b"key1", b"value1").unwrap();
+    db.put(b"key2", b"value22").unwrap();
+    db.delete(b"key3").unwrap();
+    db.delete(b"key4").unwrap();
+    db.delete(b"key5").unwrap();
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    db.flush(&fopts).unwrap();
+
+    let collection = db.get_properties_of_all_tables().unwrap();
+    assert_eq!(collection.len(), 1);
+    for (_, props) in collection.iter() {
+        assert_eq!(
+            props.property_collectors_names(),
+            "[delete-counter,value-bytes-collector]"
+        );
+        let user_props = props.user_collected_properties();
+        assert_eq!(decode_u32(&user_props[DELETE_COUNT_PROP]), 3);
+        assert_eq!(decode_u32(&user_props[VALUE_BYTES_PROP]), 13);
+    }
+}
+
+struct TsRangeCollector {
+    min_ts: u64,
+    max_ts: u64,
+}
+
+impl TablePropertiesCollector for TsRangeCollector {
+    fn add(&mut self, _: &[u8], value: &[u8], _: DBEntryType, _: u64, _: u64) {
+        let ts: u64 = std::str::from_utf8(value).unwrap().parse().unwrap();
+        self.min_ts = self.min_ts.min(ts);
+        self.max_ts = self.max_ts.max(ts);
+    }
+
+    fn finish(&mut self) -> HashMap<Vec<u8>, Vec<u8>> {
+        let mut props = HashMap::new();
+        props.insert(b"min_ts".to_vec(), self.min_ts.to_le_bytes().to_vec());
+        props.insert(b"max_ts".to_vec(), self.max_ts.to_le_bytes().to_vec());
+        // Varint of 300.
+        props.insert(b"varint".to_vec(), vec![0xac, 0x02]);
+        props
+    }
+}
+
+struct TsRangeFactory;
+
+impl TablePropertiesCollectorFactory<TsRangeCollector> for TsRangeFactory {
+    fn create_table_properties_collector(&mut self, _: u32) -> TsRangeCollector {
+        TsRangeCollector {
+            min_ts: u64::MAX,
+            max_ts: 0,
+        }
+    }
+}
+
+#[test]
+fn test_user_collected_properties_typed_getters() {
+    let mut opts = DBOptions::new();
+    let mut cf_opts = ColumnFamilyOptions::new();
+    opts.create_if_missing(true);
+    cf_opts.add_table_properties_collector_factory::<TsRangeCollector, TsRangeFactory>(
+        "ts-range-collector",
+        TsRangeFactory,
+    );
+    let path = tempdir_with_prefix("_rust_rocksdb_user_collected_properties_typed_getters");
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![("default", cf_opts)],
+    )
+    .unwrap();
+
+    db.put(b"k1", b"20").unwrap();
+    db.put(b"k2", b"5").unwrap();
+    db.put(b"k3", b"300").unwrap();
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    db.flush(&fopts).unwrap();
+
+    let collection = db.get_properties_of_all_tables().unwrap();
+    assert_eq!(collection.len(), 1);
+    for (_, props) in collection.iter() {
+        let user_props = props.user_collected_properties();
+        assert_eq!(user_props.get_u64(b"min_ts"), Some(5));
+        assert_eq!(user_props.get_u64(b"max_ts"), Some(300));
+        assert_eq!(user_props.get_varint_u64(b"varint"), Some(300));
+        assert_eq!(user_props.get_u64(b"varint"), None);
+        assert_eq!(user_props.get_u64(b"missing"), None);
+        let keys: Vec<_> = user_props.iter().map(|(k, _)| k).collect();
+        assert!(keys.contains(&&b"min_ts"[..]));
+        assert!(keys.contains(&&b"max_ts"[..]));
+    }
+}
+
+#[test]
+fn test_table_properties_in_disjoint_ranges() {
+    let path = tempdir_with_prefix("_rust_rocksdb_table_properties_in_disjoint_ranges");
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![
+            ("default", ColumnFamilyOptions::new()),
+            ("cf", ColumnFamilyOptions::new()),
+        ],
+    )
+    .unwrap();
+    let cf = db.cf_handle("cf").unwrap();
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+
+    // One sst file per prefix, with a different number of entries each.
+    for (prefix, count) in &[("a", 2), ("c", 3), ("e", 4)] {
+        for i in 0..*count {
+            let key = format!("{}{}", prefix, i);
+            db.put_cf(cf, key.as_bytes(), b"v").unwrap();
+        }
+        db.flush_cf(cf, &fopts).unwrap();
+    }
+    assert_eq!(db.get_properties_of_all_tables_cf(cf).unwrap().len(), 3);
+
+    let entries = |ranges: &[Range]| {
+        let collection = db.get_properties_of_tables_in_range(cf, ranges).unwrap();
+        let mut entries: Vec<_> = collection
+            .iter()
+            .map(|(name, props)| {
+                assert!(name.ends_with(".sst"), "{}", name);
+                props.num_entries()
+            })
+            .collect();
+        entries.sort_unstable();
+        entries
+    };
+    assert_eq!(entries(&[Range::new(b"c", b"d")]), vec![3]);
+    assert_eq!(entries(&[Range::new(b"a1", b"c1")]), vec![2, 3]);
+    assert_eq!(
+        entries(&[Range::new(b"a", b"b"), Range::new(b"e", b"f")]),
+        vec![2, 4]
+    );
+    assert!(entries(&[Range::new(b"b", b"c")]).is_empty());
+    // The default column family has no sst files at all.
+    let default_cf = db.cf_handle("default").unwrap();
+    let collection = db
+        .get_properties_of_tables_in_range(default_cf, &[Range::new(b"a", b"z")])
+        .unwrap();
+    assert!(collection.is_empty());
+}
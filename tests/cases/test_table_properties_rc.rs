@@ -45,3 +45,29 @@ fn test_lifetimes() {
 
     drop(prop);
 }
+
+#[test]
+fn test_clone_outlives_original() {
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    let path = tempdir_with_prefix("table_properties_rc_clone");
+    let path = path.path().to_str().unwrap();
+    let db = DB::open(opts, path).unwrap();
+    for i in 0..3 {
+        db.put(format!("key{}", i).as_bytes(), b"value").unwrap();
+    }
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    db.flush(&fopts).unwrap();
+
+    let collection = db.get_properties_of_all_tables_rc().unwrap();
+    let cloned = collection.clone();
+    drop(collection);
+    assert_eq!(cloned.len(), 1);
+
+    let (_key, prop) = cloned.iter().next().unwrap();
+    let prop_clone = prop.clone();
+    drop(prop);
+    drop(cloned);
+    assert_eq!(prop_clone.num_entries(), 3);
+}
@@ -20,7 +20,7 @@ use rocksdb::{
     DBCompressionType, DBEntryType, DBOptions, DBStatisticsHistogramType as HistogramType,
     DBStatisticsTickerType as TickerType, FlushOptions, LRUCacheOptions, Range, ReadOptions,
     SeekKey, Statistics, TablePropertiesCollector, TablePropertiesCollectorFactory, TitanBlobIndex,
-    TitanDBOptions, UserCollectedProperties, Writable, WriteOptions, DB,
+    TitanBlobStats, TitanDBOptions, UserCollectedProperties, Writable, WriteOptions, DB,
 };
 
 use super::tempdir_with_prefix;
@@ -463,3 +463,99 @@ fn test_titan_statistics() {
 
     assert_eq!(get_micros.max, 0.0);
 }
+
+#[test]
+fn test_titan_blob_stats() {
+    let path = tempdir_with_prefix("_rust_rocksdb_titan_blob_stats");
+    let mut tdb_opts = TitanDBOptions::new();
+    tdb_opts.set_min_blob_size(16);
+    tdb_opts.set_disable_background_gc(true);
+    let mut opts = DBOptions::new();
+    opts.set_titandb_options(&tdb_opts);
+    opts.create_if_missing(true);
+    let mut cf_opts = ColumnFamilyOptions::new();
+    cf_opts.set_titandb_options(&tdb_opts);
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![("default", cf_opts)],
+    )
+    .unwrap();
+    let cf = db.cf_handle("default").unwrap();
+    assert_eq!(
+        db.get_titan_blob_stats_cf(cf).unwrap(),
+        TitanBlobStats::default()
+    );
+
+    let value = vec![b'v'; 1024];
+    for i in 0..100 {
+        db.put(format!("k{:03}", i).as_bytes(), &value).unwrap();
+    }
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    db.flush(&fopts).unwrap();
+
+    let stats = db.get_titan_blob_stats_cf(cf).unwrap();
+    assert_eq!(stats.num_live_blob_file, 1);
+    assert_eq!(stats.num_obsolete_blob_file, 0);
+    assert!(stats.live_blob_size > 0);
+    assert!(stats.live_blob_file_size >= stats.live_blob_size);
+    // Nothing is overwritten yet, so no data in the blob file is discardable.
+    assert_eq!(stats.num_discardable_ratio_le0_file, 1);
+
+    // A db without Titan has no blob files.
+    let path = tempdir_with_prefix("_rust_rocksdb_titan_blob_stats_plain");
+    let db = DB::open_default(path.path().to_str().unwrap()).unwrap();
+    let cf = db.cf_handle("default").unwrap();
+    assert!(db.get_titan_blob_stats_cf(cf).is_none());
+}
+
+#[test]
+fn test_titan_switch_blob_run_mode() {
+    let path = tempdir_with_prefix("_rust_rocksdb_titan_switch_blob_run_mode");
+    let mut tdb_opts = TitanDBOptions::new();
+    tdb_opts.set_min_blob_size(16);
+    tdb_opts.set_disable_background_gc(true);
+    let mut opts = DBOptions::new();
+    opts.set_titandb_options(&tdb_opts);
+    opts.create_if_missing(true);
+    let mut cf_opts = ColumnFamilyOptions::new();
+    cf_opts.set_titandb_options(&tdb_opts);
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![("default", cf_opts)],
+    )
+    .unwrap();
+    let cf = db.cf_handle("default").unwrap();
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+
+    let v1 = vec![b'1'; 1024];
+    db.put(b"k1", &v1).unwrap();
+    db.flush_cf(cf, &fopts).unwrap();
+    let stats = db.get_titan_blob_stats_cf(cf).unwrap();
+    assert_eq!(stats.num_live_blob_file, 1);
+
+    db.set_options_cf(cf, &[("blob_run_mode", "kFallback")])
+        .unwrap();
+    let v2 = vec![b'2'; 1024];
+    db.put(b"k2", &v2).unwrap();
+    db.flush_cf(cf, &fopts).unwrap();
+    // The second value is stored inline in the LSM, no blob file is added.
+    assert_eq!(db.get_titan_blob_stats_cf(cf).unwrap(), stats);
+    assert_eq!(db.get(b"k1").unwrap().unwrap(), &v1[..]);
+    assert_eq!(db.get(b"k2").unwrap().unwrap(), &v2[..]);
+
+    assert!(db
+        .set_options_cf(cf, &[("blob_run_mode", "kUnknown")])
+        .is_err());
+    db.set_options_cf(cf, &[("blob_run_mode", "kNormal")])
+        .unwrap();
+    db.put(b"k3", &v2).unwrap();
+    db.flush_cf(cf, &fopts).unwrap();
+    assert_eq!(
+        db.get_titan_blob_stats_cf(cf).unwrap().num_live_blob_file,
+        2
+    );
+}
@@ -0,0 +1,111 @@
+// Copyright 2023 TiKV Project Authors. Licensed under Apache-2.0.
+
+use rocksdb::{ColumnFamilyOptions, DBOptions, DBValueType, Writable, WriteBatch, DB};
+
+use super::tempdir_with_prefix;
+
+#[test]
+fn test_get_updates_since() {
+    let path = tempdir_with_prefix("_rust_rocksdb_test_get_updates_since");
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    let db = DB::open(opts, path.path().to_str().unwrap()).unwrap();
+
+    let mut expected = vec![];
+    for i in 0..3 {
+        let wb = WriteBatch::new();
+        for j in 0..=i {
+            let key = format!("k{}_{}", i, j);
+            wb.put(key.as_bytes(), b"v").unwrap();
+            expected.push(key.into_bytes());
+        }
+        if i == 2 {
+            wb.delete(b"k0_0").unwrap();
+        }
+        db.write(&wb).unwrap();
+    }
+
+    let mut puts = vec![];
+    let mut deletes = vec![];
+    let mut seqs = vec![];
+    for res in db.get_updates_since(1).unwrap() {
+        let (seq, wb) = res.unwrap();
+        seqs.push(seq);
+        for (value_type, _, key, _) in wb.iter() {
+            match value_type {
+                DBValueType::TypeValue => puts.push(key.to_vec()),
+                DBValueType::TypeDeletion => deletes.push(key.to_vec()),
+                other => panic!("unexpected {:?}", other),
+            }
+        }
+    }
+    assert_eq!(puts, expected);
+    assert_eq!(deletes, vec![b"k0_0".to_vec()]);
+    // Each batch starts right after the operations of the previous one.
+    assert_eq!(seqs, vec![1, 2, 4]);
+    assert_eq!(db.get_latest_sequence_number(), 7);
+
+    // Starting in the middle returns the batch containing the sequence.
+    let mut iter = db.get_updates_since(3).unwrap();
+    let (seq, wb) = iter.next().unwrap().unwrap();
+    assert_eq!((seq, wb.count()), (2, 2));
+    let (seq, wb) = iter.next().unwrap().unwrap();
+    assert_eq!((seq, wb.count()), (4, 4));
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn test_get_updates_since_cf() {
+    let path = tempdir_with_prefix("_rust_rocksdb_test_get_updates_since_cf");
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![
+            ("default", ColumnFamilyOptions::new()),
+            ("cf1", ColumnFamilyOptions::new()),
+        ],
+    )
+    .unwrap();
+    let default_cf = db.cf_handle("default").unwrap();
+    let cf1 = db.cf_handle("cf1").unwrap();
+
+    let start = db.get_latest_sequence_number() + 1;
+    db.put_cf(default_cf, b"k1", b"v1").unwrap();
+    let wb = WriteBatch::new();
+    wb.put_cf(default_cf, b"k2", b"v2").unwrap();
+    wb.put_cf(cf1, b"k3", b"v3").unwrap();
+    wb.delete_cf(cf1, b"k4").unwrap();
+    db.write(&wb).unwrap();
+    db.put_cf(cf1, b"k5", b"v5").unwrap();
+
+    let mut ops = vec![];
+    let mut seqs = vec![];
+    for res in db.get_updates_since_cf(start, &[cf1.id()]).unwrap() {
+        let (seq, wb) = res.unwrap();
+        seqs.push(seq);
+        for (value_type, cf, key, _) in wb.iter() {
+            assert_eq!(cf, cf1.id());
+            ops.push((value_type, key.to_vec()));
+        }
+    }
+    // The batch only writing to the default CF is skipped.
+    assert_eq!(seqs, vec![start + 1, start + 4]);
+    assert_eq!(
+        ops,
+        vec![
+            (DBValueType::TypeValue, b"k3".to_vec()),
+            (DBValueType::TypeDeletion, b"k4".to_vec()),
+            (DBValueType::TypeValue, b"k5".to_vec()),
+        ]
+    );
+
+    let count: usize = db
+        .get_updates_since_cf(start, &[default_cf.id()])
+        .unwrap()
+        .map(|res| res.unwrap().1.count())
+        .sum();
+    assert_eq!(count, 2);
+}
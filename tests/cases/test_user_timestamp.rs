@@ -0,0 +1,497 @@
+// Copyright 2023 TiKV Project Authors. Licensed under Apache-2.0.
+
+use rocksdb::{
+    ColumnFamilyOptions, CompactionFilter, CompactionFilterDecision, CompactionFilterValueType,
+    DBOptions, EnvOptions, FlushOptions, MergeOperands, ReadOptions, SeekKey, SstFileWriter,
+    Writable, WriteOptions, DB,
+};
+
+use super::tempdir_with_prefix;
+
+fn open_ts_db(path: &str) -> DB {
+    let mut ts_opts = ColumnFamilyOptions::new();
+    ts_opts.set_bytewise_comparator_with_u64_ts();
+    open_ts_db_with(path, ts_opts)
+}
+
+fn open_ts_db_with(path: &str, ts_opts: ColumnFamilyOptions) -> DB {
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    DB::open_cf(
+        opts,
+        path,
+        vec![("default", ColumnFamilyOptions::new()), ("ts", ts_opts)],
+    )
+    .unwrap()
+}
+
+fn put_at(db: &DB, key: &[u8], value: &[u8], ts: u64) {
+    let cf = db.cf_handle("ts").unwrap();
+    let mut wopts = WriteOptions::new();
+    wopts.set_timestamp(ts.to_le_bytes().to_vec());
+    db.put_cf_opt(cf, key, value, &wopts).unwrap();
+}
+
+fn get_at(db: &DB, key: &[u8], ts: u64) -> Option<Vec<u8>> {
+    let cf = db.cf_handle("ts").unwrap();
+    let mut ropts = ReadOptions::new();
+    ropts.set_timestamp(ts.to_le_bytes().to_vec());
+    db.get_cf_opt(cf, key, &ropts).unwrap().map(|v| v.to_vec())
+}
+
+#[test]
+fn test_user_timestamp_read_write() {
+    let path = tempdir_with_prefix("_rust_rocksdb_user_timestamp_read_write");
+    let db = open_ts_db(path.path().to_str().unwrap());
+    put_at(&db, b"k1", b"v1", 10);
+    put_at(&db, b"k1", b"v2", 20);
+
+    assert_eq!(get_at(&db, b"k1", 5), None);
+    assert_eq!(get_at(&db, b"k1", 10).unwrap(), b"v1");
+    assert_eq!(get_at(&db, b"k1", 15).unwrap(), b"v1");
+    assert_eq!(get_at(&db, b"k1", 20).unwrap(), b"v2");
+    assert_eq!(get_at(&db, b"k1", u64::MAX).unwrap(), b"v2");
+    assert_eq!(get_at(&db, b"k2", u64::MAX), None);
+}
+
+#[test]
+fn test_user_timestamp_slice_reuse() {
+    let path = tempdir_with_prefix("_rust_rocksdb_user_timestamp_slice_reuse");
+    let db = open_ts_db(path.path().to_str().unwrap());
+    for ts in (10..=100).step_by(10) {
+        put_at(&db, b"k", format!("v{}", ts).as_bytes(), ts);
+    }
+
+    let cf = db.cf_handle("ts").unwrap();
+    let mut ts_buf = [0u8; 8];
+    let mut ropts = ReadOptions::new();
+    for ts in 0..110u64 {
+        ts_buf.copy_from_slice(&ts.to_le_bytes());
+        // `ts_buf` is not modified until the next iteration sets it again.
+        unsafe {
+            ropts.set_timestamp_slice(&ts_buf);
+        }
+        let value = db.get_cf_opt(cf, b"k", &ropts).unwrap();
+        if ts < 10 {
+            assert!(value.is_none());
+        } else {
+            let expected = format!("v{}", ts.min(100) / 10 * 10);
+            assert_eq!(value.unwrap(), expected.as_bytes());
+        }
+    }
+}
+
+#[test]
+fn test_multi_get_cf_opt_ts_into() {
+    let path = tempdir_with_prefix("_rust_rocksdb_multi_get_cf_opt_ts_into");
+    let db = open_ts_db(path.path().to_str().unwrap());
+    put_at(&db, b"k1", b"v1", 10);
+    put_at(&db, b"k2", b"v2", 20);
+    put_at(&db, b"k1", b"v1_new", 30);
+
+    let cf = db.cf_handle("ts").unwrap();
+    let keys: Vec<&[u8]> = vec![b"k1", b"k2", b"k3"];
+    let mut out = Vec::new();
+    let mut ropts = ReadOptions::new();
+    ropts.set_timestamp(25u64.to_le_bytes().to_vec());
+    db.multi_get_cf_opt_ts_into(cf, &keys, &ropts, &mut out)
+        .unwrap();
+    assert_eq!(
+        out,
+        vec![
+            Some((b"v1".to_vec(), 10u64.to_le_bytes().to_vec())),
+            Some((b"v2".to_vec(), 20u64.to_le_bytes().to_vec())),
+            None,
+        ]
+    );
+
+    put_at(&db, b"k3", b"v3", 40);
+    let mut ropts = ReadOptions::new();
+    ropts.set_timestamp(u64::MAX.to_le_bytes().to_vec());
+    db.multi_get_cf_opt_ts_into(cf, &keys[..], &ropts, &mut out)
+        .unwrap();
+    assert_eq!(
+        out,
+        vec![
+            Some((b"v1_new".to_vec(), 30u64.to_le_bytes().to_vec())),
+            Some((b"v2".to_vec(), 20u64.to_le_bytes().to_vec())),
+            Some((b"v3".to_vec(), 40u64.to_le_bytes().to_vec())),
+        ]
+    );
+
+    db.multi_get_cf_opt_ts_into(cf, &keys[1..2], &ropts, &mut out)
+        .unwrap();
+    assert_eq!(
+        out,
+        vec![Some((b"v2".to_vec(), 20u64.to_le_bytes().to_vec()))]
+    );
+}
+
+fn decode_ts(ts: &[u8]) -> u64 {
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(ts);
+    u64::from_le_bytes(buf)
+}
+
+struct WatermarkFilter {
+    watermark: u64,
+}
+
+impl CompactionFilter for WatermarkFilter {
+    fn timestamp_size(&self) -> usize {
+        8
+    }
+
+    fn unsafe_filter_with_ts(
+        &mut self,
+        _: usize,
+        key: &[u8],
+        ts: &[u8],
+        _: u64,
+        _: &[u8],
+        value_type: CompactionFilterValueType,
+    ) -> CompactionFilterDecision {
+        assert!(key.starts_with(b"k") && key.len() == 2);
+        if value_type == CompactionFilterValueType::Value && decode_ts(ts) < self.watermark {
+            CompactionFilterDecision::Remove
+        } else {
+            CompactionFilterDecision::Keep
+        }
+    }
+}
+
+#[test]
+fn test_user_timestamp_compaction_filter() {
+    let path = tempdir_with_prefix("_rust_rocksdb_user_timestamp_compaction_filter");
+    let mut ts_opts = ColumnFamilyOptions::new();
+    ts_opts.set_bytewise_comparator_with_u64_ts();
+    ts_opts
+        .set_compaction_filter("watermark", WatermarkFilter { watermark: 25 })
+        .unwrap();
+    let db = open_ts_db_with(path.path().to_str().unwrap(), ts_opts);
+    put_at(&db, b"k1", b"v1", 10);
+    put_at(&db, b"k2", b"v2", 20);
+    put_at(&db, b"k1", b"v1_new", 30);
+    let cf = db.cf_handle("ts").unwrap();
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    db.flush_cf(cf, &fopts).unwrap();
+    assert_eq!(get_at(&db, b"k1", 15).unwrap(), b"v1");
+    assert_eq!(get_at(&db, b"k2", 20).unwrap(), b"v2");
+
+    db.compact_range_cf(cf, None, None);
+    assert_eq!(get_at(&db, b"k1", 15), None);
+    assert_eq!(get_at(&db, b"k2", u64::MAX), None);
+    assert_eq!(get_at(&db, b"k1", u64::MAX).unwrap(), b"v1_new");
+}
+
+/// Collapses the versions of each key into its newest one, whose value is
+/// rewritten.
+#[derive(Default)]
+struct CollapseFilter {
+    current: Option<(Vec<u8>, u64)>,
+}
+
+impl CompactionFilter for CollapseFilter {
+    fn timestamp_size(&self) -> usize {
+        8
+    }
+
+    fn unsafe_filter_with_ts(
+        &mut self,
+        _: usize,
+        key: &[u8],
+        ts: &[u8],
+        _: u64,
+        value: &[u8],
+        value_type: CompactionFilterValueType,
+    ) -> CompactionFilterDecision {
+        if value_type != CompactionFilterValueType::Value {
+            return CompactionFilterDecision::Keep;
+        }
+        match &self.current {
+            Some((k, newest)) if k.as_slice() == key => {
+                assert!(decode_ts(ts) < *newest);
+                CompactionFilterDecision::Remove
+            }
+            _ => {
+                self.current = Some((key.to_vec(), decode_ts(ts)));
+                let mut new_value = b"collapsed:".to_vec();
+                new_value.extend_from_slice(value);
+                CompactionFilterDecision::ChangeValue(new_value)
+            }
+        }
+    }
+}
+
+#[test]
+fn test_user_timestamp_compaction_filter_change_value() {
+    let path = tempdir_with_prefix("_rust_rocksdb_user_timestamp_compaction_filter_change_value");
+    let mut ts_opts = ColumnFamilyOptions::new();
+    ts_opts.set_bytewise_comparator_with_u64_ts();
+    ts_opts
+        .set_compaction_filter("collapse", CollapseFilter::default())
+        .unwrap();
+    let db = open_ts_db_with(path.path().to_str().unwrap(), ts_opts);
+    put_at(&db, b"k1", b"a", 10);
+    put_at(&db, b"k1", b"b", 20);
+    put_at(&db, b"k1", b"c", 30);
+    put_at(&db, b"k2", b"d", 15);
+    let cf = db.cf_handle("ts").unwrap();
+    let mut fopts = FlushOptions::default();
+    fopts.set_wait(true);
+    db.flush_cf(cf, &fopts).unwrap();
+    assert_eq!(get_at(&db, b"k1", 25).unwrap(), b"b");
+
+    db.compact_range_cf(cf, None, None);
+    let mut ropts = ReadOptions::new();
+    ropts.set_timestamp(u64::MAX.to_le_bytes().to_vec());
+    assert_eq!(
+        db.get_cf_opt_ts(cf, b"k1", &ropts).unwrap(),
+        Some((b"collapsed:c".to_vec(), 30u64.to_le_bytes().to_vec()))
+    );
+    assert_eq!(
+        db.get_cf_opt_ts(cf, b"k2", &ropts).unwrap(),
+        Some((b"collapsed:d".to_vec(), 15u64.to_le_bytes().to_vec()))
+    );
+    assert_eq!(get_at(&db, b"k1", 25), None);
+}
+
+#[test]
+fn test_sst_file_writer_put_with_ts() {
+    let path = tempdir_with_prefix("_rust_rocksdb_sst_file_writer_put_with_ts");
+    let mut ts_opts = ColumnFamilyOptions::new();
+    ts_opts.set_bytewise_comparator_with_u64_ts();
+    let mut writer = SstFileWriter::new(EnvOptions::new(), ts_opts);
+    let sst_path = path.path().join("ts.sst");
+    writer.open(sst_path.to_str().unwrap()).unwrap();
+
+    writer
+        .put_with_ts(b"k1", &10u64.to_le_bytes(), b"v1")
+        .unwrap();
+    let err = writer
+        .put_with_ts(b"k2", &20u32.to_le_bytes(), b"v2")
+        .unwrap_err();
+    assert!(
+        err.contains("timestamp size mismatch: the comparator expects 8 bytes, got 4"),
+        "{}",
+        err
+    );
+    writer
+        .put_with_ts(b"k2", &20u64.to_le_bytes(), b"v2")
+        .unwrap();
+    let info = writer.finish().unwrap();
+    assert_eq!(info.num_entries(), 2);
+
+    // A writer without user timestamp rejects any timestamp.
+    let mut writer = SstFileWriter::new(EnvOptions::new(), ColumnFamilyOptions::new());
+    writer
+        .open(path.path().join("plain.sst").to_str().unwrap())
+        .unwrap();
+    let err = writer
+        .put_with_ts(b"k1", &10u64.to_le_bytes(), b"v1")
+        .unwrap_err();
+    assert!(err.contains("expects 0 bytes, got 8"), "{}", err);
+}
+
+#[test]
+fn test_iterator_seek_with_ts() {
+    let path = tempdir_with_prefix("_rust_rocksdb_iterator_seek_with_ts");
+    let db = open_ts_db(path.path().to_str().unwrap());
+    for (ts, suffix) in &[(10u64, "1"), (20, "2")] {
+        for k in 1..=3 {
+            let value = format!("v{}{}", k, suffix);
+            put_at(&db, format!("k{}", k).as_bytes(), value.as_bytes(), *ts);
+        }
+    }
+
+    let cf = db.cf_handle("ts").unwrap();
+    let mut ropts = ReadOptions::new();
+    ropts.set_timestamp(20u64.to_le_bytes().to_vec());
+    let mut iter = db.iter_cf_opt(cf, ropts);
+    assert!(iter.seek(SeekKey::Key(b"k3")).unwrap());
+    assert_eq!(iter.value(), b"v32");
+
+    let ts1 = 10u64.to_le_bytes();
+    assert!(iter.seek_with_ts(SeekKey::Key(b"k3"), &ts1).unwrap());
+    assert!(iter.key().starts_with(b"k3"));
+    assert_eq!(iter.value(), b"v31");
+    assert!(iter
+        .seek_for_prev_with_ts(SeekKey::Key(b"k2"), &ts1)
+        .unwrap());
+    assert_eq!(iter.value(), b"v21");
+    // The timestamp sticks for later moves.
+    assert!(iter.prev().unwrap());
+    assert_eq!(iter.value(), b"v11");
+
+    // Nothing is visible before the first write.
+    assert!(!iter
+        .seek_with_ts(SeekKey::Start, &5u64.to_le_bytes())
+        .unwrap());
+
+    let err = iter
+        .seek_with_ts(SeekKey::Key(b"k3"), &10u32.to_le_bytes())
+        .unwrap_err();
+    assert!(err.contains("timestamp size mismatch"), "{}", err);
+}
+
+fn concat_merge(_: &[u8], existing_val: Option<&[u8]>, operands: &mut MergeOperands) -> Vec<u8> {
+    let mut result = existing_val.map_or_else(Vec::new, |v| v.to_vec());
+    for op in operands {
+        result.extend_from_slice(op);
+    }
+    result
+}
+
+#[test]
+fn test_batch_builder() {
+    let path = tempdir_with_prefix("_rust_rocksdb_batch_builder");
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let mut default_opts = ColumnFamilyOptions::new();
+    default_opts.add_merge_operator("concat", concat_merge);
+    let mut ts_opts = ColumnFamilyOptions::new();
+    ts_opts.set_bytewise_comparator_with_u64_ts();
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![("default", default_opts), ("ts", ts_opts)],
+    )
+    .unwrap();
+    db.put(b"k2", b"v2").unwrap();
+    db.put(b"k3", b"a").unwrap();
+
+    let default_cf = db.cf_handle("default").unwrap();
+    let ts_cf = db.cf_handle("ts").unwrap();
+    let mut batch = db.batch();
+    batch
+        .put_cf(default_cf, b"k1", b"v1")
+        .delete_cf(default_cf, b"k2")
+        .merge_cf(default_cf, b"k3", b"b")
+        .put_cf_with_ts(ts_cf, b"k1", &10u64.to_le_bytes(), b"ts_v1");
+    assert_eq!(batch.count(), 4);
+    // Nothing is visible before the commit.
+    assert!(db.get(b"k1").unwrap().is_none());
+    batch.commit(&WriteOptions::new()).unwrap();
+    assert_eq!(batch.count(), 0);
+
+    assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+    assert!(db.get(b"k2").unwrap().is_none());
+    assert_eq!(db.get(b"k3").unwrap().unwrap(), b"ab");
+    assert_eq!(get_at(&db, b"k1", 5), None);
+    assert_eq!(get_at(&db, b"k1", 10).unwrap(), b"ts_v1");
+
+    // A bad operation fails the whole batch.
+    let err = db
+        .batch()
+        .put_cf(default_cf, b"k4", b"v4")
+        .put_cf_with_ts(ts_cf, b"k2", &20u32.to_le_bytes(), b"ts_v2")
+        .commit(&WriteOptions::new())
+        .unwrap_err();
+    assert!(
+        err.contains("timestamp size mismatch: the column family expects 8 bytes, got 4"),
+        "{}",
+        err
+    );
+    assert!(db.get(b"k4").unwrap().is_none());
+}
+
+#[test]
+fn test_get_opt_ts_and_versions() {
+    let path = tempdir_with_prefix("_rust_rocksdb_get_opt_ts_and_versions");
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    let mut cf_opts = ColumnFamilyOptions::new();
+    cf_opts.set_bytewise_comparator_with_u64_ts();
+    let db = DB::open_cf(
+        opts,
+        path.path().to_str().unwrap(),
+        vec![("default", cf_opts)],
+    )
+    .unwrap();
+    for (ts, value) in &[(10u64, "v10"), (20, "v20"), (30, "v30")] {
+        let mut wopts = WriteOptions::new();
+        wopts.set_timestamp(ts.to_le_bytes().to_vec());
+        db.put_opt(b"k", value.as_bytes(), &wopts).unwrap();
+    }
+
+    let mut ropts = ReadOptions::new();
+    ropts.set_timestamp(25u64.to_le_bytes().to_vec());
+    assert_eq!(
+        db.get_opt_ts(b"k", &ropts).unwrap(),
+        Some((b"v20".to_vec(), 20u64.to_le_bytes().to_vec()))
+    );
+    assert_eq!(db.get_opt_ts(b"k2", &ropts).unwrap(), None);
+    let mut ropts = ReadOptions::new();
+    ropts.set_timestamp(u64::MAX.to_le_bytes().to_vec());
+    assert_eq!(
+        db.get_opt_ts(b"k", &ropts).unwrap(),
+        Some((b"v30".to_vec(), 30u64.to_le_bytes().to_vec()))
+    );
+
+    let cf = db.cf_handle("default").unwrap();
+    let mut ropts = ReadOptions::new();
+    ropts.set_timestamp(35u64.to_le_bytes().to_vec());
+    // Without a start timestamp only the latest version is returned.
+    let versions = db.get_versions_cf_opt(cf, b"k", &ropts).unwrap();
+    assert_eq!(
+        versions,
+        vec![(b"v30".to_vec(), 30u64.to_le_bytes().to_vec())]
+    );
+
+    ropts.set_iter_start_ts(15u64.to_le_bytes().to_vec());
+    let versions: Vec<_> = db
+        .get_versions_cf_opt(cf, b"k", &ropts)
+        .unwrap()
+        .into_iter()
+        .map(|(v, ts)| (v, decode_ts(&ts)))
+        .collect();
+    assert_eq!(versions, vec![(b"v30".to_vec(), 30), (b"v20".to_vec(), 20)]);
+
+    ropts.set_iter_start_ts(0u64.to_le_bytes().to_vec());
+    let versions = db.get_versions_cf_opt(cf, b"k", &ropts).unwrap();
+    assert_eq!(versions.len(), 3);
+    assert!(db
+        .get_versions_cf_opt(cf, b"k2", &ropts)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_scan_cf_limit_with_ts() {
+    let path = tempdir_with_prefix("_rust_rocksdb_scan_cf_limit_with_ts");
+    let db = open_ts_db(path.path().to_str().unwrap());
+    put_at(&db, b"k1", b"v1", 10);
+    put_at(&db, b"k2", b"v2", 10);
+    put_at(&db, b"k1", b"v1_new", 20);
+    put_at(&db, b"k3", b"v3", 20);
+    let cf = db.cf_handle("ts").unwrap();
+
+    let mut ropts = ReadOptions::new();
+    ropts.set_timestamp(15u64.to_le_bytes().to_vec());
+    let entries = db
+        .scan_cf_limit(cf, SeekKey::Start, None, 10, ropts)
+        .unwrap();
+    assert_eq!(
+        entries,
+        vec![
+            (b"k1".to_vec(), b"v1".to_vec()),
+            (b"k2".to_vec(), b"v2".to_vec())
+        ]
+    );
+
+    let mut ropts = ReadOptions::new();
+    ropts.set_timestamp(u64::MAX.to_le_bytes().to_vec());
+    let entries = db
+        .scan_cf_limit(cf, SeekKey::Start, Some(b"k3"), 10, ropts)
+        .unwrap();
+    assert_eq!(
+        entries,
+        vec![
+            (b"k1".to_vec(), b"v1_new".to_vec()),
+            (b"k2".to_vec(), b"v2".to_vec())
+        ]
+    );
+}
@@ -0,0 +1,59 @@
+// Copyright 2023 TiKV Project Authors. Licensed under Apache-2.0.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use rocksdb::{DBOptions, WalFilter, WalFilterResult, Writable, WriteBatch, DB};
+
+use super::tempdir_with_prefix;
+
+struct DropKeyFilter {
+    key: &'static [u8],
+    seen: Arc<AtomicUsize>,
+}
+
+impl WalFilter for DropKeyFilter {
+    fn log_record(&self, batch: &WriteBatch) -> WalFilterResult {
+        self.seen.fetch_add(1, Ordering::SeqCst);
+        if batch.iter().any(|(_, _, key, _)| key == self.key) {
+            WalFilterResult::IgnoreCurrentRecord
+        } else {
+            WalFilterResult::Continue
+        }
+    }
+}
+
+#[test]
+fn test_wal_filter() {
+    let path = tempdir_with_prefix("_rust_rocksdb_wal_filter");
+    let path_str = path.path().to_str().unwrap();
+    let mut opts = DBOptions::new();
+    opts.create_if_missing(true);
+    opts.avoid_flush_during_shutdown(true);
+    let db = DB::open(opts, path_str).unwrap();
+    db.put(b"k1", b"v1").unwrap();
+    let batch = WriteBatch::new();
+    batch.put(b"k2", b"v2").unwrap();
+    batch.put(b"drop", b"v").unwrap();
+    db.write(&batch).unwrap();
+    db.put(b"k3", b"v3").unwrap();
+    drop(db);
+
+    let seen = Arc::new(AtomicUsize::new(0));
+    let mut opts = DBOptions::new();
+    opts.set_wal_filter(
+        "drop_key_filter",
+        DropKeyFilter {
+            key: b"drop",
+            seen: seen.clone(),
+        },
+    )
+    .unwrap();
+    let db = DB::open(opts, path_str).unwrap();
+    assert_eq!(seen.load(Ordering::SeqCst), 3);
+    assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+    // The whole batch holding the key is dropped.
+    assert!(db.get(b"drop").unwrap().is_none());
+    assert!(db.get(b"k2").unwrap().is_none());
+    assert_eq!(db.get(b"k3").unwrap().unwrap(), b"v3");
+}
@@ -0,0 +1,61 @@
+// Copyright 2023 TiKV Project Authors. Licensed under Apache-2.0.
+
+use rocksdb::{ReadOptions, SeekKey, Writable, WriteBatchWithIndex, WriteOptions, DB};
+
+use super::tempdir_with_prefix;
+
+#[test]
+fn test_write_batch_with_index() {
+    let path = tempdir_with_prefix("_rust_rocksdb_write_batch_with_index");
+    let db = DB::open_default(path.path().to_str().unwrap()).unwrap();
+    db.put(b"a", b"a_db").unwrap();
+    db.put(b"b", b"b_db").unwrap();
+    db.put(b"d", b"d_db").unwrap();
+
+    let cf = db.cf_handle("default").unwrap();
+    let mut wbwi = WriteBatchWithIndex::new(0, true);
+    wbwi.put_cf(cf, b"b", b"b_old").unwrap();
+    wbwi.put_cf(cf, b"b", b"b_batch").unwrap();
+    wbwi.delete_cf(cf, b"a").unwrap();
+    wbwi.put(b"c", b"c_batch").unwrap();
+    assert_eq!(wbwi.count(), 4);
+
+    // The staged writes are visible before being written.
+    let ropts = ReadOptions::new();
+    let get = |key: &[u8]| {
+        wbwi.get_from_batch_and_db(&db, cf, key, &ropts)
+            .unwrap()
+            .map(|v| v.to_vec())
+    };
+    assert_eq!(get(b"a"), None);
+    assert_eq!(get(b"b").unwrap(), b"b_batch");
+    assert_eq!(get(b"c").unwrap(), b"c_batch");
+    assert_eq!(get(b"d").unwrap(), b"d_db");
+    assert!(db.get(b"c").unwrap().is_none());
+
+    let mut iter = wbwi.iter_with_base_cf(&db, cf, ReadOptions::new());
+    iter.seek(SeekKey::Start).unwrap();
+    let mut kvs = vec![];
+    while iter.valid().unwrap() {
+        kvs.push((iter.key().to_vec(), iter.value().to_vec()));
+        iter.next().unwrap();
+    }
+    drop(iter);
+    assert_eq!(
+        kvs,
+        vec![
+            (b"b".to_vec(), b"b_batch".to_vec()),
+            (b"c".to_vec(), b"c_batch".to_vec()),
+            (b"d".to_vec(), b"d_db".to_vec()),
+        ]
+    );
+
+    db.write_batch_with_index(&wbwi, &WriteOptions::new())
+        .unwrap();
+    assert!(db.get(b"a").unwrap().is_none());
+    assert_eq!(db.get(b"b").unwrap().unwrap(), b"b_batch");
+    assert_eq!(db.get(b"c").unwrap().unwrap(), b"c_batch");
+
+    wbwi.clear();
+    assert!(wbwi.is_empty());
+}